@@ -1,5 +1,4 @@
-use std::io;
-
+use aoc_common::visualize::{self, Cell, Color, Frame, Visualize};
 use aoc_zen_runner_macros::{aoc, generator, solver};
 use itertools::Itertools;
 use regex::Regex;
@@ -15,34 +14,79 @@ pub type CrateStack = Vec<char>;
 
 pub type Board = Vec<CrateStack>;
 
-fn print_board(brd: &Board) {
-    println!("Board [");
-    for (i, stack) in brd.iter().enumerate() {
-        let s = stack.iter().join(" ");
-        println!("  {}  {}", i, s);
+/// A board partway through an arrangement, drawn upright like the puzzle, with the crates that just moved
+/// highlighted.
+struct Arrangement<'a> {
+    board: &'a Board,
+    /// The tallest any stack gets during the arrangement, so every frame is the same size.
+    height: usize,
+    step: usize,
+    total: usize,
+    last_move: Option<&'a MoveInfo>,
+}
+
+impl Visualize for Arrangement<'_> {
+    fn frame(&self) -> Frame {
+        let moved = |stack: usize, level: usize| {
+            self.last_move.is_some_and(|m| {
+                m.dest_stack as usize == stack && level + m.crate_count as usize >= self.board[stack].len()
+            })
+        };
+
+        let mut frame = Frame::new().titled(match self.last_move {
+            None => format!("Initial board, {} moves to go", self.total),
+            Some(m) => format!(
+                "Move {} of {}: {} crates from stack {} to stack {}",
+                self.step,
+                self.total,
+                m.crate_count,
+                m.source_stack + 1,
+                m.dest_stack + 1
+            ),
+        });
+        for level in (0..self.height).rev() {
+            let mut row = Vec::new();
+            for (i, stack) in self.board.iter().enumerate() {
+                match stack.get(level) {
+                    Some(&c) if moved(i, level) => {
+                        row.extend(['[', c, ']'].map(|ch| Cell::colored(ch, Color::Yellow).bold()))
+                    }
+                    Some(&c) => row.extend(['[', c, ']'].map(Cell::new)),
+                    None => row.extend([Cell::new(' '); 3]),
+                }
+                row.push(Cell::new(' '));
+            }
+            frame.push_row(row);
+        }
+        frame.push_text(&(1..=self.board.len()).map(|i| format!(" {} ", i % 10)).join(" "));
+        frame
     }
-    println!("]")
 }
 
-const DEBUG_MOVE: bool = false;
+/// The tallest any stack gets while `moves` are made, counting crates only.
+fn peak_height(board: &Board, moves: &[MoveInfo]) -> usize {
+    let mut heights = board.iter().map(Vec::len).collect_vec();
+    let mut peak = heights.iter().copied().max().unwrap_or(0);
+    for mov in moves {
+        heights[mov.source_stack as usize] -= mov.crate_count as usize;
+        heights[mov.dest_stack as usize] += mov.crate_count as usize;
+        peak = peak.max(heights[mov.dest_stack as usize]);
+    }
+    peak
+}
 
 fn run_arrangement(board: &mut Board, moves: &[MoveInfo], do_reverse: bool) {
-    let stdin = io::stdin();
     let num_moves = moves.len();
+    let height = if visualize::enabled() { peak_height(board, moves) } else { 0 };
+    visualize::show(&Arrangement {
+        board,
+        height,
+        step: 0,
+        total: num_moves,
+        last_move: None,
+    });
     for (i, mov) in moves.iter().enumerate() {
-        if DEBUG_MOVE {
-            println!(
-                "Executing move {} of {}: Move {} crates from stack {} to stack {}",
-                i + 1,
-                num_moves,
-                mov.crate_count,
-                mov.source_stack,
-                mov.dest_stack
-            );
-            println!("Board before move:");
-            print_board(board);
-        }
-        let mut i = {
+        let mut crates = {
             let stack = board.get_mut(mov.source_stack as usize).unwrap();
             let mut xs = stack.drain((stack.len() - mov.crate_count as usize)..).collect_vec();
             if do_reverse {
@@ -51,19 +95,15 @@ fn run_arrangement(board: &mut Board, moves: &[MoveInfo], do_reverse: bool) {
             xs
         };
         {
-            board.get_mut(mov.dest_stack as usize).unwrap().append(&mut i);
-        }
-        if DEBUG_MOVE {
-            println!("Board after move:");
-            print_board(board);
-            println!("\n\n");
-            println!("Continue? ");
-            let mut line_read: String = String::new();
-            _ = stdin.read_line(&mut line_read);
-            if line_read.trim() == "n" {
-                panic!("Aborted by user request.");
-            }
+            board.get_mut(mov.dest_stack as usize).unwrap().append(&mut crates);
         }
+        visualize::show(&Arrangement {
+            board,
+            height,
+            step: i + 1,
+            total: num_moves,
+            last_move: Some(mov),
+        });
     }
 }
 
@@ -119,8 +159,6 @@ pub mod solutions {
                 }
             }
         }
-        // println!("Initial Board:");
-        // print_board(&stacks);
         (stacks, moves)
     }
 
@@ -62,7 +62,7 @@ pub mod solutions {
             .enumerate()
             .scan(0u128, |state, (idx, b)| {
                 *state = (*state << 8 | *b as u128) & 0x0000_FFFF_FFFF_FFFF_FFFF_FFFF_FFFF_FFFFu128;
-                if idx < 14 {
+                if idx < 13 {
                     return Some(0);
                 }
                 let [_, _, a, b, c, d, e, f, g, h, i, j, k, l, m, n] = state.to_be_bytes();
@@ -168,8 +168,29 @@ pub mod solutions {
     }
 }
 
+#[allow(dead_code)]
+pub mod gen {
+    use rand::seq::SliceRandom;
+    use rand::Rng;
+
+    /// Length of the real datastream.
+    pub const REAL_SIZE: usize = 4096;
+
+    /// A datastream of `size` letters from a random-sized alphabet, followed by a start-of-message marker.
+    pub fn input(rng: &mut impl Rng, size: usize) -> String {
+        let mut alphabet: Vec<u8> = (b'a'..=b'z').collect();
+        let k = rng.gen_range(4..=alphabet.len());
+        let mut rv: Vec<u8> = (0..size).map(|_| alphabet[rng.gen_range(0..k)]).collect();
+
+        alphabet.shuffle(rng);
+        rv.extend(&alphabet[..14]);
+        String::from_utf8(rv).unwrap()
+    }
+}
+
 #[cfg(test)]
 mod test {
+    use aoc_common::differential_test;
     use aoc_zen_runner_macros::aoc_case;
 
     #[aoc_case(7, 19)]
@@ -186,4 +207,13 @@ mod test {
 
     #[aoc_case(11, 26)]
     const input_5: &str = "zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw";
+
+    differential_test!(
+        test_solvers_agree,
+        part1: [input_1, input_2, input_3, input_4, input_5],
+        part2: [input_1, input_2, input_3, input_4, input_5],
+        gen: super::gen::input,
+        size: 200,
+        cases: 100,
+    );
 }
@@ -1,5 +1,5 @@
+use aoc_common::grid::Grid;
 use aoc_zen_runner_macros::{aoc, solution};
-use itertools::Itertools;
 use std::cmp::max;
 
 #[aoc(2022, day08)]
@@ -10,10 +10,11 @@ pub mod solutions {
 
     #[solution(part1, draft)]
     pub fn solve_part1(input: &str) -> Day8Output {
-        let grid = input.lines().map(|s| s.bytes().collect_vec()).collect_vec();
+        let grid = Grid::parse_bytes(input);
         let mut count = 0u64;
-        for (row_num, row) in grid.iter().enumerate() {
-            if row_num == 0 || row_num == grid.len() - 1 {
+        for row_num in 0..grid.rows() {
+            let row = grid.row(row_num);
+            if row_num == 0 || row_num == grid.rows() - 1 {
                 count += row.len() as u64;
                 continue;
             }
@@ -23,41 +24,25 @@ pub mod solutions {
                     continue;
                 }
                 // Up
-                let mut vis = true;
-                for r in 0..row_num {
-                    vis = vis && grid[r][col_num] < *cell;
-                }
-                if vis {
+                if grid.col(col_num).take(row_num).all(|t| t < cell) {
                     count += 1;
                     continue;
                 }
 
                 // Down
-                let mut vis = true;
-                for r in row_num + 1..grid.len() {
-                    vis = vis && grid[r][col_num] < *cell;
-                }
-                if vis {
+                if grid.col(col_num).skip(row_num + 1).all(|t| t < cell) {
                     count += 1;
                     continue;
                 }
 
                 // Left
-                let mut vis = true;
-                for c in 0..col_num {
-                    vis = vis && grid[row_num][c] < *cell;
-                }
-                if vis {
+                if row[..col_num].iter().all(|t| t < cell) {
                     count += 1;
                     continue;
                 }
 
                 // Right
-                let mut vis = true;
-                for c in col_num + 1..row.len() {
-                    vis = vis && grid[row_num][c] < *cell;
-                }
-                if vis {
+                if row[col_num + 1..].iter().all(|t| t < cell) {
                     count += 1;
                     continue;
                 }
@@ -67,71 +52,46 @@ pub mod solutions {
         count
     }
 
-    pub fn scenic_score(grid: &Vec<Vec<u8>>, row_num: usize, col_num: usize) -> u64 {
+    pub fn scenic_score(grid: &Grid<u8>, row_num: usize, col_num: usize) -> u64 {
         let mut score = 1u64;
-        let row = &grid[row_num];
+        let row = grid.row(row_num);
         let cell = row[col_num];
 
-        if row_num == 0 || col_num == 0 || row_num == grid.len() || col_num == row.len() {
+        if row_num == 0 || col_num == 0 || row_num == grid.rows() || col_num == row.len() {
             return 0;
         }
 
-        // Up
-        let mut subscore = 0u64;
-        for r in (0..row_num).rev() {
-            let target = grid[r][col_num];
-            subscore += 1;
-            if target >= cell {
-                break;
-            }
-        }
-        score *= subscore;
+        let up = grid.col(col_num).take(row_num).rev();
+        let down = grid.col(col_num).skip(row_num + 1);
+        let left = row[..col_num].iter().rev();
+        let right = row[col_num + 1..].iter();
 
-        // Down
-        let mut subscore = 0u64;
-        for r in row_num + 1..grid.len() {
-            let target = grid[r][col_num];
-            subscore += 1;
-            if target >= cell {
-                break;
-            }
-        }
-        score *= subscore;
+        score *= viewing_distance(up, cell);
+        score *= viewing_distance(down, cell);
+        score *= viewing_distance(left, cell);
+        score *= viewing_distance(right, cell);
 
-        // Left
-        let mut subscore = 0u64;
-        for c in (0..col_num).rev() {
-            let target = grid[row_num][c];
-            subscore += 1;
-            if target >= cell {
-                break;
-            }
-        }
-        score *= subscore;
+        score
+    }
 
-        // Right
+    fn viewing_distance<'a>(trees: impl Iterator<Item = &'a u8>, height: u8) -> u64 {
         let mut subscore = 0u64;
-        for c in col_num + 1..row.len() {
-            let target = grid[row_num][c];
+        for &target in trees {
             subscore += 1;
-            if target >= cell {
+            if target >= height {
                 break;
             }
         }
-        score *= subscore;
-
-        score
+        subscore
     }
 
     #[solution(part2, nestedfor)]
     pub fn solve_part2(input: &str) -> Day8Output {
-        let grid = input.lines().map(|s| s.bytes().collect_vec()).collect_vec();
+        let grid = Grid::parse_bytes(input);
         let mut max_score = 0u64;
-        for (row_num, row) in grid.iter().enumerate() {
-            for (col_num, _cell) in row.iter().enumerate() {
-                let score = scenic_score(&grid, row_num, col_num);
-                max_score = max(max_score, score);
-            }
+        for pos in grid.positions() {
+            let score = scenic_score(&grid, pos.row, pos.col);
+            max_score = max(max_score, score);
         }
 
         max_score
@@ -154,18 +114,9 @@ pub mod test {
 
     #[test]
     pub fn test_score() {
-        let test_in: &str = r#"30373
-        25512
-        65332
-        33549
-        35390
-        "#;
-        let grid = test_in
-            .lines()
-            .map(|s| s.bytes().collect_vec())
-            .collect_vec();
+        let grid = Grid::parse_bytes(TEST_IN);
         assert_eq!(scenic_score(&grid, 1, 2), 4);
         assert_eq!(scenic_score(&grid, 3, 2), 8);
-        assert_eq!(solve_part2(test_in), 8);
+        assert_eq!(solve_part2(TEST_IN), 8);
     }
 }
@@ -1,5 +1,7 @@
+use aoc_common::export::Palette;
+use aoc_common::visualize::{self, Cell, Color, Frame, Visualize};
 use aoc_zen_runner_macros::{aoc, generator, solver};
-use itertools::{Itertools, MinMaxResult};
+use itertools::Itertools;
 use std::collections::HashSet;
 
 #[derive(Debug)]
@@ -16,6 +18,59 @@ pub struct Movement {
     count: u8,
 }
 
+/// The knots of a rope, head first, along with every spot the tail has visited.
+struct Rope<'a> {
+    knots: &'a [(i32, i32)],
+    visited: &'a HashSet<(i32, i32)>,
+    /// The lowest and highest x, then y, the head reaches over every move.
+    extent: ((i32, i32), (i32, i32)),
+}
+
+/// The box the head stays inside over `moves`, starting from the origin. The other knots only ever step towards
+/// the one in front, so they stay inside it too.
+fn extent(moves: &[Movement]) -> ((i32, i32), (i32, i32)) {
+    let (mut x, mut y) = (0, 0);
+    let (mut xs, mut ys) = ((0, 0), (0, 0));
+    for Movement { dir, count } in moves {
+        let count = *count as i32;
+        match dir {
+            Direction::Left => x -= count,
+            Direction::Right => x += count,
+            Direction::Down => y -= count,
+            Direction::Up => y += count,
+        }
+        xs = (xs.0.min(x), xs.1.max(x));
+        ys = (ys.0.min(y), ys.1.max(y));
+    }
+    (xs, ys)
+}
+
+impl Visualize for Rope<'_> {
+    /// Everywhere the head goes, with up at the top, so every frame is the same size. The head is red, the tail
+    /// green.
+    fn frame(&self) -> Frame {
+        let ((x_low, x_high), (y_low, y_high)) = self.extent;
+
+        let mut frame = Frame::new();
+        for y in (y_low..=y_high).rev() {
+            frame.push_row((x_low..=x_high).map(|x| {
+                match self.knots.iter().position(|&k| k == (x, y)) {
+                    Some(0) => Cell::colored('H', Color::Red).bold(),
+                    Some(idx) if idx == self.knots.len() - 1 => Cell::colored('T', Color::Green),
+                    Some(idx) => Cell::new(char::from_digit(idx as u32, 36).unwrap()),
+                    None if self.visited.contains(&(x, y)) => Cell::colored('#', Color::Gray),
+                    None => Cell::new('.'),
+                }
+            }));
+        }
+        frame
+    }
+
+    fn palette(&self) -> Palette {
+        Palette::default().with('#', [0x3a, 0x4a, 0x6a])
+    }
+}
+
 // Solution ---------------------------------------------------------
 // Choose One
 
@@ -98,42 +153,13 @@ pub mod solutions {
         tail_coords.len()
     }
 
-    #[allow(unused)]
-    fn draw_rope(rope: &[(i32, i32)]) {
-        let MinMaxResult::MinMax(x_low, x_high) = rope.iter().map(|k| k.0).minmax() else {
-            panic!("Grid is only one column.")
-        };
-        let MinMaxResult::MinMax(y_low, y_high) = rope.iter().map(|k| k.1).minmax() else {
-            panic!("Grid is only one row.")
-        };
-
-        let offset_x = -x_low; // x == 0 => do nothing, x < 0 => offset right, x > 0 should never happen (start is 0)
-        let offset_y = -y_low;
-
-        let grid_width = (x_high + offset_x + 1) as usize;
-        let grid_height = (y_high + offset_y + 1) as usize;
-
-        let mut grid = vec![vec!['.'; grid_width]; grid_height];
-
-        for (idx, knot) in rope.iter().enumerate() {
-            let (x, y) = knot;
-            grid[(y + offset_y) as usize][(x + offset_x) as usize] =
-                idx.to_string().chars().next().unwrap()
-        }
-
-        println!("Current rope grid:");
-        for row in grid.iter().rev() {
-            println!("{:?}", row);
-        }
-        println!("");
-    }
-
     #[solver(part2, draft)]
     pub fn solve_part2(input: GenData) -> OutData {
         const KNOT_COUNT: usize = 10;
         let mut knots = [(0i32, 0i32); KNOT_COUNT];
         let mut tail_coords: HashSet<(i32, i32)> = HashSet::new();
         tail_coords.insert((0, 0));
+        let extent = if visualize::enabled() { extent(&input) } else { ((0, 0), (0, 0)) };
         for Movement { dir, count } in input {
             let offset = match dir {
                 Direction::Left => (-1, 0),
@@ -164,6 +190,11 @@ pub mod solutions {
                     }
                 }
                 tail_coords.insert(knots[KNOT_COUNT - 1]);
+                visualize::show(&Rope {
+                    knots: &knots,
+                    visited: &tail_coords,
+                    extent,
+                });
             }
         }
 
@@ -174,6 +205,9 @@ pub mod solutions {
 #[cfg(test)]
 pub mod tests {
     use super::solutions::*;
+    use super::{extent, Rope};
+    use aoc_common::visualize::{Frame, Visualize};
+    use std::collections::HashSet;
 
     #[allow(unused)]
     const TEST_IN: &str = r#"
@@ -209,4 +243,21 @@ U 20
         //assert_eq!(solve_part2(&input_generator(TEST_IN)), 1);
         assert_eq!(solve_part2(input_generator(TEST_IN_2)), 36);
     }
+
+    fn size(frame: &Frame) -> (usize, usize) {
+        (frame.rows.len(), frame.rows.iter().map(Vec::len).max().unwrap_or(0))
+    }
+
+    /// The first frame, with every knot at the start, is as big as one with the rope stretched across the corners.
+    #[test]
+    pub fn test_frame_bounds() {
+        let extent = extent(&input_generator(TEST_IN_2));
+        assert_eq!(((-11, 14), (-5, 15)), extent);
+        let visited = HashSet::new();
+        let start = Rope { knots: &[(0, 0); 10], visited: &visited, extent }.frame();
+        let stretched = Rope { knots: &[(-11, 15), (14, -5)], visited: &visited, extent }.frame();
+        assert_eq!(size(&start), size(&stretched));
+        assert_eq!((21, 26), size(&stretched));
+        assert!(stretched.rows[0][0].ch == 'H' && stretched.rows[20][25].ch == 'T');
+    }
 }
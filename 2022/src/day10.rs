@@ -1,4 +1,5 @@
 use aoc_zen_runner_macros::{aoc, generator, solver};
+use aoc_common::ocr;
 use itertools::Itertools;
 
 pub enum Instruction {
@@ -74,8 +75,20 @@ pub mod solutions {
             .sum()
     }
 
+    /// Reads the letters off the CRT. A screen that isn't letters, like the example's stripes, is returned as drawn.
     #[solver(part2, draft)]
     pub fn solve_part2(input: GenData) -> String {
+        let screen = render_screen(input);
+        match ocr::decode(&screen) {
+            Ok(letters) => letters,
+            Err(e) => {
+                eprintln!("Could not read the screen: {}\n{}", e, screen);
+                screen
+            }
+        }
+    }
+
+    pub fn render_screen(input: GenData) -> String {
         let instr_stream = convert_instrs_to_register_stream(input);
         let reg_stream = [(1, 1)].iter().chain(instr_stream.iter());
         let mut instr_iter = reg_stream.tuple_windows::<(_, _)>();
@@ -147,9 +160,7 @@ pub mod solutions {
             cycle_cntr = cycle_cntr + 1;
         }
 
-        disp = format_screen(&disp);
-        println!("{}\n{:->50}", disp, "");
-        disp
+        format_screen(&disp)
     }
 }
 
@@ -331,5 +342,135 @@ pub fn test_part1() {
 
 #[test]
 pub fn test_part2() {
-    assert_eq!(solutions::solve_part2(solutions::input_generator(TEST_IN)), TEST_OUT_2.trim());
+    assert_eq!(solutions::render_screen(solutions::input_generator(TEST_IN)), TEST_OUT_2.trim());
+}
+
+#[cfg(test)]
+mod test {
+    use aoc_zen_runner_macros::aoc_case;
+
+    /// Draws "ZEBRAJOG" one pair of pixels per `addx`.
+    #[aoc_case(11900, "ZEBRAJOG")]
+    const LETTERS_IN: &str = r#"
+addx 1
+addx 4
+addx 0
+addx 1
+addx 3
+addx 1
+addx 5
+addx 0
+addx 0
+addx 6
+addx -1
+addx 0
+addx 7
+addx -1
+addx 5
+addx -1
+addx 0
+addx 5
+addx 0
+addx -32
+addx 0
+addx 2
+addx -2
+addx 1
+addx 4
+addx 5
+addx 2
+addx -2
+addx 3
+addx 2
+addx 5
+addx -2
+addx 1
+addx 4
+addx 2
+addx 5
+addx 2
+addx -2
+addx 3
+addx -33
+addx -3
+addx 5
+addx 0
+addx 0
+addx 4
+addx 1
+addx 5
+addx -2
+addx 3
+addx 2
+addx 5
+addx -2
+addx 1
+addx 4
+addx 2
+addx 5
+addx 2
+addx -2
+addx 1
+addx -33
+addx -2
+addx 6
+addx -2
+addx 1
+addx 4
+addx 5
+addx 2
+addx 0
+addx 0
+addx 4
+addx 2
+addx 0
+addx 1
+addx 4
+addx 2
+addx 5
+addx 2
+addx 2
+addx -1
+addx -38
+addx 0
+addx 7
+addx -2
+addx 1
+addx 4
+addx 5
+addx 2
+addx 2
+addx -2
+addx 3
+addx 5
+addx 2
+addx -2
+addx 3
+addx 2
+addx 5
+addx 2
+addx -2
+addx 3
+addx -36
+addx 1
+addx 4
+addx 0
+addx 1
+addx 3
+addx 1
+addx 5
+addx -2
+addx 3
+addx 2
+addx 5
+addx -2
+addx 4
+addx 0
+addx 6
+addx -1
+addx 0
+addx 5
+addx 1
+addx 0
+"#;
 }
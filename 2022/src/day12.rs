@@ -1,6 +1,10 @@
+use aoc_common::graph_export::{self, GraphExport, Node};
 use aoc_zen_runner_macros::{aoc, generator, solver};
 use itertools::Itertools;
-use petgraph::{algo::dijkstra, prelude::*};
+use petgraph::{
+    algo::{astar, dijkstra},
+    prelude::*,
+};
 #[allow(unused_imports)]
 use std::cmp::max;
 
@@ -18,6 +22,28 @@ pub mod solutions {
     pub type GenData = ParseResults;
     pub type OutData = u64;
 
+    /// The heightmap for export, one node per square in reading order, with `route` highlighted.
+    pub fn export(input: &ParseResults, route: &[NodeIndex<usize>]) -> GraphExport {
+        let mut rv = GraphExport::from_petgraph(
+            &input.graph,
+            |idx, &c| {
+                let node = Node::new(format!("n{}", idx.index()))
+                    .label(c.to_string())
+                    .attr("elevation", c as u8 - b'a');
+                if idx == input.starting {
+                    node.attr("role", "start")
+                } else if idx == input.ending {
+                    node.attr("role", "end")
+                } else {
+                    node
+                }
+            },
+            |_, edge| edge,
+        );
+        rv.highlight_path(&route.iter().map(|idx| idx.index()).collect_vec());
+        rv
+    }
+
     pub fn check_add_edges(
         graph: &mut GraphType,
         cell: &CellType,
@@ -127,6 +153,11 @@ pub mod solutions {
 
         // dbg!(&results);
 
+        graph_export::emit(|| {
+            let (_, route) = astar(graph, starting_idx, |n| n == ending_idx, |_| 1u64, |_| 0).unwrap_or_default();
+            export(&input, &route)
+        });
+
         *results
             .get(&ending_idx)
             .unwrap_or_else(|| panic!("Could not locate ending node in explored part of graph."))
@@ -185,4 +216,15 @@ accszExk
 acctuvwj
 abdefghi
 "#;
+
+    #[test]
+    pub fn test_export() {
+        let input = super::solutions::input_generator(test_in);
+        let export = super::solutions::export(&input, &[]);
+        assert_eq!(40, export.nodes.len());
+        assert!(export.directed);
+        assert_eq!(Some("a"), export.nodes[0].label.as_deref());
+        assert!(export.nodes[0].attrs.contains(&("role".to_string(), "start".to_string())));
+        assert!(export.nodes[21].attrs.contains(&("elevation".to_string(), "25".to_string())));
+    }
 }
@@ -1,13 +1,10 @@
+use aoc_common::grid::Grid;
+use aoc_common::export::Palette;
+use aoc_common::visualize::{self, Color, Frame, Visualize};
 use aoc_zen_runner_macros::{solver, generator, aoc};
-use grid::Grid;
 use itertools::{Itertools, MinMaxResult};
 use std::cmp::max;
-use std::{
-    cmp::min,
-    fmt::Display,
-    io::{stdout, Write},
-};
-use termion::cursor;
+use std::cmp::min;
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
 pub enum Cell {
@@ -17,12 +14,12 @@ pub enum Cell {
     Stone,
 }
 
-impl Display for Cell {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl Cell {
+    fn glyph(self) -> visualize::Cell {
         match self {
-            Nothing => ".".fmt(f),
-            Sand => "o".fmt(f),
-            Stone => "#".fmt(f),
+            Nothing => visualize::Cell::new('.'),
+            Sand => visualize::Cell::colored('o', Color::Yellow),
+            Stone => visualize::Cell::colored('#', Color::Gray),
         }
     }
 }
@@ -35,6 +32,35 @@ pub struct GenData {
     grid: Grid<Cell>,
 }
 
+impl Visualize for GenData {
+    /// The cave from the source down to the floor. Sand can't spread further sideways than it falls, so the columns
+    /// run from the source out to the floor's depth either side, and every frame is the same size.
+    fn frame(&self) -> Frame {
+        let source = 500 - self.x_offset;
+        let bottom = min(self.y_abyss - 2, self.grid.rows() - 1);
+        let (left, right) = (source.saturating_sub(bottom), min(source + bottom, self.grid.cols() - 1));
+
+        let mut frame = Frame::new();
+        for r in 0..=bottom {
+            frame.push_row((left..=right).map(|c| {
+                if r == 0 && c == source && self.grid[(r, c)] == Nothing {
+                    visualize::Cell::colored('+', Color::Red)
+                } else {
+                    self.grid[(r, c)].glyph()
+                }
+            }));
+        }
+        frame
+    }
+
+    fn palette(&self) -> Palette {
+        Palette::default()
+            .with('#', [0x8b, 0x7d, 0x6b])
+            .with('o', [0xe8, 0xc0, 0x6a])
+            .with('+', [0xe0, 0x40, 0x40])
+    }
+}
+
 #[aoc(2022, day14)]
 pub mod solutions {
     use super::*;
@@ -80,7 +106,7 @@ pub mod solutions {
         let x_offset = min_x - 200;
         let y_abyss = max_y + 4;
 
-        let grid: Grid<Cell> = Grid::new(max_y + 6, max_x - x_offset + 200);
+        let grid: Grid<Cell> = Grid::new(max_y + 6, max_x - x_offset + 200, Nothing);
 
         let mut res = GenData {
             grid,
@@ -98,7 +124,7 @@ pub mod solutions {
                     let start = min(p1.1, p2.1);
                     let stop = max(p1.1, p2.1);
                     for y in start..=stop {
-                        let cell = res.grid.get_mut(y, x).unwrap();
+                        let cell = res.grid.get_mut((y, x)).unwrap();
                         *cell = Stone;
                     }
                 } else {
@@ -112,7 +138,7 @@ pub mod solutions {
                     let stop = max(p1.0, p2.0);
                     for x in start..=stop {
                         let x = x - x_offset;
-                        let cell = res.grid.get_mut(y, x).unwrap();
+                        let cell = res.grid.get_mut((y, x)).unwrap();
                         *cell = Stone;
                     }
                 }
@@ -120,12 +146,12 @@ pub mod solutions {
         }
 
         if gen_floor {
-            for c in res.grid.iter_row_mut(max_y + 2) {
+            for c in res.grid.row_mut(max_y + 2) {
                 *c = Stone;
             }
         }
 
-        //draw_cave(&res, "Initial Grid:");
+        visualize::show_titled(&res, || "Initial Grid:".to_string());
 
         res
     }
@@ -158,21 +184,21 @@ pub mod solutions {
                 x_offset
             )
         };
-        let c1 = *grid.get(y + 1, x).unwrap_or_else(|| {
+        let c1 = *grid.get((y + 1, x)).unwrap_or_else(|| {
             panic!(
                 "Coords could not be offset: {:?} {}",
                 (x + x_offset, y),
                 "c1"
             )
         });
-        let c2 = *grid.get(y + 1, x - 1).unwrap_or_else(|| {
+        let c2 = *grid.get((y + 1, x - 1)).unwrap_or_else(|| {
             panic!(
                 "Coords could not be offset: {:?} {}",
                 (x + x_offset, y),
                 "c2"
             )
         });
-        let c3 = *grid.get(y + 1, x + 1).unwrap_or_else(|| {
+        let c3 = *grid.get((y + 1, x + 1)).unwrap_or_else(|| {
             panic!(
                 "Coords could not be offset: {:?} {}",
                 (x + x_offset, y),
@@ -182,30 +208,6 @@ pub mod solutions {
         Some([c1, c2, c3])
     }
 
-    #[allow(unused)]
-    pub fn draw_cave(cave: &GenData, msg: &str) {
-        let GenData {
-            grid,
-            x_offset,
-            y_abyss: _,
-        } = cave;
-
-        let mut s = stdout();
-        println!("{}{}", cursor::Save, msg);
-        for r_idx in 0..grid.rows() {
-            for (c_idx, c) in grid.iter_row(r_idx).enumerate() {
-                if r_idx == 0 && c_idx + x_offset == 500 {
-                    print!("+");
-                } else {
-                    print!("{}", c);
-                }
-            }
-            println!("")
-        }
-        print!("{}", cursor::Restore);
-        s.flush().unwrap();
-    }
-
     #[solver(part1, patterns)]
     pub fn solve_part1(input: &GenData) -> OutData {
         let mut input = GenData {
@@ -214,7 +216,7 @@ pub mod solutions {
             y_abyss: input.y_abyss,
         };
         let mut sand_counter = 0;
-        while input.grid.get(0, 500 - input.x_offset) != Some(&Sand) {
+        while input.grid.get((0, 500 - input.x_offset)) != Some(&Sand) {
             sand_counter += 1;
             let mut current_loc = (500, 0);
             let Some(mut next_locs) = get_next_locs(&input, current_loc) else {
@@ -238,7 +240,6 @@ pub mod solutions {
                     }
                 }
 
-                //draw_cave(&input, &format!("Sand Unit {}", sand_counter));
                 match get_next_locs(&input, current_loc) {
                     Some(nl) => {
                         next_locs = nl;
@@ -251,7 +252,7 @@ pub mod solutions {
 
             let c = input
                 .grid
-                .get_mut(current_loc.1, current_loc.0 - input.x_offset)
+                .get_mut((current_loc.1, current_loc.0 - input.x_offset))
                 .expect("Final location for grain outside of grid.");
             assert_eq!(
                 *c, Nothing,
@@ -259,6 +260,7 @@ pub mod solutions {
                 current_loc, c
             );
             *c = Sand;
+            visualize::show_titled(&input, || format!("Sand Unit {}", sand_counter));
         }
 
         todo!()
@@ -272,7 +274,7 @@ pub mod solutions {
             y_abyss: input.y_abyss,
         };
         let mut sand_counter = 0;
-        while input.grid.get(0, 500 - input.x_offset) != Some(&Sand) {
+        while input.grid.get((0, 500 - input.x_offset)) != Some(&Sand) {
             sand_counter += 1;
             let mut current_loc = (500, 0);
             let Some(mut next_locs) = get_next_locs(&input, current_loc) else {
@@ -296,7 +298,6 @@ pub mod solutions {
                     }
                 }
 
-                //draw_cave(&input, &format!("Sand Unit {}", sand_counter));
                 match get_next_locs(&input, current_loc) {
                     Some(nl) => {
                         next_locs = nl;
@@ -309,7 +310,7 @@ pub mod solutions {
 
             let c = input
                 .grid
-                .get_mut(current_loc.1, current_loc.0 - input.x_offset)
+                .get_mut((current_loc.1, current_loc.0 - input.x_offset))
                 .expect("Final location for grain outside of grid.");
             assert_eq!(
                 *c, Nothing,
@@ -317,6 +318,7 @@ pub mod solutions {
                 current_loc, c
             );
             *c = Sand;
+            visualize::show_titled(&input, || format!("Sand Unit {}", sand_counter));
         }
 
         sand_counter
@@ -325,6 +327,7 @@ pub mod solutions {
 
 #[cfg(test)]
 pub mod test {
+    use super::*;
     use aoc_zen_runner_macros::aoc_case;
 
     #[aoc_case(24, 93)]
@@ -332,4 +335,29 @@ pub mod test {
 498,4 -> 498,6 -> 496,6
 503,4 -> 502,4 -> 502,9 -> 494,9
 "#;
+
+    fn size(frame: &Frame) -> (usize, usize) {
+        (frame.rows.len(), frame.rows.iter().map(Vec::len).max().unwrap_or(0))
+    }
+
+    /// The widest pile sand could make on the part 2 floor fits in the same frame as the empty cave.
+    #[test]
+    pub fn test_frame_bounds() {
+        let mut cave = solutions::input_p2(test_in);
+        let empty = cave.frame();
+        let source = 500 - cave.x_offset;
+        let mut grains = 0;
+        for r in 0..cave.y_abyss - 2 {
+            for c in source - r..=source + r {
+                let cell = cave.grid.get_mut((r, c)).unwrap();
+                if *cell == Nothing {
+                    *cell = Sand;
+                    grains += 1;
+                }
+            }
+        }
+        let full = cave.frame();
+        assert_eq!(size(&empty), size(&full));
+        assert_eq!(grains, full.text().matches('o').count());
+    }
 }
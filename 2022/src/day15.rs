@@ -1,96 +1,8 @@
+use aoc_common::params::Params as _;
+use aoc_common::ranges::RangeSet;
 use aoc_zen_runner_macros::{aoc, generator, solver};
 use std::cmp::{max, min};
 use std::collections::HashSet;
-use std::mem::take;
-use std::ops::{Add, RangeInclusive};
-
-fn range_overlaps<T>(a: &RangeInclusive<T>, b: &RangeInclusive<T>) -> bool
-where
-    T: Ord,
-{
-    !(a.end() < b.start() || b.end() < a.start())
-}
-
-fn range_adjacent<T>(a: &RangeInclusive<T>, b: &RangeInclusive<T>) -> bool
-where
-    T: Ord + Add<T> + From<i32> + Copy,
-    <T as std::ops::Add>::Output: PartialEq<T>,
-{
-    *a.end() + 1.into() == *b.start() || *b.end() + 1.into() == *a.start()
-}
-
-#[derive(Debug)]
-pub struct RangeLine {
-    ranges_set: Vec<RangeInclusive<i64>>,
-}
-
-impl RangeLine {
-    fn new() -> RangeLine {
-        RangeLine {
-            ranges_set: Vec::new(),
-        }
-    }
-
-    fn set_range(&mut self, x: &RangeInclusive<i64>) {
-        let mut x = x.clone();
-        for idx in 0..self.ranges_set.len() {
-            while idx < self.ranges_set.len()
-                && (range_overlaps(&x, &self.ranges_set[idx])
-                    || range_adjacent(&x, &self.ranges_set[idx]))
-            {
-                let old = self.ranges_set.remove(idx);
-                let existing_min = *old.start();
-                let existing_max = *old.end();
-                let new_min = min(*x.start(), existing_min);
-                let new_max = max(*x.end(), existing_max);
-                x = new_min..=new_max;
-            }
-        }
-        self.ranges_set.push(x);
-    }
-
-    fn unset_point(&mut self, pt: i64) {
-        for idx in 0..self.ranges_set.len() {
-            if self.ranges_set[idx].contains(&pt) {
-                let range_a = *self.ranges_set[idx].start()..=(pt - 1);
-                let range_b = (pt + 1)..=*(self.ranges_set[idx].end());
-                self.ranges_set[idx] = range_a;
-                self.set_range(&range_b);
-                return;
-            }
-        }
-    }
-
-    fn len(&self) -> i64 {
-        self.ranges_set
-            .iter()
-            .map(|r| {
-                let e = *r.end();
-                let s = *r.start();
-                (e.abs_diff(s) + 1) as i64
-            })
-            .sum()
-    }
-
-    fn normalize(&mut self) {
-        let ranges = take(&mut self.ranges_set);
-        for r in ranges {
-            self.set_range(&r);
-        }
-    }
-
-    fn ranges_count(&self) -> usize {
-        self.ranges_set.len()
-    }
-
-    fn contains(&self, point: i64) -> bool {
-        self.ranges_set.iter().any(|r| r.contains(&point))
-    }
-
-    fn raw<'a>(&'a self) -> &'a Vec<RangeInclusive<i64>> {
-        &self.ranges_set
-    }
-}
 
 // Solution ---------------------------------------------------------
 #[aoc(2022, day15)]
@@ -100,6 +12,13 @@ pub mod solutions {
     pub type GenData = Vec<(i64, i64, i64, i64)>;
     pub type OutData = usize;
 
+    aoc_common::params! {
+        pub struct Params {
+            target_y: i64 = 2_000_000,
+            max_coord: i64 = 4_000_000,
+        }
+    }
+
     #[generator(day15)]
     pub fn input_generator(input: &str) -> GenData {
         let input = input.trim_start();
@@ -128,10 +47,10 @@ pub mod solutions {
 
     #[solver(part1, loopyloop)]
     pub fn solve_part1(input: GenData) -> OutData {
-        let mut mapped_spots: RangeLine = RangeLine::new();
+        let mut mapped_spots: RangeSet<i64> = RangeSet::new();
         let mut beacon_spots: HashSet<i64> = HashSet::new();
 
-        let target_y = if cfg!(test) { 10i64 } else { 2_000_000i64 };
+        let target_y = Params::current().target_y;
 
         for (sensor_x, sensor_y, beacon_x, beacon_y) in input.iter() {
             let distance = (sensor_x.abs_diff(*beacon_x) + sensor_y.abs_diff(*beacon_y)) as i64;
@@ -140,7 +59,7 @@ pub mod solutions {
                 let min_x = sensor_x - dist_remain;
                 let max_x = sensor_x + dist_remain;
                 let range = min_x..=max_x;
-                mapped_spots.set_range(&range);
+                mapped_spots.insert(&range);
             }
             if *beacon_y == target_y {
                 beacon_spots.insert(*beacon_x);
@@ -148,60 +67,51 @@ pub mod solutions {
         }
 
         for pt in beacon_spots.iter() {
-            mapped_spots.unset_point(*pt);
+            mapped_spots.remove_point(*pt);
         }
 
-        mapped_spots.normalize();
-
         dbg!(&beacon_spots.len());
         dbg!(&mapped_spots.len());
 
         mapped_spots.len() as usize
     }
 
-    #[cfg(test)]
-    const MAX_COORD: u32 = 20;
-
-    #[cfg(not(test))]
-    const MAX_COORD: u32 = 4_000_000;
-
     const TUNING_ADJUST: i64 = 4_000_000;
 
     #[solver(part2, distancecheck)]
     pub fn solve_part2(input: GenData) -> OutData {
-        let mut grid: Vec<RangeLine> = Vec::new();
-        for _ in 0..=MAX_COORD {
-            grid.push(RangeLine::new());
+        let max_coord = Params::current().max_coord;
+        let mut grid: Vec<RangeSet<i64>> = Vec::new();
+        for _ in 0..=max_coord {
+            grid.push(RangeSet::new());
         }
 
         for (sensor_x, sensor_y, beacon_x, beacon_y) in input.iter() {
             let total_distance =
                 (sensor_x.abs_diff(*beacon_x) + sensor_y.abs_diff(*beacon_y)) as i64;
             let min_y = max(sensor_y - total_distance, 0);
-            let max_y = min(sensor_y + total_distance, MAX_COORD as i64);
+            let max_y = min(sensor_y + total_distance, max_coord);
             // For each Y (within bounds) the sensor can cover {
             for y_val in min_y..=max_y {
                 // Do projection to figure out coverage range
                 let distance_remaining = total_distance - y_val.abs_diff(*sensor_y) as i64;
                 let min_x = max(0, *sensor_x - distance_remaining);
-                let max_x = min(MAX_COORD as i64, *sensor_x + distance_remaining);
-                // Set range for that Y's RangeLine
+                let max_x = min(max_coord, *sensor_x + distance_remaining);
+                // Set range for that Y's RangeSet
                 let range = min_x..=max_x;
-                grid[y_val as usize].set_range(&range);
+                grid[y_val as usize].insert(&range);
             }
         }
 
-        dbg!(grid.iter().map(|r| r.raw().capacity()).max());
+        dbg!(grid.iter().map(|r| r.ranges_count()).max());
 
         for (idx, row) in grid
             .iter()
             .enumerate()
-            .filter(|&(_, c)| c.ranges_count() > 1)
+            .filter(|&(_, c)| c.len() <= max_coord as u64)
         {
-            for x_val in 0..MAX_COORD {
-                if !row.contains(x_val as i64) {
-                    return ((x_val as i64) * TUNING_ADJUST) as usize + idx;
-                }
+            if let Some(x_val) = row.complement(0..=max_coord).min() {
+                return (x_val * TUNING_ADJUST) as usize + idx;
             }
         }
 
@@ -209,11 +119,81 @@ pub mod solutions {
     }
 }
 
+#[allow(dead_code)]
+pub mod gen {
+    use rand::Rng;
+
+    /// Sensors in a real input.
+    pub const REAL_SIZE: usize = 30;
+
+    fn manhattan(a: (i64, i64), b: (i64, i64)) -> i64 {
+        (a.0 - b.0).abs() + (a.1 - b.1).abs()
+    }
+
+    fn sensor_line(sensor: (i64, i64), beacon: (i64, i64)) -> String {
+        format!(
+            "Sensor at x={}, y={}: closest beacon is at x={}, y={}\n",
+            sensor.0, sensor.1, beacon.0, beacon.1
+        )
+    }
+
+    /// A random point exactly `radius` away from `center`.
+    fn point_at(rng: &mut impl Rng, center: (i64, i64), radius: i64) -> (i64, i64) {
+        let dx = rng.gen_range(-radius..=radius);
+        let dy = radius - dx.abs();
+        let dy = if rng.gen_bool(0.5) { dy } else { -dy };
+        (center.0 + dx, center.1 + dy)
+    }
+
+    /// `size` sensors scattered over the real input's 0..=4,000,000 square, with beacons up to 1,000,000 away.
+    pub fn input(rng: &mut impl Rng, size: usize) -> String {
+        let mut rv = String::new();
+        for _ in 0..size {
+            let sensor = (rng.gen_range(0..=4_000_000), rng.gen_range(0..=4_000_000));
+            let radius = rng.gen_range(1..=1_000_000);
+            rv.push_str(&sensor_line(sensor, point_at(rng, sensor, radius)));
+        }
+        rv
+    }
+
+    /// Sensors covering every point of `0..=max_coord` squared except one, which is returned with the input.
+    pub fn with_distress_beacon(rng: &mut impl Rng, max_coord: i64) -> (String, (i64, i64)) {
+        let hidden = (rng.gen_range(0..=max_coord), rng.gen_range(0..=max_coord));
+        let mut sensors: Vec<((i64, i64), i64)> = Vec::new();
+        let mut rv = String::new();
+
+        let mut points: Vec<(i64, i64)> = (0..=max_coord).flat_map(|x| (0..=max_coord).map(move |y| (x, y))).collect();
+        rand::seq::SliceRandom::shuffle(&mut points[..], rng);
+
+        for q in points {
+            if q == hidden || sensors.iter().any(|&(s, r)| manhattan(s, q) <= r) {
+                continue;
+            }
+            // Step further away from the hidden point when adjacent, so the radius can stay positive.
+            let sensor = if manhattan(q, hidden) >= 2 {
+                q
+            } else {
+                (2 * q.0 - hidden.0, 2 * q.1 - hidden.1)
+            };
+            let radius = manhattan(sensor, hidden) - 1;
+            sensors.push((sensor, radius));
+            rv.push_str(&sensor_line(sensor, point_at(rng, sensor, radius)));
+        }
+
+        (rv, hidden)
+    }
+}
+
 #[cfg(test)]
 pub mod test {
-    use aoc_zen_runner_macros::aoc_case;
+    use super::gen;
+    use super::solutions::*;
+    use aoc_common::param_case;
+    use aoc_common::params::with_overrides;
+    use rand::Rng;
+
+    param_case!(aoc_test_test_in, test_in, { target_y: 10, max_coord: 20 }, 26, 56000011);
 
-    #[aoc_case(26, 56000011)]
     const test_in: &str = r#"
 Sensor at x=2, y=18: closest beacon is at x=-2, y=15
 Sensor at x=9, y=16: closest beacon is at x=10, y=16
@@ -230,4 +210,40 @@ Sensor at x=16, y=7: closest beacon is at x=15, y=3
 Sensor at x=14, y=3: closest beacon is at x=15, y=3
 Sensor at x=20, y=1: closest beacon is at x=15, y=3
 "#;
+
+    fn brute_force_part1(input: &GenData, target_y: i64) -> usize {
+        let covers = |x: i64| {
+            input.iter().any(|&(sx, sy, bx, by)| {
+                (x - sx).abs() + (target_y - sy).abs() <= (bx - sx).abs() + (by - sy).abs()
+            })
+        };
+        let is_beacon = |x: i64| input.iter().any(|&(_, _, bx, by)| (bx, by) == (x, target_y));
+        (-200..=400).filter(|&x| covers(x) && !is_beacon(x)).count()
+    }
+
+    #[test]
+    pub fn test_part1_matches_brute_force() {
+        for seed in 0..30 {
+            let mut rng = aoc_common::differential::seeded(seed);
+            let (input, _) = gen::with_distress_beacon(&mut rng, 40);
+            let target_y = rng.gen_range(-5..=45);
+            let parsed = input_generator(&input);
+
+            let overrides = vec![("target_y".to_string(), target_y.to_string())];
+            let answer = with_overrides(overrides, || solve_part1(parsed.clone()));
+            assert_eq!(brute_force_part1(&parsed, target_y), answer, "seed {}, target_y {}", seed, target_y);
+        }
+    }
+
+    #[test]
+    pub fn test_part2_finds_distress_beacon() {
+        for seed in 0..30 {
+            let mut rng = aoc_common::differential::seeded(seed);
+            let (input, (x, y)) = gen::with_distress_beacon(&mut rng, 40);
+
+            let overrides = vec![("max_coord".to_string(), "40".to_string())];
+            let answer = with_overrides(overrides, || solve_part2(input_generator(&input)));
+            assert_eq!((x * 4_000_000 + y) as usize, answer, "seed {}", seed);
+        }
+    }
 }
@@ -1,44 +1,174 @@
-use aoc_zen_runner_macros::{aoc, generator, solution, solver};
+use aoc_zen_runner_macros::{aoc, generator, solver};
 
 #[aoc(2022, day17)]
 pub mod solutions {
     use super::*;
+    use aoc_common::cycle::CycleDetector;
+
+    pub type GenData = Vec<Jet>;
+    pub type OutData = i64;
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum Jet {
+        Left,
+        Right,
+    }
+
+    /// Rows of each rock from the bottom up, already two columns in from the left wall. Bit 6 is the left wall.
+    const ROCKS: [&[u8]; 5] = [
+        &[0b0011110],
+        &[0b0001000, 0b0011100, 0b0001000],
+        &[0b0011100, 0b0000100, 0b0000100],
+        &[0b0010000, 0b0010000, 0b0010000, 0b0010000],
+        &[0b0011000, 0b0011000],
+    ];
+
+    /// Rows below the top of the tower that can still matter to a falling rock, for the cycle key.
+    const SKYLINE_DEPTH: u8 = 64;
+
+    /// The tower as one 7-bit row per level, plus where we are in the rock and jet sequences.
+    struct Chamber<'a> {
+        rows: Vec<u8>,
+        jets: &'a [Jet],
+        jet: usize,
+        rock: usize,
+    }
+
+    impl<'a> Chamber<'a> {
+        fn new(jets: &'a [Jet]) -> Chamber<'a> {
+            Chamber {
+                rows: Vec::new(),
+                jets,
+                jet: 0,
+                rock: 0,
+            }
+        }
+
+        fn height(&self) -> i64 {
+            self.rows.len() as i64
+        }
+
+        fn hits(&self, shape: &[u8], y: usize) -> bool {
+            shape.iter().enumerate().any(|(dy, bits)| self.rows.get(y + dy).is_some_and(|row| row & bits != 0))
+        }
+
+        fn drop_rock(&mut self) {
+            let mut shape: Vec<u8> = ROCKS[self.rock % ROCKS.len()].to_vec();
+            self.rock += 1;
+            let mut y = self.rows.len() + 3;
+            loop {
+                let pushed: Option<Vec<u8>> = match self.jets[self.jet] {
+                    Jet::Left if shape.iter().all(|r| r & 0b1000000 == 0) => {
+                        Some(shape.iter().map(|r| r << 1).collect())
+                    }
+                    Jet::Right if shape.iter().all(|r| r & 1 == 0) => Some(shape.iter().map(|r| r >> 1).collect()),
+                    _ => None,
+                };
+                self.jet = (self.jet + 1) % self.jets.len();
+                if let Some(pushed) = pushed.filter(|p| !self.hits(p, y)) {
+                    shape = pushed;
+                }
+                if y == 0 || self.hits(&shape, y - 1) {
+                    break;
+                }
+                y -= 1;
+            }
+
+            for (dy, bits) in shape.iter().enumerate() {
+                if y + dy == self.rows.len() {
+                    self.rows.push(0);
+                }
+                self.rows[y + dy] |= bits;
+            }
+        }
+
+        /// How far down from the top each column's highest block is, capped at `SKYLINE_DEPTH`.
+        fn skyline(&self) -> [u8; 7] {
+            let mut depths = [SKYLINE_DEPTH; 7];
+            for (depth, row) in self.rows.iter().rev().take(SKYLINE_DEPTH as usize).enumerate() {
+                for (col, d) in depths.iter_mut().enumerate() {
+                    if *d == SKYLINE_DEPTH && row & (0b1000000 >> col) != 0 {
+                        *d = depth as u8;
+                    }
+                }
+            }
+            depths
+        }
+    }
+
+    /// Height of the tower after `rocks` rocks, skipping ahead once the falling pattern repeats.
+    fn height_after(jets: &[Jet], rocks: usize) -> i64 {
+        let mut chamber = Chamber::new(jets);
+        let mut detector = CycleDetector::new();
+        let mut heights = vec![0];
+        for n in 1..=rocks {
+            chamber.drop_rock();
+            heights.push(chamber.height());
+            let key = (chamber.rock % ROCKS.len(), chamber.jet, chamber.skyline());
+            if let Some(cycle) = detector.observe(n, key) {
+                return cycle.extrapolate(&heights, rocks);
+            }
+        }
+        chamber.height()
+    }
 
     #[generator(gen)]
-    pub fn input_generator(input: &str) -> Vec<i32> {
-        Vec::new()
+    pub fn input_generator(input: &str) -> GenData {
+        input
+            .trim()
+            .bytes()
+            .map(|b| match b {
+                b'<' => Jet::Left,
+                b'>' => Jet::Right,
+                _ => panic!("Bad jet: {}", b as char),
+            })
+            .collect()
     }
 
     // ----------------------- Part 1 -----------------------
 
-    #[solver(part1, draft_solvr)]
-    pub fn solve_part1(input: Vec<i32>) -> u32 {
-        0
+    #[solver(part1, simulate)]
+    pub fn solve_part1(input: GenData) -> OutData {
+        let mut chamber = Chamber::new(&input);
+        for _ in 0..2022 {
+            chamber.drop_rock();
+        }
+        chamber.height()
     }
 
-    #[solution(part1, draft_soln)]
-    pub fn part1_draft(input: &str) -> u32 {
-        0
+    #[solver(part1, cycle)]
+    pub fn solve_part1_cycle(input: GenData) -> OutData {
+        height_after(&input, 2022)
     }
 
     // ----------------------- Part 2 -----------------------
 
-    #[solver(part2, draft_solvr)]
-    pub fn solve_part2(input: Vec<i32>) -> u32 {
-        0
+    #[solver(part2, cycle)]
+    pub fn solve_part2(input: GenData) -> OutData {
+        height_after(&input, 1_000_000_000_000)
     }
+}
+
+#[allow(dead_code)]
+pub mod gen {
+    use rand::Rng;
 
-    #[solution(part2, draft_soln)]
-    pub fn part2_draft(input: &str) -> u32 {
-        0
+    /// Length of a real jet pattern.
+    pub const REAL_SIZE: usize = 10_091;
+
+    /// `size` random jets.
+    pub fn input(rng: &mut impl Rng, size: usize) -> String {
+        (0..size).map(|_| if rng.gen_bool(0.5) { '<' } else { '>' }).collect()
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use aoc_common::differential_test;
     use aoc_zen_runner_macros::aoc_case;
-    use super::solutions::*;
 
-    #[aoc_case(0, 0)]
-    const input1: &str = "";
+    #[aoc_case(3068, 1514285714288)]
+    const input1: &str = ">>><<><>><<<>><>>><<<>>><<<><<<>><>><<>>";
+
+    differential_test!(test_solvers_agree, part1: [input1], part2: [input1], gen: super::gen::input, size: 500, cases: 20);
 }
@@ -1,3 +1,5 @@
+use aoc_common::export::Palette;
+use aoc_common::visualize::{self, Cell, Color, Frame, Visualize};
 use aoc_zen_runner_macros::{aoc, generator, solver};
 use itertools::{Itertools, MinMaxResult};
 use rayon::prelude::*;
@@ -92,6 +94,40 @@ pub mod solutions {
             .sum()
     }
 
+    /// One z layer of the droplet once the steam has spread: lava is `#`, steam `~`, and air trapped inside `.`.
+    struct Slice<'a> {
+        lava: &'a HashSet<(i64, i64, i64)>,
+        steam: &'a HashSet<(i64, i64, i64)>,
+        x_range: (i64, i64),
+        y_range: (i64, i64),
+        z: i64,
+    }
+
+    impl Visualize for Slice<'_> {
+        fn frame(&self) -> Frame {
+            let mut frame = Frame::new();
+            for y in self.y_range.0..=self.y_range.1 {
+                frame.push_row((self.x_range.0..=self.x_range.1).map(|x| {
+                    if self.lava.contains(&(x, y, self.z)) {
+                        Cell::colored('#', Color::Red)
+                    } else if self.steam.contains(&(x, y, self.z)) {
+                        Cell::colored('~', Color::Cyan)
+                    } else {
+                        Cell::new('.')
+                    }
+                }));
+            }
+            frame
+        }
+
+        fn palette(&self) -> Palette {
+            Palette::default()
+                .with('#', [0xe0, 0x60, 0x20])
+                .with('~', [0x9c, 0xd0, 0xf0])
+                .with('.', [0x30, 0x30, 0x30])
+        }
+    }
+
     #[solver(part2, traverse)]
     pub fn solve_part2_traversal(input: GenData) -> OutData {
         let points: HashSet<(i64, i64, i64)> = input.iter().cloned().collect();
@@ -157,6 +193,17 @@ pub mod solutions {
             );
         }
 
+        for z in (z_min - 1)..=(z_max + 1) {
+            let slice = Slice {
+                lava: &points,
+                steam: &explored,
+                x_range: (x_min - 1, x_max + 1),
+                y_range: (y_min - 1, y_max + 1),
+                z,
+            };
+            visualize::show_titled(&slice, || format!("z = {}", z));
+        }
+
         surface.into_inner() as usize
     }
 }
@@ -1,44 +1,178 @@
-use aoc_zen_runner_macros::{aoc, generator, solution, solver};
+use aoc_zen_runner_macros::{aoc, generator, solver};
+use rayon::prelude::*;
 
 #[aoc(2022, day19)]
 pub mod solutions {
     use super::*;
 
-    #[generator(gen)]
-    pub fn input_generator(input: &str) -> Vec<i32> {
-        Vec::new()
+    pub type GenData = Vec<Blueprint>;
+    pub type OutData = u32;
+
+    const ORE: usize = 0;
+    const CLAY: usize = 1;
+    const OBSIDIAN: usize = 2;
+    const GEODE: usize = 3;
+
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct Blueprint {
+        pub id: u32,
+        /// `costs[robot][resource]`, indexed by `ORE`, `CLAY`, `OBSIDIAN` and `GEODE`.
+        pub costs: [[u32; 4]; 4],
     }
 
-    // ----------------------- Part 1 -----------------------
+    impl Blueprint {
+        /// The most of each resource any one robot needs. Producing more per minute than this is never useful,
+        /// since only one robot can be built at a time. Geodes aren't spent, so they have no cap.
+        fn caps(&self) -> [u32; 4] {
+            let mut caps = [0; 4];
+            for resource in [ORE, CLAY, OBSIDIAN] {
+                caps[resource] = self.costs.iter().map(|c| c[resource]).max().unwrap();
+            }
+            caps
+        }
+    }
 
-    #[solver(part1, draft_solvr)]
-    pub fn solve_part1(input: Vec<i32>) -> u32 {
-        0
+    #[derive(Debug, Clone, Copy)]
+    struct State {
+        time_left: u32,
+        robots: [u32; 4],
+        stock: [u32; 4],
     }
 
-    #[solution(part1, draft_soln)]
-    pub fn part1_draft(input: &str) -> u32 {
-        0
+    impl State {
+        /// Geodes at the end if a geode robot could be built every remaining minute.
+        fn upper_bound(&self) -> u32 {
+            let t = self.time_left;
+            self.stock[GEODE] + self.robots[GEODE] * t + t * t.saturating_sub(1) / 2
+        }
+
+        /// Wait until `robot` is affordable, then build it. `None` if it can't be built in time to help.
+        fn build(&self, blueprint: &Blueprint, robot: usize) -> Option<State> {
+            let cost = &blueprint.costs[robot];
+            let mut wait = 0;
+            for resource in [ORE, CLAY, OBSIDIAN] {
+                if cost[resource] > self.stock[resource] {
+                    if self.robots[resource] == 0 {
+                        return None;
+                    }
+                    let needed = cost[resource] - self.stock[resource];
+                    wait = wait.max(needed.div_ceil(self.robots[resource]));
+                }
+            }
+            // Building takes a minute, and the robot must have at least a minute left to collect.
+            if wait + 1 >= self.time_left {
+                return None;
+            }
+
+            let mut next = *self;
+            next.time_left -= wait + 1;
+            for ((stock, robots), cost) in next.stock.iter_mut().zip(self.robots).zip(cost) {
+                *stock += robots * (wait + 1);
+                *stock -= cost;
+            }
+            next.robots[robot] += 1;
+            Some(next)
+        }
     }
 
-    // ----------------------- Part 2 -----------------------
+    fn search(blueprint: &Blueprint, caps: &[u32; 4], state: State, best: &mut u32) {
+        let idle = state.stock[GEODE] + state.robots[GEODE] * state.time_left;
+        *best = (*best).max(idle);
+        if state.upper_bound() <= *best {
+            return;
+        }
+        for robot in [GEODE, OBSIDIAN, CLAY, ORE] {
+            // Enough robots, or enough stockpiled, to afford the dearest robot every minute from here on.
+            let t = state.time_left;
+            let enough =
+                state.robots[robot] >= caps[robot] || state.robots[robot] * t + state.stock[robot] >= caps[robot] * t;
+            if robot != GEODE && enough {
+                continue;
+            }
+            if let Some(next) = state.build(blueprint, robot) {
+                search(blueprint, caps, next, best);
+            }
+        }
+    }
 
-    #[solver(part2, draft_solvr)]
-    pub fn solve_part2(input: Vec<i32>) -> u32 {
-        0
+    /// Most geodes `blueprint` can open in `minutes`, starting with one ore robot.
+    pub fn max_geodes(blueprint: &Blueprint, minutes: u32) -> u32 {
+        let start = State {
+            time_left: minutes,
+            robots: [1, 0, 0, 0],
+            stock: [0; 4],
+        };
+        let mut best = 0;
+        search(blueprint, &blueprint.caps(), start, &mut best);
+        best
     }
 
-    #[solution(part2, draft_soln)]
-    pub fn part2_draft(input: &str) -> u32 {
-        0
+    /// Blueprints may be wrapped across lines, so each one is read from the numbers after its `Blueprint`.
+    #[generator(gen)]
+    pub fn input_generator(input: &str) -> GenData {
+        input
+            .split("Blueprint")
+            .filter(|s| !s.trim().is_empty())
+            .map(|s| {
+                let n: Vec<u32> = s
+                    .split(|c: char| !c.is_ascii_digit())
+                    .filter(|w| !w.is_empty())
+                    .map(|w| w.parse().unwrap())
+                    .collect();
+                let [id, ore_ore, clay_ore, obs_ore, obs_clay, geode_ore, geode_obs] = n[..] else {
+                    panic!("Bad blueprint: {}", s);
+                };
+                Blueprint {
+                    id,
+                    costs: [
+                        [ore_ore, 0, 0, 0],
+                        [clay_ore, 0, 0, 0],
+                        [obs_ore, obs_clay, 0, 0],
+                        [geode_ore, 0, geode_obs, 0],
+                    ],
+                }
+            })
+            .collect()
+    }
+
+    // ----------------------- Part 1 -----------------------
+
+    #[solver(part1, rayon)]
+    pub fn solve_part1(input: GenData) -> OutData {
+        input.par_iter().map(|b| b.id * max_geodes(b, 24)).sum()
+    }
+
+    // ----------------------- Part 2 -----------------------
+
+    #[solver(part2, rayon)]
+    pub fn solve_part2(input: GenData) -> OutData {
+        input.par_iter().take(3).map(|b| max_geodes(b, 32)).product()
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use aoc_zen_runner_macros::aoc_case;
     use super::solutions::*;
+    use aoc_zen_runner_macros::aoc_case;
+
+    #[aoc_case(33, 3472)]
+    const input1: &str = "Blueprint 1:
+  Each ore robot costs 4 ore.
+  Each clay robot costs 2 ore.
+  Each obsidian robot costs 3 ore and 14 clay.
+  Each geode robot costs 2 ore and 7 obsidian.
 
-    #[aoc_case(0, 0)]
-    const input1: &str = "";
+Blueprint 2:
+  Each ore robot costs 2 ore.
+  Each clay robot costs 3 ore.
+  Each obsidian robot costs 3 ore and 8 clay.
+  Each geode robot costs 3 ore and 12 obsidian.
+";
+
+    #[test]
+    pub fn test_max_geodes() {
+        let blueprints = input_generator(input1);
+        assert_eq!(vec![9, 12], blueprints.iter().map(|b| max_geodes(b, 24)).collect::<Vec<_>>());
+        assert_eq!(vec![56, 62], blueprints.iter().map(|b| max_geodes(b, 32)).collect::<Vec<_>>());
+    }
 }
@@ -64,9 +64,36 @@ pub mod solutions {
     }
 }
 
+#[allow(dead_code)]
+pub mod gen {
+    use rand::Rng;
+
+    /// Numbers in a real input.
+    pub const REAL_SIZE: usize = 5000;
+
+    /// `size` numbers in -10,000..=10,000 with repeats allowed but exactly one zero, like the real input.
+    pub fn input(rng: &mut impl Rng, size: usize) -> String {
+        let zero_at = rng.gen_range(0..size);
+        let mut rv = String::new();
+        for i in 0..size {
+            let n = if i == zero_at {
+                0
+            } else {
+                let n = rng.gen_range(1..=10_000);
+                if rng.gen_bool(0.5) { n } else { -n }
+            };
+            rv.push_str(&format!("{}\n", n));
+        }
+        rv
+    }
+}
+
 #[cfg(test)]
 mod test {
+    use super::gen;
+    use super::solutions::*;
     use aoc_zen_runner_macros::aoc_case;
+    use std::collections::VecDeque;
 
     #[aoc_case(3, 1623178306)]
     const test_in: &str = r#"
@@ -78,4 +105,32 @@ mod test {
 0
 4
 "#;
+
+    /// Grove coordinates via a rotating deque, independent of `mix`.
+    fn reference(input: &str, key: i64, rounds: usize) -> i64 {
+        let numbers: Vec<i64> = input.lines().map(|ln| ln.parse::<i64>().unwrap() * key).collect();
+        let mut ring: VecDeque<usize> = (0..numbers.len()).collect();
+        for _ in 0..rounds {
+            for (idx, &n) in numbers.iter().enumerate() {
+                let pos = ring.iter().position(|&i| i == idx).unwrap();
+                ring.rotate_left(pos);
+                ring.pop_front();
+                let shift = n.rem_euclid(ring.len() as i64) as usize;
+                ring.rotate_left(shift);
+                ring.push_front(idx);
+            }
+        }
+
+        let zero = ring.iter().position(|&i| numbers[i] == 0).unwrap();
+        [1000, 2000, 3000].iter().map(|off| numbers[ring[(zero + off) % ring.len()]]).sum()
+    }
+
+    #[test]
+    pub fn test_matches_reference() {
+        for seed in 0..20 {
+            let input = gen::input(&mut aoc_common::differential::seeded(seed), 100);
+            assert_eq!(reference(&input, 1, 1), solve_part1(&input), "Part 1, seed {}", seed);
+            assert_eq!(reference(&input, 811589153, 10), solve_part2(&input), "Part 2, seed {}", seed);
+        }
+    }
 }
@@ -0,0 +1,147 @@
+use std::collections::HashMap;
+
+use aoc_zen_runner_macros::{aoc, generator, solver};
+
+#[aoc(2022, day21)]
+pub mod solutions {
+    use super::*;
+
+    pub type GenData = Troop;
+    pub type OutData = i64;
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum Job {
+        Number(i64),
+        /// Left operand, operator and right operand, as indexes into `Troop::jobs`.
+        Op(usize, u8, usize),
+    }
+
+    #[derive(Debug, Clone)]
+    pub struct Troop {
+        pub names: Vec<String>,
+        pub jobs: Vec<Job>,
+        pub root: usize,
+        pub humn: usize,
+    }
+
+    impl Troop {
+        fn eval(&self, monkey: usize) -> i64 {
+            match self.jobs[monkey] {
+                Job::Number(n) => n,
+                Job::Op(a, op, b) => apply(self.eval(a), op, self.eval(b)),
+            }
+        }
+
+        fn depends_on_humn(&self, monkey: usize) -> bool {
+            monkey == self.humn
+                || matches!(self.jobs[monkey], Job::Op(a, _, b) if self.depends_on_humn(a) || self.depends_on_humn(b))
+        }
+
+        /// The number `humn` must yell for `monkey` to yell `target`. `humn` appears only once in the tree, so at
+        /// every operation one side is a known number and the other can be solved for by undoing the operation.
+        fn solve_for_humn(&self, monkey: usize, target: i64) -> i64 {
+            let Job::Op(a, op, b) = self.jobs[monkey] else {
+                assert_eq!(self.humn, monkey, "Reached a number that doesn't depend on humn");
+                return target;
+            };
+            if self.depends_on_humn(a) {
+                let known = self.eval(b);
+                let next = match op {
+                    b'+' => target - known,
+                    b'-' => target + known,
+                    b'*' => target / known,
+                    b'/' => target * known,
+                    _ => unreachable!(),
+                };
+                self.solve_for_humn(a, next)
+            } else {
+                let known = self.eval(a);
+                let next = match op {
+                    b'+' => target - known,
+                    b'-' => known - target,
+                    b'*' => target / known,
+                    b'/' => known / target,
+                    _ => unreachable!(),
+                };
+                self.solve_for_humn(b, next)
+            }
+        }
+    }
+
+    fn apply(a: i64, op: u8, b: i64) -> i64 {
+        match op {
+            b'+' => a + b,
+            b'-' => a - b,
+            b'*' => a * b,
+            b'/' => a / b,
+            _ => panic!("Unknown operator: {}", op as char),
+        }
+    }
+
+    #[generator(gen)]
+    pub fn input_generator(input: &str) -> GenData {
+        let lines: Vec<(&str, &str)> = input
+            .lines()
+            .filter(|ln| !ln.trim().is_empty())
+            .map(|ln| ln.trim().split_once(": ").expect("Bad monkey"))
+            .collect();
+        let index: HashMap<&str, usize> = lines.iter().enumerate().map(|(i, &(name, _))| (name, i)).collect();
+        let jobs = lines
+            .iter()
+            .map(|&(_, job)| match job.split_ascii_whitespace().collect::<Vec<_>>()[..] {
+                [a, op, b] => Job::Op(index[a], op.as_bytes()[0], index[b]),
+                [n] => Job::Number(n.parse().unwrap()),
+                _ => panic!("Bad job: {}", job),
+            })
+            .collect();
+        Troop {
+            names: lines.iter().map(|&(name, _)| name.to_string()).collect(),
+            jobs,
+            root: index["root"],
+            humn: index["humn"],
+        }
+    }
+
+    // ----------------------- Part 1 -----------------------
+
+    #[solver(part1, tree)]
+    pub fn solve_part1(input: GenData) -> OutData {
+        input.eval(input.root)
+    }
+
+    // ----------------------- Part 2 -----------------------
+
+    #[solver(part2, invert)]
+    pub fn solve_part2(input: GenData) -> OutData {
+        let Job::Op(a, _, b) = input.jobs[input.root] else {
+            panic!("root should compare two monkeys");
+        };
+        if input.depends_on_humn(a) {
+            input.solve_for_humn(a, input.eval(b))
+        } else {
+            input.solve_for_humn(b, input.eval(a))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_zen_runner_macros::aoc_case;
+
+    #[aoc_case(152, 301)]
+    const INPUT1: &str = "root: pppw + sjmn
+dbpl: 5
+cczh: sllz + lgvd
+zczc: 2
+ptdq: humn - dvpt
+dvpt: 3
+lfqf: 4
+humn: 5
+ljgn: 2
+sjmn: drzm * dbpl
+sllz: 4
+pppw: cczh / lfqf
+lgvd: ljgn * ptdq
+drzm: hmdt - zczc
+hmdt: 32";
+}
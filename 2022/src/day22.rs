@@ -0,0 +1,298 @@
+use aoc_common::grid::{Dir, Grid, Pos};
+use aoc_zen_runner_macros::{aoc, generator, solver};
+
+#[aoc(2022, day22)]
+pub mod solutions {
+    use super::*;
+
+    pub type GenData = Notes;
+    pub type OutData = usize;
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum Step {
+        Forward(usize),
+        Left,
+        Right,
+    }
+
+    #[derive(Debug, Clone)]
+    pub struct Notes {
+        /// The board, padded with `b' '` to a rectangle.
+        pub board: Grid<u8>,
+        pub path: Vec<Step>,
+    }
+
+    type Vec3 = [i32; 3];
+
+    fn dot(a: Vec3, b: Vec3) -> i32 {
+        a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+    }
+
+    fn neg(a: Vec3) -> Vec3 {
+        a.map(|x| -x)
+    }
+
+    /// Where one face of the net ends up once folded: its outward normal, and the directions its columns and rows
+    /// run in.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    struct Face {
+        normal: Vec3,
+        right: Vec3,
+        down: Vec3,
+    }
+
+    impl Face {
+        /// The face across the edge in `dir`, folded down from this one.
+        fn fold(self, dir: Dir) -> Face {
+            let Face { normal, right, down } = self;
+            match dir {
+                Dir::East => Face {
+                    normal: right,
+                    right: neg(normal),
+                    down,
+                },
+                Dir::West => Face {
+                    normal: neg(right),
+                    right: normal,
+                    down,
+                },
+                Dir::South => Face {
+                    normal: down,
+                    right,
+                    down: neg(normal),
+                },
+                Dir::North => Face {
+                    normal: neg(down),
+                    right,
+                    down: normal,
+                },
+            }
+        }
+
+        fn vector(self, dir: Dir) -> Vec3 {
+            match dir {
+                Dir::East => self.right,
+                Dir::South => self.down,
+                Dir::West => neg(self.right),
+                Dir::North => neg(self.down),
+            }
+        }
+    }
+
+    /// How to wrap around the edge of the board.
+    trait Wrap {
+        /// Where walking off the board from `pos` facing `dir` lands, and the new facing.
+        fn wrap(&self, board: &Grid<u8>, pos: Pos, dir: Dir) -> (Pos, Dir);
+    }
+
+    /// Part 1: come back in from the far side of the same row or column.
+    struct Flat;
+
+    impl Wrap for Flat {
+        fn wrap(&self, board: &Grid<u8>, pos: Pos, dir: Dir) -> (Pos, Dir) {
+            let mut back = pos;
+            while let Some(p) = board.step(back, -dir).filter(|&p| board[p] != b' ') {
+                back = p;
+            }
+            (back, dir)
+        }
+    }
+
+    /// Part 2: fold the board into a cube and walk over the edge onto the neighbouring face.
+    struct Cube {
+        size: usize,
+        /// The folded face for each `size` by `size` block of the board, if it's part of the net.
+        faces: Grid<Option<Face>>,
+    }
+
+    impl Cube {
+        fn new(board: &Grid<u8>) -> Cube {
+            let tiles = board.iter().filter(|&&b| b != b' ').count();
+            let size = ((tiles / 6) as f64).sqrt() as usize;
+            assert_eq!(tiles, 6 * size * size, "Board isn't the net of a cube");
+            let mut faces: Grid<Option<Face>> = Grid::new(board.rows() / size, board.cols() / size, None);
+            let first = faces
+                .positions()
+                .find(|&f| board[(f.row * size, f.col * size)] != b' ')
+                .unwrap();
+            faces[first] = Some(Face {
+                normal: [0, 0, -1],
+                right: [1, 0, 0],
+                down: [0, 1, 0],
+            });
+            let mut stack = vec![first];
+            while let Some(f) = stack.pop() {
+                let face = faces[f].unwrap();
+                for (dir, n) in faces.neighbors4(f).collect::<Vec<_>>() {
+                    if faces[n].is_none() && board[(n.row * size, n.col * size)] != b' ' {
+                        faces[n] = Some(face.fold(dir));
+                        stack.push(n);
+                    }
+                }
+            }
+            Cube { size, faces }
+        }
+    }
+
+    impl Wrap for Cube {
+        /// Works in doubled 3D coordinates, where the cube spans `-size..=size` on each axis and cell centres have
+        /// odd coordinates along their face. Stepping over the edge moves the point out along the direction of
+        /// travel and in along the old face's normal.
+        fn wrap(&self, _board: &Grid<u8>, pos: Pos, dir: Dir) -> (Pos, Dir) {
+            let n = self.size as i32;
+            let block = Pos::new(pos.row / self.size, pos.col / self.size);
+            let from = self.faces[block].unwrap();
+            let (local_row, local_col) = ((pos.row % self.size) as i32, (pos.col % self.size) as i32);
+            let travel = from.vector(dir);
+            let point: Vec3 = std::array::from_fn(|i| {
+                n * from.normal[i]
+                    + (2 * local_col - (n - 1)) * from.right[i]
+                    + (2 * local_row - (n - 1)) * from.down[i]
+                    + travel[i]
+                    - from.normal[i]
+            });
+
+            let (to_block, to) = self
+                .faces
+                .positions()
+                .find_map(|f| self.faces[f].filter(|face| face.normal == travel).map(|face| (f, face)))
+                .unwrap();
+            let new_dir = Dir::ALL
+                .into_iter()
+                .find(|&d| to.vector(d) == neg(from.normal))
+                .unwrap();
+            let row = ((dot(point, to.down) + n - 1) / 2) as usize;
+            let col = ((dot(point, to.right) + n - 1) / 2) as usize;
+            (
+                Pos::new(to_block.row * self.size + row, to_block.col * self.size + col),
+                new_dir,
+            )
+        }
+    }
+
+    fn password(notes: &Notes, wrap: &impl Wrap) -> usize {
+        let board = &notes.board;
+        let mut pos = Pos::new(
+            0,
+            board
+                .row(0)
+                .iter()
+                .position(|&b| b == b'.')
+                .expect("No open tile on top row"),
+        );
+        let mut dir = Dir::East;
+        for &step in &notes.path {
+            match step {
+                Step::Left => dir = dir.rotate_ccw(),
+                Step::Right => dir = dir.rotate_cw(),
+                Step::Forward(n) => {
+                    for _ in 0..n {
+                        let (next, next_dir) = match board.step(pos, dir).filter(|&p| board[p] != b' ') {
+                            Some(p) => (p, dir),
+                            None => wrap.wrap(board, pos, dir),
+                        };
+                        if board[next] == b'#' {
+                            break;
+                        }
+                        (pos, dir) = (next, next_dir);
+                    }
+                }
+            }
+        }
+        let facing = match dir {
+            Dir::East => 0,
+            Dir::South => 1,
+            Dir::West => 2,
+            Dir::North => 3,
+        };
+        1000 * (pos.row + 1) + 4 * (pos.col + 1) + facing
+    }
+
+    #[generator(gen)]
+    pub fn input_generator(input: &str) -> GenData {
+        let (map, path) = input
+            .trim_start_matches('\n')
+            .split_once("\n\n")
+            .expect("No blank line before the path");
+        let width = map.lines().map(str::len).max().unwrap_or(0);
+        let cells: Vec<u8> = map
+            .lines()
+            .flat_map(|ln| format!("{:width$}", ln).into_bytes())
+            .collect();
+
+        let mut steps = Vec::new();
+        let mut n = 0;
+        for b in path.trim().bytes() {
+            match b {
+                b'0'..=b'9' => n = n * 10 + (b - b'0') as usize,
+                b'L' | b'R' => {
+                    steps.push(Step::Forward(n));
+                    steps.push(if b == b'L' { Step::Left } else { Step::Right });
+                    n = 0;
+                }
+                _ => panic!("Bad path step: {}", b as char),
+            }
+        }
+        steps.push(Step::Forward(n));
+        Notes {
+            board: Grid::from_vec(cells, width),
+            path: steps,
+        }
+    }
+
+    // ----------------------- Part 1 -----------------------
+
+    #[solver(part1, walk)]
+    pub fn solve_part1(input: GenData) -> OutData {
+        password(&input, &Flat)
+    }
+
+    // ----------------------- Part 2 -----------------------
+
+    #[solver(part2, walk)]
+    pub fn solve_part2(input: GenData) -> OutData {
+        password(&input, &Cube::new(&input.board))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::solutions::*;
+    use aoc_zen_runner_macros::aoc_case;
+
+    #[aoc_case(6032, 5031)]
+    const INPUT1: &str = "        ...#
+        .#..
+        #...
+        ....
+...#.......#
+........#...
+..#....#....
+..........#.
+        ...#....
+        .....#..
+        .#......
+        ......#.
+
+10R5L5R10L4R5L5";
+
+    /// A wall-free cube of side 2, laid out in the same net as the real input.
+    const OPEN_NET: &str = "  ....
+  ....
+  ..
+  ..
+....
+....
+..
+..
+";
+
+    #[test]
+    pub fn test_cube_round_trips() {
+        // Four sides' worth of steps in a straight line always leads back to the start, facing the same way.
+        for (path, expected) in [("8", 1012), ("R8", 1013), ("L8", 1015), ("2R8", 1021), ("3R1R8", 2026)] {
+            let notes = input_generator(&format!("{}\n{}", OPEN_NET, path));
+            assert_eq!(expected, solve_part2(notes), "path {}", path);
+        }
+    }
+}
@@ -0,0 +1,108 @@
+use std::collections::{HashMap, HashSet};
+
+use aoc_zen_runner_macros::{aoc, generator, solver};
+
+#[aoc(2022, day23)]
+pub mod solutions {
+    use super::*;
+
+    pub type GenData = HashSet<(i32, i32)>;
+    pub type OutData = usize;
+
+    /// A (row, col) step.
+    type Offset = (i32, i32);
+
+    /// The direction each proposal moves in, and the three neighbours that must be empty for it.
+    const PROPOSALS: [(Offset, [Offset; 3]); 4] = [
+        ((-1, 0), [(-1, -1), (-1, 0), (-1, 1)]),
+        ((1, 0), [(1, -1), (1, 0), (1, 1)]),
+        ((0, -1), [(-1, -1), (0, -1), (1, -1)]),
+        ((0, 1), [(-1, 1), (0, 1), (1, 1)]),
+    ];
+
+    /// Run one round, with proposals considered starting from `PROPOSALS[round % 4]`. Returns whether any elf moved.
+    fn spread(elves: &mut HashSet<(i32, i32)>, round: usize) -> bool {
+        let occupied = |(r, c): (i32, i32), (dr, dc): (i32, i32)| elves.contains(&(r + dr, c + dc));
+        let mut targets: HashMap<(i32, i32), Vec<(i32, i32)>> = HashMap::new();
+        for &elf in elves.iter() {
+            let crowded = (-1..=1).any(|dr| (-1..=1).any(|dc| (dr, dc) != (0, 0) && occupied(elf, (dr, dc))));
+            if !crowded {
+                continue;
+            }
+            let proposal = (0..4)
+                .map(|i| PROPOSALS[(round + i) % 4])
+                .find(|(_, checks)| checks.iter().all(|&d| !occupied(elf, d)));
+            if let Some(((dr, dc), _)) = proposal {
+                targets.entry((elf.0 + dr, elf.1 + dc)).or_default().push(elf);
+            }
+        }
+
+        let mut moved = false;
+        for (target, from) in targets {
+            if let [elf] = from[..] {
+                elves.remove(&elf);
+                elves.insert(target);
+                moved = true;
+            }
+        }
+        moved
+    }
+
+    #[generator(gen)]
+    pub fn input_generator(input: &str) -> GenData {
+        input
+            .lines()
+            .filter(|ln| !ln.trim().is_empty())
+            .enumerate()
+            .flat_map(|(r, ln)| {
+                ln.bytes()
+                    .enumerate()
+                    .filter(|&(_, b)| b == b'#')
+                    .map(move |(c, _)| (r as i32, c as i32))
+            })
+            .collect()
+    }
+
+    // ----------------------- Part 1 -----------------------
+
+    #[solver(part1, sets)]
+    pub fn solve_part1(input: GenData) -> OutData {
+        let mut elves = input;
+        for round in 0..10 {
+            spread(&mut elves, round);
+        }
+        let rows = elves.iter().map(|e| e.0).max().unwrap() - elves.iter().map(|e| e.0).min().unwrap() + 1;
+        let cols = elves.iter().map(|e| e.1).max().unwrap() - elves.iter().map(|e| e.1).min().unwrap() + 1;
+        (rows * cols) as usize - elves.len()
+    }
+
+    // ----------------------- Part 2 -----------------------
+
+    #[solver(part2, sets)]
+    pub fn solve_part2(input: GenData) -> OutData {
+        let mut elves = input;
+        (0..).find(|&round| !spread(&mut elves, round)).unwrap() + 1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_zen_runner_macros::aoc_case;
+
+    #[aoc_case(110, 20)]
+    const INPUT1: &str = "....#..
+..###.#
+#...#.#
+.#...##
+#.###..
+##.#.##
+.#..#..";
+
+    #[aoc_case(25, 4)]
+    const INPUT2: &str = ".....
+..##.
+..#..
+.....
+..##.
+.....";
+}
@@ -0,0 +1,103 @@
+use aoc_zen_runner_macros::{aoc, generator, solver};
+
+#[aoc(2022, day24)]
+pub mod solutions {
+    use super::*;
+
+    pub type GenData = Valley;
+    pub type OutData = usize;
+
+    /// The valley inside its walls. Blizzards move in straight lines and wrap, so rather than track them, check
+    /// whether any blizzard would be on a tile at a given minute by looking back along its row and column.
+    #[derive(Debug, Clone)]
+    pub struct Valley {
+        /// The starting layout, without the walls, one row per line.
+        pub tiles: Vec<Vec<u8>>,
+        pub rows: usize,
+        pub cols: usize,
+    }
+
+    impl Valley {
+        fn clear(&self, r: usize, c: usize, minute: usize) -> bool {
+            let (rows, cols) = (self.rows, self.cols);
+            self.tiles[r][(c + cols - minute % cols) % cols] != b'>'
+                && self.tiles[r][(c + minute) % cols] != b'<'
+                && self.tiles[(r + rows - minute % rows) % rows][c] != b'v'
+                && self.tiles[(r + minute) % rows][c] != b'^'
+        }
+
+        /// Minutes after `start` before the expedition can reach the exit below (or with `up`, the entrance above),
+        /// tracking every tile the expedition could be on at each minute. Entering or leaving the valley only
+        /// happens through the gap in the wall, above the top left tile and below the bottom right one.
+        fn cross(&self, start: usize, up: bool) -> usize {
+            let (entry, exit) = if up {
+                ((self.rows - 1, self.cols - 1), (0, 0))
+            } else {
+                ((0, 0), (self.rows - 1, self.cols - 1))
+            };
+            let mut reachable = vec![vec![false; self.cols]; self.rows];
+            for minute in start + 1.. {
+                let mut next = vec![vec![false; self.cols]; self.rows];
+                for r in 0..self.rows {
+                    for c in 0..self.cols {
+                        let from_here = reachable[r][c]
+                            || r > 0 && reachable[r - 1][c]
+                            || r + 1 < self.rows && reachable[r + 1][c]
+                            || c > 0 && reachable[r][c - 1]
+                            || c + 1 < self.cols && reachable[r][c + 1]
+                            || (r, c) == entry;
+                        next[r][c] = from_here && self.clear(r, c, minute);
+                    }
+                }
+                reachable = next;
+                if reachable[exit.0][exit.1] {
+                    return minute + 1;
+                }
+            }
+            unreachable!()
+        }
+    }
+
+    #[generator(gen)]
+    pub fn input_generator(input: &str) -> GenData {
+        let lines: Vec<&str> = input.lines().map(str::trim).filter(|ln| !ln.is_empty()).collect();
+        let tiles: Vec<Vec<u8>> = lines[1..lines.len() - 1]
+            .iter()
+            .map(|ln| ln.as_bytes()[1..ln.len() - 1].to_vec())
+            .collect();
+        Valley {
+            rows: tiles.len(),
+            cols: tiles[0].len(),
+            tiles,
+        }
+    }
+
+    // ----------------------- Part 1 -----------------------
+
+    #[solver(part1, frontier)]
+    pub fn solve_part1(input: GenData) -> OutData {
+        input.cross(0, false)
+    }
+
+    // ----------------------- Part 2 -----------------------
+
+    #[solver(part2, frontier)]
+    pub fn solve_part2(input: GenData) -> OutData {
+        let there = input.cross(0, false);
+        let back = input.cross(there, true);
+        input.cross(back, false)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_zen_runner_macros::aoc_case;
+
+    #[aoc_case(18, 54)]
+    const INPUT1: &str = "#.######
+#>>.<^<#
+#.<..<<#
+#>v.><>#
+#<^v^^>#
+######.#";
+}
@@ -0,0 +1,99 @@
+use aoc_zen_runner_macros::{aoc, generator, solver};
+
+#[aoc(2022, day25)]
+pub mod solutions {
+    use super::*;
+
+    pub type GenData = Vec<i64>;
+    pub type OutData = String;
+
+    pub fn from_snafu(s: &str) -> i64 {
+        s.bytes().fold(0, |acc, b| {
+            acc * 5
+                + match b {
+                    b'2' => 2,
+                    b'1' => 1,
+                    b'0' => 0,
+                    b'-' => -1,
+                    b'=' => -2,
+                    _ => panic!("Bad SNAFU digit: {}", b as char),
+                }
+        })
+    }
+
+    /// Balanced base 5: a digit of 3 or 4 becomes -2 or -1 with a carry into the next place.
+    pub fn to_snafu(mut n: i64) -> String {
+        if n == 0 {
+            return "0".to_string();
+        }
+        let mut digits = Vec::new();
+        while n != 0 {
+            let (digit, carry) = match n.rem_euclid(5) {
+                3 => (b'=', 1),
+                4 => (b'-', 1),
+                d => (b'0' + d as u8, 0),
+            };
+            digits.push(digit);
+            n = n.div_euclid(5) + carry;
+        }
+        digits.reverse();
+        String::from_utf8(digits).unwrap()
+    }
+
+    #[generator(gen)]
+    pub fn input_generator(input: &str) -> GenData {
+        input
+            .lines()
+            .map(str::trim)
+            .filter(|ln| !ln.is_empty())
+            .map(from_snafu)
+            .collect()
+    }
+
+    // ----------------------- Part 1 -----------------------
+
+    #[solver(part1, balanced)]
+    pub fn solve_part1(input: GenData) -> OutData {
+        to_snafu(input.iter().sum())
+    }
+
+    // There is no part 2 puzzle on the last day.
+}
+
+#[cfg(test)]
+mod tests {
+    use super::solutions::*;
+    use super::*;
+    use aoc_zen_runner_macros::aoc_case;
+
+    #[aoc_case("2=-1=0")]
+    const INPUT1: &str = "1=-0-2
+12111
+2=0=
+21
+2=01
+111
+20012
+112
+1=-1=
+1-12
+12
+1=
+122";
+
+    #[test]
+    pub fn test_snafu() {
+        let cases = [
+            (1, "1"),
+            (3, "1="),
+            (8, "2="),
+            (2022, "1=11-2"),
+            (12345, "1-0---0"),
+            (314159265, "1121-1110-1=0"),
+        ];
+        for (n, snafu) in cases {
+            assert_eq!(snafu, to_snafu(n));
+            assert_eq!(n, from_snafu(snafu));
+        }
+    }
+}
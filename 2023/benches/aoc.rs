@@ -0,0 +1,13 @@
+//! Every generator and solver in this crate. Pass `--profile-time <secs>` to write flamegraphs instead.
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use pprof::criterion::{Output, PProfProfiler};
+
+include!(concat!(env!("OUT_DIR"), "/benches.rs"));
+
+criterion_group! {
+    name = benches;
+    config = Criterion::default().with_profiler(PProfProfiler::new(100, Output::Flamegraph(None)));
+    targets = bench_all
+}
+criterion_main!(benches);
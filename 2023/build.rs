@@ -0,0 +1,8 @@
+use std::env;
+use std::path::Path;
+
+fn main() {
+    let out_dir = env::var("OUT_DIR").unwrap();
+    aoc_common::codegen::generate(Path::new("src"), Path::new(&out_dir));
+    println!("cargo:rerun-if-changed=src");
+}
@@ -205,10 +205,39 @@ pub mod solutions {
     }
 }
 
+#[allow(dead_code)]
+pub mod gen {
+    use rand::Rng;
+
+    /// Lines in a real input.
+    pub const REAL_SIZE: usize = 1000;
+
+    const WORDS: [&str; 9] = ["one", "two", "three", "four", "five", "six", "seven", "eight", "nine"];
+
+    /// `size` lines of letters, digits and spelled-out digits, each with at least one real digit.
+    pub fn input(rng: &mut impl Rng, size: usize) -> String {
+        let mut rv = String::new();
+        for _ in 0..size {
+            let digit_at = rng.gen_range(0..8);
+            for i in 0..8 {
+                match rng.gen_range(0..3) {
+                    _ if i == digit_at => rv.push(char::from(b'1' + rng.gen_range(0..9))),
+                    0 => rv.push(char::from(b'1' + rng.gen_range(0..9))),
+                    1 => rv.push_str(WORDS[rng.gen_range(0..WORDS.len())]),
+                    _ => rv.extend((0..rng.gen_range(1..4)).map(|_| char::from(rng.gen_range(b'a'..=b'z')))),
+                }
+            }
+            rv.push('\n');
+        }
+        rv
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use super::solutions::*;
+    use aoc_common::differential_test;
     use aoc_zen_runner_macros::aoc_case;
 
     #[aoc_case(142, 142)]
@@ -225,6 +254,15 @@ mod tests {
     zoneight234
     7pqrstsixteen";
 
+    differential_test!(
+        test_solvers_agree,
+        part1: [INPUT1],
+        part2: [INPUT1, INPUT2],
+        gen: super::gen::input,
+        size: 200,
+        cases: 50,
+    );
+
     #[test]
     fn test_p2() {
         assert_eq!(281, part2_draft(INPUT2));
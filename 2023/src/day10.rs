@@ -1,8 +1,7 @@
-use std::fmt::Display;
-
+use aoc_common::grid::{Dir, Grid};
+use aoc_common::export::Palette;
+use aoc_common::visualize::{self, Cell, Color, Frame, Visualize};
 use aoc_zen_runner_macros::{aoc, generator, solution, solver};
-use colored::Colorize;
-use grid::Grid;
 use itertools::Itertools;
 
 #[derive(Debug, Default, Clone, Copy)]
@@ -23,26 +22,26 @@ impl GridNode {
     }
 }
 
-impl Display for GridNode {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl GridNode {
+    fn cell(&self) -> Cell {
         if self.is_start {
-            return write!(f, "{}", 'S');
+            return Cell::colored('S', Color::Red).bold();
         }
         let c = match (self.north, self.south, self.west, self.east) {
-            (false, false, false, false) => ".",
-            (true,  true,  false, false) => "│",
-            (false, false, true,  true)  => "─",
-            (true,  false, true,  false) => "┘",
-            (true,  false, false, true)  => "└",
-            (false, true,  true,  false) => "┐",
-            (false, true,  false, true)  => "┌",
-            _ => "?"
+            (false, false, false, false) => '.',
+            (true,  true,  false, false) => '│',
+            (false, false, true,  true)  => '─',
+            (true,  false, true,  false) => '┘',
+            (true,  false, false, true)  => '└',
+            (false, true,  true,  false) => '┐',
+            (false, true,  false, true)  => '┌',
+            _ => '?'
         };
 
         if self.is_loop {
-            write!(f, "{}", c.bold().yellow())
+            Cell::colored(c, Color::Yellow).bold()
         } else {
-            write!(f, "{}", c)
+            Cell::new(c)
         }
     }
 }
@@ -52,9 +51,36 @@ pub struct Input {
     pipes: Grid<GridNode>,
 }
 
+impl Visualize for Input {
+    fn frame(&self) -> Frame {
+        Frame::from_grid(&self.pipes, |_, gn| gn.cell())
+    }
+}
+
+/// The pipes with every tile off the main loop marked as inside (`I`) or outside (`O`) it.
+struct Shaded<'a> {
+    pipes: &'a Grid<GridNode>,
+    inside: &'a Grid<bool>,
+}
+
+impl Visualize for Shaded<'_> {
+    fn frame(&self) -> Frame {
+        Frame::from_grid(self.pipes, |pos, gn| match (gn.is_loop, self.inside[pos]) {
+            (true, _) => gn.cell(),
+            (false, true) => Cell::colored('I', Color::Green),
+            (false, false) => Cell::colored('O', Color::Blue),
+        })
+    }
+
+    fn palette(&self) -> Palette {
+        Palette::default()
+            .with('I', [0x5a, 0xc8, 0x5a])
+            .with('O', [0x1c, 0x2a, 0x48])
+    }
+}
+
 #[aoc(2023, day10)]
 pub mod solutions {
-    use grid::Grid;
     use pathfinding::directed::dijkstra::dijkstra_all;
     use super::*;
 
@@ -64,7 +90,7 @@ pub mod solutions {
         let first_line = lns.peek().unwrap();
         let columns = first_line.as_bytes().len();
 
-        let mut rv: Grid<GridNode> = Grid::new(0, columns);
+        let mut cells: Vec<GridNode> = Vec::new();
         let mut c_idx: usize = 0;
         let mut r_idx: usize = 0;
 
@@ -89,9 +115,11 @@ pub mod solutions {
 
             //println!("Grid row: {}", &r.iter().join(""));
 
-            rv.push_row(r);
+            cells.extend(r);
         }
 
+        let rv = Grid::from_vec(cells, columns);
+
         assert_eq!(rv[(r_idx, c_idx)].is_start, true);
 
         Input {start_loc: (r_idx, c_idx), pipes: rv}
@@ -147,54 +175,51 @@ pub mod solutions {
 
     #[solver(part2, draft_solvr)]
     pub fn solve_part2(input: Input) -> u32 {
-        let Input { start_loc, pipes: mut grid } = input;
-        fix_start_loc(&start_loc, &mut grid);
-        flood_fill_loop(&start_loc, &mut grid);
+        let mut input = input;
+        fix_start_loc(&input.start_loc, &mut input.pipes);
+        flood_fill_loop(&input.start_loc, &mut input.pipes);
+        visualize::show_titled(&input, || "Main loop".to_string());
+        let Input { pipes: grid, .. } = input;
 
+        let mut inside = Grid::new(grid.rows(), grid.cols(), false);
         let mut tally = 0;
-        for r in grid.iter_rows() {
+        for (r_idx, r) in grid.iter_rows().enumerate() {
             let mut is_in = false;
-            for gn in r {
+            for (c_idx, gn) in r.iter().enumerate() {
                 if gn.is_loop & gn.is_vertical() {
                     is_in = !is_in;
                 } else if !gn.is_loop & is_in {
                     tally += 1;
+                    inside[(r_idx, c_idx)] = true;
                 }
             }
         }
+        visualize::show_titled(&Shaded { pipes: &grid, inside: &inside }, || format!("{} tiles inside", tally));
 
         tally
     }
 
-    #[derive(Debug, Copy, Clone)]
-    enum Direction {
-        North,
-        South,
-        East,
-        West,
-    }
-
     fn flood_fill_loop(start_loc: &(usize, usize), grid: &mut Grid<GridNode>) {
         let (r, c) = *start_loc;
-        let mut work_queue: Vec<(Direction, (usize, usize), GridNode)> = vec![];
+        let mut work_queue: Vec<(Dir, (usize, usize), GridNode)> = vec![];
 
         grid[*start_loc].is_loop = true;
 
         let gn = grid[*start_loc].clone();
         if gn.north {
-            let next = (Direction::South, (r-1, c), grid[(r-1, c)]);
+            let next = (Dir::South, (r-1, c), grid[(r-1, c)]);
             work_queue.push(next);
         }
         if gn.south {
-            let next = (Direction::North, (r+1, c), grid[(r+1, c)]);
+            let next = (Dir::North, (r+1, c), grid[(r+1, c)]);
             work_queue.push(next);
         }
         if gn.west {
-            let next = (Direction::East, (r, c-1), grid[(r, c-1)]);
+            let next = (Dir::East, (r, c-1), grid[(r, c-1)]);
             work_queue.push(next);
         }
         if gn.east {
-            let next = (Direction::West, (r, c+1), grid[(r, c+1)]);
+            let next = (Dir::West, (r, c+1), grid[(r, c+1)]);
             work_queue.push(next);
         }
 
@@ -203,18 +228,18 @@ pub mod solutions {
             let (r, c) = curr_loc;
             grid[curr_loc].is_loop = true;
             let (next_src_dir, next_coords) = match (src_dir, curr_node.north, curr_node.south, curr_node.west, curr_node.east) {
-                (Direction::North, true,  true,  false, false) => (Direction::North, (r+1, c)),
-                (Direction::North, true,  false, true,  false) => (Direction::East,  (r, c-1)),
-                (Direction::North, true,  false, false, true)  => (Direction::West,  (r, c+1)),
-                (Direction::South, true,  true,  false, false) => (Direction::South, (r-1, c)),
-                (Direction::South, false, true,  true,  false) => (Direction::East,  (r, c-1)),
-                (Direction::South, false, true,  false, true)  => (Direction::West,  (r, c+1)),
-                (Direction::West,  true,  false, true,  false) => (Direction::South, (r-1, c)),
-                (Direction::West,  false, true,  true,  false) => (Direction::North, (r+1, c)),
-                (Direction::West,  false, false, true,  true)  => (Direction::West,  (r, c+1)),
-                (Direction::East,  true,  false, false, true)  => (Direction::South, (r-1, c)),
-                (Direction::East,  false, true,  false, true)  => (Direction::North, (r+1, c)),
-                (Direction::East,  false, false, true,  true)  => (Direction::East,  (r, c-1)),
+                (Dir::North, true,  true,  false, false) => (Dir::North, (r+1, c)),
+                (Dir::North, true,  false, true,  false) => (Dir::East,  (r, c-1)),
+                (Dir::North, true,  false, false, true)  => (Dir::West,  (r, c+1)),
+                (Dir::South, true,  true,  false, false) => (Dir::South, (r-1, c)),
+                (Dir::South, false, true,  true,  false) => (Dir::East,  (r, c-1)),
+                (Dir::South, false, true,  false, true)  => (Dir::West,  (r, c+1)),
+                (Dir::West,  true,  false, true,  false) => (Dir::South, (r-1, c)),
+                (Dir::West,  false, true,  true,  false) => (Dir::North, (r+1, c)),
+                (Dir::West,  false, false, true,  true)  => (Dir::West,  (r, c+1)),
+                (Dir::East,  true,  false, false, true)  => (Dir::South, (r-1, c)),
+                (Dir::East,  false, true,  false, true)  => (Dir::North, (r+1, c)),
+                (Dir::East,  false, false, true,  true)  => (Dir::East,  (r, c-1)),
                 _ => unreachable!(),
             };
             let next_gn = grid[next_coords];
@@ -1,5 +1,6 @@
+use aoc_common::grid::Grid;
+use aoc_common::params::Params as _;
 use aoc_zen_runner_macros::{aoc, generator, solution, solver};
-use grid::Grid;
 use itertools::Itertools;
 
 pub struct Input {
@@ -13,44 +14,27 @@ pub mod solutions {
 
     use super::*;
 
-    #[generator(gen)]
-    pub fn input_generator(input: &str) -> Input {
-        let mut lns = input.lines().map(|s| s.chars().collect_vec()).peekable();
-        let width = lns.peek().unwrap().len();
-
-        let mut rv_grid = Grid::from_vec(lns.flat_map(|i| i).collect(), width);
-
-        // Double the rows
-
-        let mut i = 0;
-
-        while i < rv_grid.rows() {
-            let row = rv_grid.iter_row(i).map(|&i| i).collect_vec();
-            if row.contains(&'#') {
-                i += 1;
-            } else {
-                rv_grid.insert_row(i, row);
-                i += 2;
-            }
+    aoc_common::params! {
+        pub struct Params {
+            /// Extra rows or columns each empty one expands into.
+            scale_factor: usize = 999_999,
         }
+    }
 
-        // Double the columns
-        let mut i = 0;
+    #[generator(gen)]
+    pub fn input_generator(input: &str) -> Input {
+        let rv_grid = Grid::parse_chars(input);
 
-        while i < rv_grid.cols() {
-            let col = rv_grid.iter_col(i).map(|&i| i).collect_vec();
-            if col.contains(&'#') {
-                i += 1;
-            } else {
-                rv_grid.insert_col(i, col);
-                i += 2;
-            }
-        }
+        // Double the empty rows and columns
+        let empty_rows = (0..rv_grid.rows()).filter(|&r| !rv_grid.row(r).contains(&'#')).collect_vec();
+        let empty_cols = (0..rv_grid.cols()).filter(|&c| !rv_grid.col(c).any(|&ch| ch == '#')).collect_vec();
 
         let mut rv_galaxies = vec![];
-        for (idx, cell) in rv_grid.indexed_iter() {
+        for (pos, cell) in rv_grid.indexed_iter() {
             if cell == &'#' {
-                rv_galaxies.push(idx);
+                let row = pos.row + empty_rows.iter().filter(|&&r| r < pos.row).count();
+                let col = pos.col + empty_cols.iter().filter(|&&c| c < pos.col).count();
+                rv_galaxies.push((row, col));
             }
         }
 
@@ -77,17 +61,14 @@ pub mod solutions {
 
     #[solution(part2, draft_soln)]
     pub fn part2_draft(input: &str) -> usize {
-        let mut lns = input.lines().map(|s| s.chars().collect_vec()).peekable();
-        let width = lns.peek().unwrap().len();
-
-        let rv_grid = Grid::from_vec(lns.flat_map(|i| i).collect(), width);
+        let rv_grid = Grid::parse_chars(input);
 
         let mut expanded_rows = vec![];
         let mut expanded_cols = vec![];
 
         // Find the empty rows
 
-        for (idx, mut r) in rv_grid.iter_rows().enumerate() {
+        for (idx, r) in rv_grid.iter_rows().enumerate() {
             if !r.contains(&'#') {
                 expanded_rows.push(idx)
             }
@@ -96,8 +77,8 @@ pub mod solutions {
         // dbg!(expanded_rows.len());
 
         // Find the empty columns
-        for (idx, mut c) in rv_grid.iter_cols().enumerate() {
-            if !c.contains(&'#') {
+        for idx in 0..rv_grid.cols() {
+            if !rv_grid.col(idx).any(|&c| c == '#') {
                 expanded_cols.push(idx)
             }
         }
@@ -107,15 +88,11 @@ pub mod solutions {
         let mut rv_galaxies = vec![];
         for (idx, cell) in rv_grid.indexed_iter() {
             if cell == &'#' {
-                rv_galaxies.push(idx);
+                rv_galaxies.push((idx.row, idx.col));
             }
         }
 
-        #[cfg(test)]
-        const SCALE_FACTOR: usize = 99;
-
-        #[cfg(not(test))]
-        const SCALE_FACTOR: usize = 999_999;
+        let scale_factor = Params::current().scale_factor;
 
         let mut tally = 0;
         for (idx1, loc1) in rv_galaxies.iter().enumerate() {
@@ -125,8 +102,8 @@ pub mod solutions {
                 let r_st = max(loc1.0, loc2.0);
                 let c_sr = min(loc1.1, loc2.1);
                 let c_st = max(loc1.1, loc2.1);
-                distance += expanded_rows.iter().filter(|ri| (r_sr..r_st).contains(ri)).count() * SCALE_FACTOR;
-                distance += expanded_cols.iter().filter(|ci| (c_sr..c_st).contains(ci)).count() * SCALE_FACTOR;
+                distance += expanded_rows.iter().filter(|ri| (r_sr..r_st).contains(ri)).count() * scale_factor;
+                distance += expanded_cols.iter().filter(|ci| (c_sr..c_st).contains(ci)).count() * scale_factor;
                 // dbg!(idx1, idx2, distance);
                 tally += distance;
             }
@@ -138,11 +115,10 @@ pub mod solutions {
 
 #[cfg(test)]
 mod tests {
-    use aoc_zen_runner_macros::aoc_case;
-    use super::*;
-    use super::solutions::*;
+    use aoc_common::param_case;
+
+    param_case!(aoc_test_input1, input1, { scale_factor: 99 }, 374, 8410);
 
-    #[aoc_case(374, 8410)]
     const input1: &str = "...#......
 .......#..
 #.........
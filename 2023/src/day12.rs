@@ -191,11 +191,50 @@ pub mod solutions {
 
 }
 
+#[allow(dead_code)]
+pub mod gen {
+    use rand::Rng;
+
+    /// Rows in a real input.
+    pub const REAL_SIZE: usize = 1000;
+
+    /// `size` condition records of 1-20 springs, each hiding a random arrangement behind `?`s.
+    pub fn input(rng: &mut impl Rng, size: usize) -> String {
+        let mut rv = String::new();
+        for _ in 0..size {
+            let len = rng.gen_range(1..=20);
+            let mut springs: Vec<bool> = (0..len).map(|_| rng.gen_bool(0.5)).collect();
+            if !springs.contains(&true) {
+                springs[rng.gen_range(0..len)] = true;
+            }
+
+            let groups = springs
+                .split(|&damaged| !damaged)
+                .filter(|g| !g.is_empty())
+                .map(|g| g.len().to_string())
+                .collect::<Vec<_>>()
+                .join(",");
+            let template: String = springs
+                .iter()
+                .map(|&damaged| match (rng.gen_bool(0.5), damaged) {
+                    (true, _) => '?',
+                    (false, true) => '#',
+                    (false, false) => '.',
+                })
+                .collect();
+
+            rv.push_str(&format!("{} {}\n", template, groups));
+        }
+        rv
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::solutions::*;
     use super::*;
     use aoc_zen_runner_macros::aoc_case;
+    use std::collections::HashMap;
     use test_case::test_case;
 
     #[aoc_case(21, 525152)]
@@ -206,6 +245,21 @@ mod tests {
 ????.######..#####. 1,6,5
 ?###???????? 3,2,1";
 
+    #[test]
+    pub fn test_dynamic_matches_enumeration() {
+        for seed in 0..20 {
+            let input = super::gen::input(&mut aoc_common::differential::seeded(seed), 20);
+            for ln in input.lines() {
+                let (template, grps_txt) = ln.split_once(' ').unwrap();
+                let grps: Vec<usize> = grps_txt.split(',').map(|g| g.parse().unwrap()).collect();
+
+                let dynamic = dynamic_solve(&mut HashMap::new(), template.as_bytes(), &grps, 0, 0, 0);
+                assert!(dynamic > 0, "{} has no arrangements", ln);
+                assert_eq!(part1_draft(ln), dynamic, "seed {}: {}", seed, ln);
+            }
+        }
+    }
+
     // #[test_case("#.#.###", &[1,1,3], true; "Simple case from example")]
     // #[test_case(".##.###", &[1,1,3], false; "Identify group too long")]
     // #[test_case("#.#.#.##", &[1,1,3], false; "Identify group too short")]
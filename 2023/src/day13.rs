@@ -1,26 +1,18 @@
+use aoc_common::grid::{Grid, GridView, Pos};
 use aoc_zen_runner_macros::{aoc, generator, solution, solver};
-use grid::Grid;
-use itertools::Itertools;
 
 #[aoc(2023, day13)]
 pub mod solutions {
     use super::*;
 
-    pub fn parse_grid(grid_str: &str) -> Vec<&[u8]> {
-        let mut lns = grid_str.lines().peekable();
-        let cols = lns.peek().unwrap().as_bytes().len();
-        let mut rv: Vec<&[u8]> = vec![];
-        for row in lns {
-            rv.push(row.as_bytes().into());
-        }
-
-        rv
+    pub fn parse_grid(grid_str: &str) -> Grid<u8> {
+        Grid::parse_bytes(grid_str)
     }
 
-    pub fn brute_find_h_mirror(grid: &[&[u8]]) -> Option<usize> {
-        for r in 1..(grid.len()) {
-            let (top, bottom) = grid.split_at(r);
-            if top.iter().rev().zip(bottom.iter()).all(|(a, b)| a == b) {
+    pub fn brute_find_h_mirror(grid: GridView<'_, u8>) -> Option<usize> {
+        for r in 1..(grid.rows()) {
+            let span = r.min(grid.rows() - r);
+            if (0..span).all(|i| grid.row(r - 1 - i).eq(grid.row(r + i))) {
                 // Found a mirror
                 return Some(r);
             }
@@ -29,28 +21,8 @@ pub mod solutions {
         None
     }
 
-    // https://stackoverflow.com/a/64499219/1819694
-    pub fn transpose<T>(v: &[&[T]]) -> Vec<Vec<T>>
-    where
-        T: Copy,
-    {
-        assert!(!v.is_empty());
-        let len = v[0].len();
-        let mut iters: Vec<_> = v.into_iter().map(|n| n.into_iter()).collect();
-        (0..len)
-            .map(|_| {
-                iters
-                    .iter_mut()
-                    .map(|n| *n.next().unwrap())
-                    .collect::<Vec<T>>()
-            })
-            .collect()
-    }
-
-    pub fn brute_find_v_mirror(grid: &[&[u8]]) -> Option<usize> {
-        let grid = transpose(grid);
-        let grid2 = grid.iter().map(|v| v.as_slice()).collect_vec();
-        brute_find_h_mirror(&grid2)
+    pub fn brute_find_v_mirror(grid: &Grid<u8>) -> Option<usize> {
+        brute_find_h_mirror(grid.transposed())
     }
 
     // ----------------------- Part 1 -----------------------
@@ -63,7 +35,7 @@ pub mod solutions {
         let mut v_tally = 0;
 
         for grid in grids {
-            if let Some(h_incr) = brute_find_h_mirror(&grid) {
+            if let Some(h_incr) = brute_find_h_mirror(grid.view()) {
                 h_tally += h_incr;
             }
             if let Some(v_incr) = brute_find_v_mirror(&grid) {
@@ -84,7 +56,7 @@ pub mod solutions {
 
         for grid in grids {
             let mut orig_mirror = (0, 0);
-            if let Some(h_incr) = brute_find_h_mirror(&grid) {
+            if let Some(h_incr) = brute_find_h_mirror(grid.view()) {
                 orig_mirror.0 = h_incr;
             }
 
@@ -107,36 +79,30 @@ pub mod solutions {
         h_tally * 100 + v_tally
     }
 
-    pub fn find_smudge(grid: &[&[u8]], orig_mirror: (usize, usize)) -> (usize, usize) {
-        let mut mut_grid: Vec<Vec<u8>> = vec![];
-        for s in grid.iter() {
-            let v = Vec::from(*s);
-            mut_grid.push(v);
-        }
-        
+    pub fn find_smudge(grid: &Grid<u8>, orig_mirror: (usize, usize)) -> (usize, usize) {
+        let mut mut_grid = grid.clone();
+
         // Find the smudge, brute-force.
-        for r in 0..grid.len() {
-            for c in 0..grid[r].len() {
-                flip_cell(&mut mut_grid, r, c);
+        for pos in grid.positions() {
+            flip_cell(&mut mut_grid, pos);
 
-                let mut new_mirror = (0, 0);
+            let mut new_mirror = (0, 0);
 
-                if let Some(h_incr) = brute_find_h_mirror2(&mut_grid) {
-                    new_mirror.0 = h_incr;
-                }
+            if let Some(h_incr) = brute_find_h_mirror(mut_grid.view()) {
+                new_mirror.0 = h_incr;
+            }
 
-                if let Some(v_incr) = brute_find_v_mirror2(&mut_grid) {
-                    new_mirror.1 = v_incr;
-                }
+            if let Some(v_incr) = brute_find_v_mirror(&mut_grid) {
+                new_mirror.1 = v_incr;
+            }
 
-                flip_cell(&mut mut_grid, r, c);
+            flip_cell(&mut mut_grid, pos);
 
-                assert_eq!(&grid, &mut_grid);
+            assert_eq!(grid, &mut_grid);
 
-                if new_mirror != (0, 0) {
-                    if new_mirror != orig_mirror {
-                        return new_mirror;
-                    }
+            if new_mirror != (0, 0) {
+                if new_mirror != orig_mirror {
+                    return new_mirror;
                 }
             }
         }
@@ -144,49 +110,13 @@ pub mod solutions {
         (0, 0)
     }
 
-    pub fn flip_cell(grid: &mut Vec<Vec<u8>>, r: usize, c: usize) {
-        if grid[r][c] == b'.' {
-            grid[r][c] = b'#';
+    pub fn flip_cell(grid: &mut Grid<u8>, pos: Pos) {
+        if grid[pos] == b'.' {
+            grid[pos] = b'#';
         } else {
-            grid[r][c] = b'.';
+            grid[pos] = b'.';
         }
     }
-
-    pub fn brute_find_h_mirror2(grid: &Vec<Vec<u8>>) -> Option<usize> {
-        for r in 1..(grid.len()) {
-            let (top, bottom) = grid.split_at(r);
-            if top.iter().rev().zip(bottom.iter()).all(|(a, b)| a == b) {
-                // Found a mirror
-                return Some(r);
-            }
-        }
-
-        None
-    }
-
-    pub fn brute_find_v_mirror2(grid: &Vec<Vec<u8>>) -> Option<usize> {
-        let grid = transpose2(grid);
-        let grid2 = grid.iter().map(|v| v.as_slice()).collect_vec();
-        brute_find_h_mirror(&grid2)
-    }
-
-    // https://stackoverflow.com/a/64499219/1819694
-    pub fn transpose2<T>(v: &Vec<Vec<T>>) -> Vec<Vec<T>>
-    where
-        T: Copy,
-    {
-        assert!(!v.is_empty());
-        let len = v[0].len();
-        let mut iters: Vec<_> = v.into_iter().map(|n| n.into_iter()).collect();
-        (0..len)
-            .map(|_| {
-                iters
-                    .iter_mut()
-                    .map(|n| *n.next().unwrap())
-                    .collect::<Vec<T>>()
-            })
-            .collect()
-    }
 }
 
 #[cfg(test)]
@@ -246,9 +176,9 @@ mod tests {
 
     #[test]
     pub fn test_transpose() {
-        let inp = [&[1, 2, 3][..], &[4, 5, 6][..]];
-        let outp = vec![vec![1, 4], vec![2, 5], vec![3, 6]];
-        assert_eq!(outp, transpose(&inp[..]));
+        let inp = Grid::from_vec(vec![1, 2, 3, 4, 5, 6], 3);
+        let outp = Grid::from_vec(vec![1, 4, 2, 5, 3, 6], 2);
+        assert_eq!(outp, inp.transposed().to_grid());
     }
 
     #[test]
@@ -262,7 +192,7 @@ mod tests {
 ..##..###
 #....#..#",
         );
-        assert_eq!(Some(4), brute_find_h_mirror(&inp));
+        assert_eq!(Some(4), brute_find_h_mirror(inp.view()));
 
         let inp2 = parse_grid(
             "#.##..##.
@@ -274,7 +204,7 @@ mod tests {
 #.#.##.#.",
         );
 
-        assert_eq!(None, brute_find_h_mirror(&inp2));
+        assert_eq!(None, brute_find_h_mirror(inp2.view()));
     }
 
     #[test]
@@ -1,46 +1,134 @@
-use aoc_zen_runner_macros::{aoc, generator, solution, solver};
+use aoc_common::cycle;
+use aoc_common::grid::{Dir, Grid, Pos};
+use aoc_zen_runner_macros::{aoc, generator, solver};
 
 #[aoc(2023, day14)]
 pub mod solutions {
     use super::*;
 
+    pub type GenData = Grid<u8>;
+
+    const ROUND: u8 = b'O';
+    const CUBE: u8 = b'#';
+    const EMPTY: u8 = b'.';
+
+    /// Lines of cells running away from the wall that rocks roll towards when tilted `dir`, nearest first.
+    fn lanes(grid: &Grid<u8>, dir: Dir) -> Vec<Vec<Pos>> {
+        let (rows, cols) = (grid.rows(), grid.cols());
+        match dir {
+            Dir::North => (0..cols).map(|c| (0..rows).map(|r| Pos::new(r, c)).collect()).collect(),
+            Dir::South => (0..cols).map(|c| (0..rows).rev().map(|r| Pos::new(r, c)).collect()).collect(),
+            Dir::West => (0..rows).map(|r| (0..cols).map(|c| Pos::new(r, c)).collect()).collect(),
+            Dir::East => (0..rows).map(|r| (0..cols).rev().map(|c| Pos::new(r, c)).collect()).collect(),
+        }
+    }
+
+    pub fn tilt(grid: &mut Grid<u8>, dir: Dir) {
+        for lane in lanes(grid, dir) {
+            let mut free = 0;
+            for (i, &pos) in lane.iter().enumerate() {
+                match grid[pos] {
+                    CUBE => free = i + 1,
+                    ROUND => {
+                        grid[pos] = EMPTY;
+                        grid[lane[free]] = ROUND;
+                        free += 1;
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    pub fn spin(grid: &Grid<u8>) -> Grid<u8> {
+        let mut next = grid.clone();
+        for dir in [Dir::North, Dir::West, Dir::South, Dir::East] {
+            tilt(&mut next, dir);
+        }
+        next
+    }
+
+    /// Load on the north support beams: each round rock counts its distance from the south edge.
+    pub fn north_load(grid: &Grid<u8>) -> usize {
+        grid.indexed_iter().filter(|(_, &c)| c == ROUND).map(|(pos, _)| grid.rows() - pos.row).sum()
+    }
+
     #[generator(gen)]
-    pub fn input_generator(input: &str) -> Vec<i32> {
-        Vec::new()
+    pub fn input_generator(input: &str) -> GenData {
+        Grid::parse_bytes(input)
     }
 
     // ----------------------- Part 1 -----------------------
 
-    #[solver(part1, draft_solvr)]
-    pub fn solve_part1(input: Vec<i32>) -> u32 {
-        0
+    #[solver(part1, tilt)]
+    pub fn solve_part1(mut input: GenData) -> usize {
+        tilt(&mut input, Dir::North);
+        north_load(&input)
     }
 
-    #[solution(part1, draft_soln)]
-    pub fn part1_draft(input: &str) -> u32 {
-        0
+    /// Load after tilting north, worked out column by column without moving anything.
+    #[solver(part1, scan)]
+    pub fn solve_part1_scan(input: GenData) -> usize {
+        let rows = input.rows();
+        (0..input.cols())
+            .map(|c| {
+                let mut free = 0;
+                let mut load = 0;
+                for (r, &cell) in input.col(c).enumerate() {
+                    match cell {
+                        CUBE => free = r + 1,
+                        ROUND => {
+                            load += rows - free;
+                            free += 1;
+                        }
+                        _ => {}
+                    }
+                }
+                load
+            })
+            .sum()
     }
 
     // ----------------------- Part 2 -----------------------
 
-    #[solver(part2, draft_solvr)]
-    pub fn solve_part2(input: Vec<i32>) -> u32 {
-        0
+    #[solver(part2, cycle)]
+    pub fn solve_part2(input: GenData) -> usize {
+        north_load(&cycle::nth_state(input, 1_000_000_000, spin))
     }
+}
 
-    #[solution(part2, draft_soln)]
-    pub fn part2_draft(input: &str) -> u32 {
-        0
+#[allow(dead_code)]
+pub mod gen {
+    use rand::Rng;
+
+    /// Side of a real platform.
+    pub const REAL_SIZE: usize = 100;
+
+    /// A `size` by `size` platform, about a fifth cube rocks and a quarter round ones.
+    pub fn input(rng: &mut impl Rng, size: usize) -> String {
+        let mut rv = String::new();
+        for _ in 0..size {
+            for _ in 0..size {
+                rv.push(match rng.gen_range(0..20) {
+                    0..=3 => '#',
+                    4..=8 => 'O',
+                    _ => '.',
+                });
+            }
+            rv.push('\n');
+        }
+        rv
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use aoc_zen_runner_macros::aoc_case;
-    use super::*;
     use super::solutions::*;
+    use super::*;
+    use aoc_common::differential_test;
+    use aoc_zen_runner_macros::aoc_case;
 
-    #[aoc_case(136)]
+    #[aoc_case(136, 64)]
     const input1: &str = "OOOO.#.O..
 OO..#....#
 OO..O##..O
@@ -52,4 +140,24 @@ O..#.OO...
 #....###..
 #....#....
 ";
+
+    #[test]
+    pub fn test_spin() {
+        let after_one = "
+.....#....
+....#...O#
+...OO##...
+.OO#......
+.....OOO#.
+.O#...O#.#
+....O#....
+......OOOO
+#...O###..
+#..OO#....
+";
+        let grid = input_generator(input1);
+        assert_eq!(Grid::parse_bytes(after_one.trim_start()), spin(&grid));
+    }
+
+    differential_test!(test_solvers_agree, part1: [input1], part2: [input1], gen: gen::input, size: 20, cases: 50);
 }
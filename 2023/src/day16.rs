@@ -1,45 +1,114 @@
-use aoc_zen_runner_macros::{aoc, generator, solution, solver};
+use aoc_common::grid::{Dir, Grid, Pos};
+use aoc_zen_runner_macros::{aoc, generator, solver};
+use rayon::prelude::*;
 
 #[aoc(2023, day16)]
 pub mod solutions {
     use super::*;
 
-    #[generator(gen)]
-    pub fn input_generator(input: &str) -> Vec<i32> {
-        Vec::new()
+    pub type GenData = Grid<u8>;
+
+    /// Directions a beam leaves a cell in, having entered it travelling `dir`.
+    fn deflect(cell: u8, dir: Dir) -> (Dir, Option<Dir>) {
+        match (cell, dir) {
+            (b'/', Dir::North) | (b'\\', Dir::South) => (Dir::East, None),
+            (b'/', Dir::South) | (b'\\', Dir::North) => (Dir::West, None),
+            (b'/', Dir::East) | (b'\\', Dir::West) => (Dir::North, None),
+            (b'/', Dir::West) | (b'\\', Dir::East) => (Dir::South, None),
+            (b'|', Dir::East | Dir::West) => (Dir::North, Some(Dir::South)),
+            (b'-', Dir::North | Dir::South) => (Dir::East, Some(Dir::West)),
+            _ => (dir, None),
+        }
     }
 
-    // ----------------------- Part 1 -----------------------
+    /// Number of cells a beam entering at `start` travelling `dir` passes through.
+    pub fn energized(grid: &Grid<u8>, start: Pos, dir: Dir) -> usize {
+        // One bit per direction a beam has already entered each cell travelling in.
+        let mut seen: Grid<u8> = Grid::new(grid.rows(), grid.cols(), 0);
+        let mut beams = vec![(start, dir)];
+        while let Some((pos, dir)) = beams.pop() {
+            let bit = 1 << dir as u8;
+            if seen[pos] & bit != 0 {
+                continue;
+            }
+            seen[pos] |= bit;
 
-    #[solver(part1, draft_solvr)]
-    pub fn solve_part1(input: Vec<i32>) -> u32 {
-        0
+            let (out, split) = deflect(grid[pos], dir);
+            for out in std::iter::once(out).chain(split) {
+                if let Some(next) = grid.step(pos, out) {
+                    beams.push((next, out));
+                }
+            }
+        }
+        seen.iter().filter(|&&s| s != 0).count()
     }
 
-    #[solution(part1, draft_soln)]
-    pub fn part1_draft(input: &str) -> u32 {
-        0
+    /// Every edge cell, with the direction a beam entering there would travel.
+    pub fn entry_points(grid: &Grid<u8>) -> Vec<(Pos, Dir)> {
+        let (rows, cols) = (grid.rows(), grid.cols());
+        let mut rv = Vec::new();
+        for r in 0..rows {
+            rv.push((Pos::new(r, 0), Dir::East));
+            rv.push((Pos::new(r, cols - 1), Dir::West));
+        }
+        for c in 0..cols {
+            rv.push((Pos::new(0, c), Dir::South));
+            rv.push((Pos::new(rows - 1, c), Dir::North));
+        }
+        rv
+    }
+
+    #[generator(gen)]
+    pub fn input_generator(input: &str) -> GenData {
+        Grid::parse_bytes(input)
+    }
+
+    // ----------------------- Part 1 -----------------------
+
+    #[solver(part1, beams)]
+    pub fn solve_part1(input: GenData) -> usize {
+        energized(&input, Pos::new(0, 0), Dir::East)
     }
 
     // ----------------------- Part 2 -----------------------
 
-    #[solver(part2, draft_solvr)]
-    pub fn solve_part2(input: Vec<i32>) -> u32 {
-        0
+    #[solver(part2, rayon)]
+    pub fn solve_part2(input: GenData) -> usize {
+        entry_points(&input).into_par_iter().map(|(pos, dir)| energized(&input, pos, dir)).max().unwrap()
     }
+}
+
+#[allow(dead_code)]
+pub mod gen {
+    use rand::Rng;
+
+    /// Side of a real contraption.
+    pub const REAL_SIZE: usize = 110;
 
-    #[solution(part2, draft_soln)]
-    pub fn part2_draft(input: &str) -> u32 {
-        0
+    /// A `size` by `size` contraption with about one cell in ten a mirror or splitter.
+    pub fn input(rng: &mut impl Rng, size: usize) -> String {
+        let mut rv = String::new();
+        for _ in 0..size {
+            for _ in 0..size {
+                rv.push(match rng.gen_range(0..40) {
+                    0 => '/',
+                    1 => '\\',
+                    2 => '|',
+                    3 => '-',
+                    _ => '.',
+                });
+            }
+            rv.push('\n');
+        }
+        rv
     }
 }
 
 #[cfg(test)]
 mod tests {
     use aoc_zen_runner_macros::aoc_case;
-    use super::solutions::*;
 
-    #[aoc_case(0, 0)]
+    #[aoc_case(46, 51)]
     const input1: &str = r#".|...\....
 |.-.\.....
 .....|-...
@@ -1,42 +1,15 @@
+use aoc_common::grid::{Dir, Grid, Pos};
 use aoc_zen_runner_macros::{aoc, generator, solution, solver};
-use bstr::ByteSlice;
 use std::cmp::min;
-use std::{cmp::Reverse, collections::BinaryHeap, ops::Neg};
-use itertools::Itertools;
+use std::{cmp::Reverse, collections::BinaryHeap};
 
 #[aoc(2023, day17)]
 pub mod solutions {
     use super::*;
 
     #[generator(gen)]
-    pub fn input_generator(input: &str) -> Vec<&[u8]> {
-        let mut parsed_grid = Vec::new() ;
-        for ln in input.as_bytes().lines() {
-            parsed_grid.push(ln)
-        }
-
-        parsed_grid
-    }
-
-    #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Copy, Clone)]
-    pub enum Direction {
-        North,
-        South,
-        East,
-        West,
-    }
-
-    impl Neg for Direction {
-        type Output = Direction;
-
-        fn neg(self) -> Self::Output {
-            match self {
-                Direction::North => Direction::South,
-                Direction::South => Direction::North,
-                Direction::East => Direction::West,
-                Direction::West => Direction::East,
-            }
-        }
+    pub fn input_generator(input: &str) -> Grid<u8> {
+        Grid::parse_bytes(input)
     }
 
     // ----------------------- Part 1 -----------------------
@@ -47,59 +20,36 @@ pub mod solutions {
         (((row as u16) & 0xFF) << 8) | ((column as u16) & 0xFF)
     }
 
-    fn neighbors(
-        grid: &Vec<&[u8]>,
-        coords: &(usize, usize),
-        exclude: Option<Direction>,
-    ) -> Vec<(usize, usize, Direction)> {
-        let (src_r, src_c) = coords;
-
-        let mut rv = Vec::with_capacity(4);
-
-        if src_r > &0 && exclude != Some(Direction::North) {
-            rv.push((src_r - 1, *src_c, Direction::North));
-        }
-
-        if src_r < &&(grid.len() - 1) && exclude != Some(Direction::South) {
-            rv.push((src_r + 1, *src_c, Direction::South));
-        }
-
-        if src_c < &(grid[0].len() - 1) && exclude != Some(Direction::East) {
-            rv.push((*src_r, src_c + 1, Direction::East));
-        }
-
-        if src_c > &0 && exclude != Some(Direction::West) {
-            rv.push((*src_r, src_c - 1, Direction::West));
-        }
-
-        rv
+    fn neighbors(grid: &Grid<u8>, coords: Pos, exclude: Option<Dir>) -> Vec<(Pos, Dir)> {
+        grid.neighbors4(coords)
+            .filter(|(dir, _)| Some(*dir) != exclude)
+            .map(|(dir, pos)| (pos, dir))
+            .collect()
     }
 
     #[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
     pub struct VisitRecordPart1 {
         cost: AnswerType,
-        history: Vec<(usize, usize)>,
-        last_dir: Direction,
+        history: Vec<Pos>,
+        last_dir: Dir,
         dir_count: u8,
-        coords: (usize, usize),
+        coords: Pos,
     }
 
     #[solver(part1, draft_solvr)]
-    pub fn solve_part1(input: Vec<&[u8]>) -> AnswerType {
-        // All coordinates are in zero-indexed (row, column) format.
-        let src_coords = (0usize, 0usize);
-        let targ_coords = (input.len(), input[0].len());
+    pub fn solve_part1(input: Grid<u8>) -> AnswerType {
+        let src_coords = Pos::new(0, 0);
+        let targ_coords = Pos::new(input.rows(), input.cols());
         let mut work_queue: BinaryHeap<Reverse<VisitRecordPart1>> = BinaryHeap::new();
 
         let mut result: AnswerType = AnswerType::MAX;
 
-        for n in neighbors(&input, &src_coords, None) {
-            let (targ_r, targ_c, dir) = n;
+        for (targ, dir) in neighbors(&input, src_coords, None) {
             println!("Initialization direction: {:?}", &dir);
             let visit = VisitRecordPart1 {
-                coords: (targ_r, targ_c),
-                cost: input[targ_r][targ_c] as AnswerType,
-                history: vec![src_coords, (targ_r, targ_c)],
+                coords: targ,
+                cost: input[targ] as AnswerType,
+                history: vec![src_coords, targ],
                 last_dir: dir,
                 dir_count: 1,
             };
@@ -111,7 +61,7 @@ pub mod solutions {
                 .pop()
                 .expect("Work queue has items, but pop() returned None.");
 
-            let current_cost = visit.cost + input[visit.coords.0][visit.coords.1] as u16;
+            let current_cost = visit.cost + input[visit.coords] as u16;
 
             if visit.coords == targ_coords {
                 // We should have found the shortest path!
@@ -127,13 +77,13 @@ pub mod solutions {
             // println!("Checking node with cost {}: {}", &current_cost, &next_history.iter().map(|t| format!("({}, {})", t.0, t.1)).join(" -> "));
 
             let neighbors = match visit.dir_count {
-                3 => neighbors(&input, &visit.coords, Some(visit.last_dir)),
-                _ => neighbors(&input, &visit.coords, None),
+                3 => neighbors(&input, visit.coords, Some(visit.last_dir)),
+                _ => neighbors(&input, visit.coords, None),
             };
 
-            for (n_r, n_c, n_dir) in neighbors {
+            for (n_pos, n_dir) in neighbors {
                 // Don't loop
-                if next_history.contains(&(n_r, n_c)) {
+                if next_history.contains(&n_pos) {
                     continue;
                 }
                 let n_count = if &n_dir == &visit.last_dir {
@@ -142,7 +92,7 @@ pub mod solutions {
                     0
                 };
                 let n_visit = VisitRecordPart1 {
-                    coords: (n_r, n_c),
+                    coords: n_pos,
                     cost: current_cost,
                     history: next_history.clone(),
                     last_dir: n_dir,
@@ -164,7 +114,7 @@ pub mod solutions {
     // ----------------------- Part 2 -----------------------
 
     #[solver(part2, draft_solvr)]
-    pub fn solve_part2(input: Vec<&[u8]>) -> u32 {
+    pub fn solve_part2(input: Grid<u8>) -> u32 {
         0
     }
 
@@ -0,0 +1,121 @@
+use aoc_common::grid::Dir;
+use aoc_zen_runner_macros::{aoc, generator, solver};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Dig {
+    pub dir: Dir,
+    pub len: i64,
+}
+
+/// Cubic metres of lava the lagoon dug by `plan` holds. The shoelace formula gives the area inside the path through
+/// the centres of the trench cells; Pick's theorem turns that into the number of cells strictly inside, and the
+/// trench itself adds one cell per metre dug.
+pub fn lagoon_volume(plan: &[Dig]) -> i64 {
+    let (mut row, mut col) = (0i64, 0i64);
+    let mut twice_area = 0;
+    let mut perimeter = 0;
+    for dig in plan {
+        let (d_row, d_col) = dig.dir.delta();
+        let (next_row, next_col) = (row + d_row as i64 * dig.len, col + d_col as i64 * dig.len);
+        twice_area += col * next_row - next_col * row;
+        perimeter += dig.len;
+        (row, col) = (next_row, next_col);
+    }
+    let interior = twice_area.abs() / 2 - perimeter / 2 + 1;
+    interior + perimeter
+}
+
+#[aoc(2023, day18)]
+pub mod solutions {
+    use super::*;
+
+    /// Both readings of each line of the plan: the direction and length as written, and the ones hidden in the
+    /// colour code.
+    #[generator(gen)]
+    pub fn input_generator(input: &str) -> (Vec<Dig>, Vec<Dig>) {
+        input
+            .lines()
+            .filter(|ln| !ln.trim().is_empty())
+            .map(|ln| {
+                let [dir, len, color] = ln.split_ascii_whitespace().collect::<Vec<_>>()[..] else {
+                    panic!("Bad dig plan line: {}", ln);
+                };
+                let dir = match dir {
+                    "U" => Dir::North,
+                    "R" => Dir::East,
+                    "D" => Dir::South,
+                    "L" => Dir::West,
+                    _ => panic!("Bad direction: {}", dir),
+                };
+                let hex = color.trim_start_matches("(#").trim_end_matches(')');
+                let hidden = Dig {
+                    dir: [Dir::East, Dir::South, Dir::West, Dir::North][(hex.as_bytes()[5] - b'0') as usize],
+                    len: i64::from_str_radix(&hex[..5], 16).unwrap(),
+                };
+                (
+                    Dig {
+                        dir,
+                        len: len.parse().unwrap(),
+                    },
+                    hidden,
+                )
+            })
+            .unzip()
+    }
+
+    // ----------------------- Part 1 -----------------------
+
+    #[solver(part1, shoelace)]
+    pub fn solve_part1(input: (Vec<Dig>, Vec<Dig>)) -> i64 {
+        lagoon_volume(&input.0)
+    }
+
+    // ----------------------- Part 2 -----------------------
+
+    #[solver(part2, shoelace)]
+    pub fn solve_part2(input: (Vec<Dig>, Vec<Dig>)) -> i64 {
+        lagoon_volume(&input.1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::solutions::*;
+    use super::*;
+    use aoc_zen_runner_macros::aoc_case;
+
+    #[aoc_case(62, 952408144115)]
+    const INPUT1: &str = "R 6 (#70c710)
+D 5 (#0dc571)
+L 2 (#5713f0)
+D 2 (#d2c081)
+R 2 (#59c680)
+D 2 (#411b91)
+L 5 (#8ceee2)
+U 2 (#caa173)
+L 1 (#1b58a2)
+U 2 (#caa171)
+R 2 (#7807d2)
+U 3 (#a77fa3)
+L 2 (#015232)
+U 2 (#7a21e3)";
+
+    #[test]
+    pub fn test_hidden_instructions() {
+        let (_, hidden) = input_generator(INPUT1);
+        assert_eq!(
+            Dig {
+                dir: Dir::East,
+                len: 461937
+            },
+            hidden[0]
+        );
+        assert_eq!(
+            Dig {
+                dir: Dir::North,
+                len: 500254
+            },
+            hidden[13]
+        );
+    }
+}
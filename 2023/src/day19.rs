@@ -0,0 +1,201 @@
+use std::collections::HashMap;
+use std::ops::RangeInclusive;
+
+use aoc_zen_runner_macros::{aoc, generator, solver};
+
+/// A part's rating in each category, in `xmas` order.
+pub type Part = [u64; 4];
+
+/// A set of parts as one range of ratings per category.
+pub type Block = [RangeInclusive<u64>; 4];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Target {
+    Accept,
+    Reject,
+    Workflow(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rule {
+    /// Category, whether the rating must be less than `value` (or else greater), and `value`. `None` always
+    /// matches.
+    pub test: Option<(usize, bool, u64)>,
+    pub target: Target,
+}
+
+impl Rule {
+    /// Split `block` into the parts this rule sends to its target and the parts that carry on to the next rule.
+    fn split(&self, block: Block) -> (Option<Block>, Option<Block>) {
+        let Some((cat, less, value)) = self.test else {
+            return (Some(block), None);
+        };
+        let (lo, hi) = (*block[cat].start(), *block[cat].end());
+        let (matched, rest) = if less {
+            (lo..=hi.min(value - 1), value.max(lo)..=hi)
+        } else {
+            (lo.max(value + 1)..=hi, lo..=hi.min(value))
+        };
+        let with = |range: RangeInclusive<u64>| {
+            (!range.is_empty()).then(|| {
+                let mut b = block.clone();
+                b[cat] = range;
+                b
+            })
+        };
+        (with(matched), with(rest))
+    }
+}
+
+pub struct System {
+    pub workflows: HashMap<String, Vec<Rule>>,
+    pub parts: Vec<Part>,
+}
+
+impl System {
+    /// How many parts in `block` end up accepted once sent to `target`.
+    fn accepted(&self, target: &Target, block: Block) -> u64 {
+        let name = match target {
+            Target::Accept => return block.iter().map(|r| r.end() - r.start() + 1).product(),
+            Target::Reject => return 0,
+            Target::Workflow(name) => name,
+        };
+        let mut rv = 0;
+        let mut rest = Some(block);
+        for rule in &self.workflows[name] {
+            let Some(block) = rest else { break };
+            let (matched, unmatched) = rule.split(block);
+            if let Some(matched) = matched {
+                rv += self.accepted(&rule.target, matched);
+            }
+            rest = unmatched;
+        }
+        rv
+    }
+}
+
+fn parse_target(s: &str) -> Target {
+    match s {
+        "A" => Target::Accept,
+        "R" => Target::Reject,
+        name => Target::Workflow(name.to_string()),
+    }
+}
+
+fn category(c: u8) -> usize {
+    b"xmas"
+        .iter()
+        .position(|&b| b == c)
+        .unwrap_or_else(|| panic!("Bad category: {}", c as char))
+}
+
+#[aoc(2023, day19)]
+pub mod solutions {
+    use super::*;
+
+    #[generator(gen)]
+    pub fn input_generator(input: &str) -> System {
+        let mut workflows = HashMap::new();
+        let mut parts = Vec::new();
+        for ln in input.lines().map(str::trim).filter(|ln| !ln.is_empty()) {
+            if let Some(ratings) = ln.strip_prefix('{') {
+                let mut part = [0; 4];
+                for rating in ratings.trim_end_matches('}').split(',') {
+                    let (cat, value) = rating.split_once('=').expect("Bad rating");
+                    part[category(cat.as_bytes()[0])] = value.parse().unwrap();
+                }
+                parts.push(part);
+                continue;
+            }
+            let (name, rules) = ln.split_once('{').expect("Bad workflow");
+            let rules = rules
+                .trim_end_matches('}')
+                .split(',')
+                .map(|rule| match rule.split_once(':') {
+                    Some((test, target)) => Rule {
+                        test: Some((
+                            category(test.as_bytes()[0]),
+                            test.as_bytes()[1] == b'<',
+                            test[2..].parse().unwrap(),
+                        )),
+                        target: parse_target(target),
+                    },
+                    None => Rule {
+                        test: None,
+                        target: parse_target(rule),
+                    },
+                })
+                .collect();
+            workflows.insert(name.to_string(), rules);
+        }
+        System { workflows, parts }
+    }
+
+    // ----------------------- Part 1 -----------------------
+
+    /// Each part is a block of one rating per category.
+    #[solver(part1, blocks)]
+    pub fn solve_part1(input: System) -> u64 {
+        let start = Target::Workflow("in".to_string());
+        input
+            .parts
+            .iter()
+            .filter(|part| input.accepted(&start, part.map(|r| r..=r)) > 0)
+            .map(|part| part.iter().sum::<u64>())
+            .sum()
+    }
+
+    // ----------------------- Part 2 -----------------------
+
+    #[solver(part2, blocks)]
+    pub fn solve_part2(input: System) -> u64 {
+        input.accepted(
+            &Target::Workflow("in".to_string()),
+            [1..=4000, 1..=4000, 1..=4000, 1..=4000],
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_zen_runner_macros::aoc_case;
+
+    #[aoc_case(19114, 167409079868000)]
+    const INPUT1: &str = "px{a<2006:qkq,m>2090:A,rfg}
+pv{a>1716:R,A}
+lnx{m>1548:A,A}
+rfg{s<537:gd,x>2440:R,A}
+qs{s>3448:A,lnx}
+qkq{x<1416:A,crn}
+crn{x>2662:A,R}
+in{s<1351:px,qqz}
+qqz{s>2770:qs,m<1801:hdj,R}
+gd{a>3333:R,R}
+hdj{m>838:A,pv}
+
+{x=787,m=2655,a=1222,s=2876}
+{x=1679,m=44,a=2067,s=496}
+{x=2036,m=264,a=79,s=2244}
+{x=2461,m=1339,a=466,s=1291}
+{x=2127,m=1623,a=2188,s=1013}";
+
+    #[test]
+    pub fn test_rule_split() {
+        let rule = Rule {
+            test: Some((1, true, 100)),
+            target: Target::Accept,
+        };
+        assert_eq!(
+            (
+                Some([1..=1, 1..=99, 1..=1, 1..=1]),
+                Some([1..=1, 100..=200, 1..=1, 1..=1])
+            ),
+            rule.split([1..=1, 1..=200, 1..=1, 1..=1])
+        );
+        assert_eq!(
+            (None, Some([1..=1, 100..=200, 1..=1, 1..=1])),
+            rule.split([1..=1, 100..=200, 1..=1, 1..=1])
+        );
+    }
+}
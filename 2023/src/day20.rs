@@ -1,44 +1,251 @@
-use aoc_zen_runner_macros::{aoc, generator, solution, solver};
+use std::collections::{HashMap, VecDeque};
+
+use aoc_zen_runner_macros::{aoc, generator, solver};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Kind {
+    Broadcaster,
+    FlipFlop,
+    Conjunction,
+    /// Named only as a destination, like `rx`.
+    Sink,
+}
+
+#[derive(Debug, Clone)]
+pub struct Module {
+    pub name: String,
+    pub kind: Kind,
+    /// Destination modules, each with the slot this module occupies in the destination's input list.
+    pub outputs: Vec<(usize, usize)>,
+    pub inputs: Vec<usize>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Network {
+    pub modules: Vec<Module>,
+    pub broadcaster: usize,
+}
+
+impl Network {
+    pub fn find(&self, name: &str) -> Option<usize> {
+        self.modules.iter().position(|m| m.name == name)
+    }
+}
+
+/// Flip-flop and conjunction state for a run over a `Network`.
+#[derive(Debug, Clone)]
+pub struct Machine<'a> {
+    network: &'a Network,
+    on: Vec<bool>,
+    /// For each conjunction, the last pulse (`true` for high) remembered from each input slot.
+    memory: Vec<Vec<bool>>,
+    /// Pulses in flight: sender, destination, the sender's input slot at the destination, and whether it's high.
+    queue: VecDeque<(usize, usize, usize, bool)>,
+}
+
+impl<'a> Machine<'a> {
+    pub fn new(network: &'a Network) -> Machine<'a> {
+        Machine {
+            network,
+            on: vec![false; network.modules.len()],
+            memory: network.modules.iter().map(|m| vec![false; m.inputs.len()]).collect(),
+            queue: VecDeque::new(),
+        }
+    }
+
+    /// Push the button once, calling `watch(from, to, high)` for every pulse delivered. Returns the number of
+    /// (low, high) pulses sent, including the button's.
+    pub fn press(&mut self, mut watch: impl FnMut(usize, usize, bool)) -> (u64, u64) {
+        // The button's own low pulse to the broadcaster is never queued.
+        let mut counts = (1, 0);
+        let broadcaster = self.network.broadcaster;
+        self.send(broadcaster, false);
+        while let Some((from, to, slot, high)) = self.queue.pop_front() {
+            watch(from, to, high);
+            if high {
+                counts.1 += 1;
+            } else {
+                counts.0 += 1;
+            }
+            let module = &self.network.modules[to];
+            match module.kind {
+                Kind::Broadcaster => self.send(to, high),
+                Kind::FlipFlop if !high => {
+                    self.on[to] = !self.on[to];
+                    self.send(to, self.on[to]);
+                }
+                Kind::Conjunction => {
+                    self.memory[to][slot] = high;
+                    let all_high = self.memory[to].iter().all(|&m| m);
+                    self.send(to, !all_high);
+                }
+                _ => {}
+            }
+        }
+        counts
+    }
+
+    fn send(&mut self, from: usize, high: bool) {
+        for &(to, slot) in &self.network.modules[from].outputs {
+            self.queue.push_back((from, to, slot, high));
+        }
+    }
+}
 
 #[aoc(2023, day20)]
 pub mod solutions {
+    use num::integer::lcm;
+
     use super::*;
 
-    #[generator(gen)]
-    pub fn input_generator(input: &str) -> Vec<i32> {
-        Vec::new()
+    /// Index of the module called `name`, adding it if this is the first mention. A `Sink` kind leaves an existing
+    /// module's kind alone.
+    fn module_id<'a>(
+        name: &'a str,
+        kind: Kind,
+        index: &mut HashMap<&'a str, usize>,
+        modules: &mut Vec<Module>,
+    ) -> usize {
+        let i = *index.entry(name).or_insert_with(|| {
+            modules.push(Module {
+                name: name.to_string(),
+                kind: Kind::Sink,
+                outputs: Vec::new(),
+                inputs: Vec::new(),
+            });
+            modules.len() - 1
+        });
+        if kind != Kind::Sink {
+            modules[i].kind = kind;
+        }
+        i
     }
 
-    // ----------------------- Part 1 -----------------------
+    #[generator(gen)]
+    pub fn input_generator(input: &str) -> Network {
+        let lines: Vec<(Kind, &str, Vec<&str>)> = input
+            .lines()
+            .filter(|ln| !ln.trim().is_empty())
+            .map(|ln| {
+                let (name, outputs) = ln.split_once(" -> ").expect("Bad module line");
+                let outputs = outputs.split(',').map(str::trim).collect();
+                match name.trim() {
+                    "broadcaster" => (Kind::Broadcaster, "broadcaster", outputs),
+                    n if n.starts_with('%') => (Kind::FlipFlop, &n[1..], outputs),
+                    n if n.starts_with('&') => (Kind::Conjunction, &n[1..], outputs),
+                    n => panic!("Unknown module type: {}", n),
+                }
+            })
+            .collect();
+
+        let mut index: HashMap<&str, usize> = HashMap::new();
+        let mut modules: Vec<Module> = Vec::new();
+        let ids: Vec<usize> = lines
+            .iter()
+            .map(|(kind, name, _)| module_id(name, *kind, &mut index, &mut modules))
+            .collect();
+        for ((_, _, outputs), &from) in lines.iter().zip(&ids) {
+            for out in outputs {
+                let to = module_id(out, Kind::Sink, &mut index, &mut modules);
+                let slot = modules[to].inputs.len();
+                modules[to].inputs.push(from);
+                modules[from].outputs.push((to, slot));
+            }
+        }
 
-    #[solver(part1, draft_solvr)]
-    pub fn solve_part1(input: Vec<i32>) -> u32 {
-        0
+        let broadcaster = modules
+            .iter()
+            .position(|m| m.kind == Kind::Broadcaster)
+            .expect("No broadcaster");
+        Network { modules, broadcaster }
     }
 
-    #[solution(part1, draft_soln)]
-    pub fn part1_draft(input: &str) -> u32 {
-        0
+    // ----------------------- Part 1 -----------------------
+
+    #[solver(part1, queue)]
+    pub fn solve_part1(input: Network) -> u64 {
+        let mut machine = Machine::new(&input);
+        let (low, high) = (0..1000).fold((0, 0), |(low, high), _| {
+            let (l, h) = machine.press(|_, _, _| {});
+            (low + l, high + h)
+        });
+        low * high
     }
 
     // ----------------------- Part 2 -----------------------
 
-    #[solver(part2, draft_solvr)]
-    pub fn solve_part2(input: Vec<i32>) -> u32 {
-        0
-    }
+    /// `rx` is fed by one conjunction, which sends it a low pulse only once all of its inputs last sent high. Each of
+    /// those inputs sends high on its own fixed cycle, so the first press they all line up is the LCM of the
+    /// presses each first does so.
+    #[solver(part2, lcm)]
+    pub fn solve_part2(input: Network) -> u64 {
+        let rx = input.find("rx").expect("No rx module");
+        let [hub] = input.modules[rx].inputs[..] else {
+            panic!("rx should have exactly one input");
+        };
+        assert_eq!(
+            Kind::Conjunction,
+            input.modules[hub].kind,
+            "rx should be fed by a conjunction"
+        );
 
-    #[solution(part2, draft_soln)]
-    pub fn part2_draft(input: &str) -> u32 {
-        0
+        let feeders = &input.modules[hub].inputs;
+        let mut first_high: Vec<Option<u64>> = vec![None; feeders.len()];
+        let mut machine = Machine::new(&input);
+        for presses in 1..=1_000_000 {
+            machine.press(|from, to, high| {
+                if high && to == hub {
+                    let slot = feeders.iter().position(|&f| f == from).unwrap();
+                    first_high[slot].get_or_insert(presses);
+                }
+            });
+            if first_high.iter().all(Option::is_some) {
+                return first_high.into_iter().flatten().fold(1, lcm);
+            }
+        }
+        panic!("Inputs to {} never all sent a high pulse", input.modules[hub].name);
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use aoc_zen_runner_macros::aoc_case;
     use super::solutions::*;
+    use super::*;
+    use aoc_zen_runner_macros::aoc_case;
 
-    #[aoc_case(0, 0)]
-    const input1: &str = "";
+    #[aoc_case(32000000)]
+    const input1: &str = "broadcaster -> a, b, c
+%a -> b
+%b -> c
+%c -> inv
+&inv -> a
+";
+
+    #[aoc_case(11687500)]
+    const input2: &str = "broadcaster -> a
+%a -> inv, con
+&inv -> b
+%b -> con
+&con -> output
+";
+
+    /// Two flip-flop chains, whose last flip-flops first turn on after 2 and 8 presses, feeding rx's hub through
+    /// inverters that turn every low pulse high.
+    const COUNTERS: &str = "broadcaster -> a1, b1
+%a1 -> a2
+%a2 -> ia
+%b1 -> b2
+%b2 -> b3
+%b3 -> b4
+%b4 -> ib
+&ia -> hub
+&ib -> hub
+&hub -> rx
+";
+
+    #[test]
+    pub fn test_part2_counters() {
+        // ia and ib send high whenever a2 and b4 send low: a2 first turns off on press 4, and b4 on press 16.
+        assert_eq!(16, solve_part2(input_generator(COUNTERS)));
+    }
 }
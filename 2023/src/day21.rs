@@ -1,44 +1,172 @@
-use aoc_zen_runner_macros::{aoc, generator, solution, solver};
+use std::collections::VecDeque;
+
+use aoc_common::grid::{Grid, Pos};
+use aoc_common::params::Params as _;
+use aoc_common::series;
+use aoc_zen_runner_macros::{aoc, generator, solver};
+
+pub struct Garden {
+    pub grid: Grid<u8>,
+    pub start: Pos,
+}
 
 #[aoc(2023, day21)]
 pub mod solutions {
     use super::*;
 
-    #[generator(gen)]
-    pub fn input_generator(input: &str) -> Vec<i32> {
-        Vec::new()
+    aoc_common::params! {
+        pub struct Params {
+            part1_steps: usize = 64,
+            /// Steps taken on the infinitely tiled garden.
+            part2_steps: usize = 26_501_365,
+        }
     }
 
-    // ----------------------- Part 1 -----------------------
+    /// For each of `steps`, how many plots can be reached in exactly that many steps. With `tiled`, the garden
+    /// repeats forever in every direction.
+    pub fn reachable(garden: &Garden, steps: &[usize], tiled: bool) -> Vec<usize> {
+        let max = *steps.iter().max().unwrap();
+        let (rows, cols) = (garden.grid.rows() as isize, garden.grid.cols() as isize);
+        // Distances over the square of side `2 * max + 1` centred on the start, which holds every reachable plot.
+        let side = 2 * max + 1;
+        let mut dist = vec![usize::MAX; side * side];
+        let mut at_dist = vec![0; max + 1];
+        let mut queue = VecDeque::from([(max, max)]);
+        dist[max * side + max] = 0;
+        while let Some((r, c)) = queue.pop_front() {
+            let d = dist[r * side + c];
+            at_dist[d] += 1;
+            if d == max {
+                continue;
+            }
+            for (nr, nc) in [(r.wrapping_sub(1), c), (r + 1, c), (r, c.wrapping_sub(1)), (r, c + 1)] {
+                if nr >= side || nc >= side || dist[nr * side + nc] != usize::MAX {
+                    continue;
+                }
+                let gr = garden.start.row as isize + nr as isize - max as isize;
+                let gc = garden.start.col as isize + nc as isize - max as isize;
+                if !tiled && (gr < 0 || gr >= rows || gc < 0 || gc >= cols) {
+                    continue;
+                }
+                let cell = garden.grid[(gr.rem_euclid(rows) as usize, gc.rem_euclid(cols) as usize)];
+                if cell != b'#' {
+                    dist[nr * side + nc] = d + 1;
+                    queue.push_back((nr, nc));
+                }
+            }
+        }
 
-    #[solver(part1, draft_solvr)]
-    pub fn solve_part1(input: Vec<i32>) -> u32 {
-        0
+        // A plot reached in `d` steps can be revisited every other step after, by stepping off and back.
+        steps
+            .iter()
+            .map(|&s| at_dist.iter().take(s + 1).skip(s % 2).step_by(2).sum())
+            .collect()
     }
 
-    #[solution(part1, draft_soln)]
-    pub fn part1_draft(input: &str) -> u32 {
-        0
+    #[generator(gen)]
+    pub fn input_generator(input: &str) -> Garden {
+        let mut grid = Grid::parse_bytes(input);
+        let start = grid.positions().find(|&p| grid[p] == b'S').expect("No start");
+        grid[start] = b'.';
+        Garden { grid, start }
+    }
+
+    // ----------------------- Part 1 -----------------------
+
+    #[solver(part1, bfs)]
+    pub fn solve_part1(input: Garden) -> usize {
+        reachable(&input, &[Params::current().part1_steps], false)[0]
     }
 
     // ----------------------- Part 2 -----------------------
 
-    #[solver(part2, draft_solvr)]
-    pub fn solve_part2(input: Vec<i32>) -> u32 {
-        0
+    /// Once the walk has spread a lap or so across the tiles, every further `size` steps adds another ring of whole
+    /// gardens to a diamond, so the count at `rem + k * size` steps is quadratic in `k`. This relies on the real
+    /// input's clear row and column through the start, and clear border.
+    #[solver(part2, quadratic)]
+    pub fn solve_part2(input: Garden) -> usize {
+        let steps = Params::current().part2_steps;
+        let size = input.grid.rows();
+        let (laps, rem) = (steps / size, steps % size);
+        if laps < 4 {
+            return reachable(&input, &[steps], true)[0];
+        }
+        let samples: Vec<i64> = reachable(&input, &[rem + size, rem + 2 * size, rem + 3 * size], true)
+            .into_iter()
+            .map(|n| n as i64)
+            .collect();
+        series::extrapolate(&samples, laps as i64 - 1) as usize
     }
+}
+
+#[allow(dead_code)]
+pub mod gen {
+    use rand::Rng;
+
+    /// Side of a real garden.
+    pub const REAL_SIZE: usize = 131;
 
-    #[solution(part2, draft_soln)]
-    pub fn part2_draft(input: &str) -> u32 {
-        0
+    /// A `size` by `size` garden (rounded up to odd) with the start in the middle, and the middle row and column and
+    /// the border clear of rocks, like the real input.
+    pub fn input(rng: &mut impl Rng, size: usize) -> String {
+        let size = size | 1;
+        let mid = size / 2;
+        let mut rv = String::new();
+        for r in 0..size {
+            for c in 0..size {
+                rv.push(if (r, c) == (mid, mid) {
+                    'S'
+                } else if r == mid || c == mid || r == 0 || c == 0 || r == size - 1 || c == size - 1 {
+                    '.'
+                } else if rng.gen_bool(0.15) {
+                    '#'
+                } else {
+                    '.'
+                });
+            }
+            rv.push('\n');
+        }
+        rv
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use aoc_zen_runner_macros::aoc_case;
     use super::solutions::*;
+    use super::*;
+    use aoc_common::param_case;
+    use aoc_common::params;
+
+    const input1: &str = "...........
+.....###.#.
+.###.##..#.
+..#.#...#..
+....#.#....
+.##..S####.
+.##..#...#.
+.......##..
+.##.#.####.
+.##..##.##.
+...........
+";
 
-    #[aoc_case(0, 0)]
-    const input1: &str = "";
+    param_case!(aoc_test_input1, input1, { part1_steps: 6, part2_steps: 10 }, 16, 50);
+
+    #[test]
+    pub fn test_tiled_reachable() {
+        let garden = input_generator(input1);
+        assert_eq!(vec![16, 50, 1594, 6536], reachable(&garden, &[6, 10, 50, 100], true));
+    }
+
+    #[test]
+    pub fn test_quadratic_matches_bfs() {
+        for seed in 0..10 {
+            let garden = gen::input(&mut aoc_common::differential::seeded(seed), 11);
+            let steps = 5 + 11 * 9;
+            let expected = reachable(&input_generator(&garden), &[steps], true)[0];
+            let overrides = vec![("part2_steps".to_string(), steps.to_string())];
+            let actual = params::with_overrides(overrides, || solve_part2(input_generator(&garden)));
+            assert_eq!(expected, actual, "seed {}:\n{}", seed, garden);
+        }
+    }
 }
@@ -0,0 +1,135 @@
+use std::collections::VecDeque;
+
+use aoc_zen_runner_macros::{aoc, generator, solver};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Brick {
+    pub lo: [usize; 3],
+    pub hi: [usize; 3],
+}
+
+/// Which bricks each settled brick rests on, and which rest on it.
+pub struct Supports {
+    pub below: Vec<Vec<usize>>,
+    pub above: Vec<Vec<usize>>,
+}
+
+/// Let every brick fall as far as it can, lowest first, tracking the top of the pile over each (x, y) column and
+/// which brick that top belongs to.
+pub fn settle(bricks: &[Brick]) -> Supports {
+    let mut order: Vec<usize> = (0..bricks.len()).collect();
+    order.sort_by_key(|&i| bricks[i].lo[2]);
+    let width = bricks.iter().map(|b| b.hi[0] + 1).max().unwrap_or(0);
+    let depth = bricks.iter().map(|b| b.hi[1] + 1).max().unwrap_or(0);
+    let mut top: Vec<(usize, Option<usize>)> = vec![(0, None); width * depth];
+
+    let mut below = vec![Vec::new(); bricks.len()];
+    let mut above = vec![Vec::new(); bricks.len()];
+    for i in order {
+        let b = &bricks[i];
+        let columns: Vec<usize> = (b.lo[0]..=b.hi[0])
+            .flat_map(|x| (b.lo[1]..=b.hi[1]).map(move |y| x * depth + y))
+            .collect();
+        let rest = columns.iter().map(|&c| top[c].0).max().unwrap();
+        for &c in &columns {
+            if let (h, Some(j)) = top[c] {
+                if h == rest && !below[i].contains(&j) {
+                    below[i].push(j);
+                    above[j].push(i);
+                }
+            }
+            top[c] = (rest + 1 + b.hi[2] - b.lo[2], Some(i));
+        }
+    }
+    Supports { below, above }
+}
+
+#[aoc(2023, day22)]
+pub mod solutions {
+    use super::*;
+
+    #[generator(gen)]
+    pub fn input_generator(input: &str) -> Supports {
+        let bricks: Vec<Brick> = input
+            .lines()
+            .filter(|ln| !ln.trim().is_empty())
+            .map(|ln| {
+                let (lo, hi) = ln.trim().split_once('~').expect("Bad brick");
+                let coords = |s: &str| -> [usize; 3] {
+                    s.split(',')
+                        .map(|n| n.parse().unwrap())
+                        .collect::<Vec<_>>()
+                        .try_into()
+                        .unwrap()
+                };
+                let (lo, hi) = (coords(lo), coords(hi));
+                Brick {
+                    lo: [0, 1, 2].map(|i| lo[i].min(hi[i])),
+                    hi: [0, 1, 2].map(|i| lo[i].max(hi[i])),
+                }
+            })
+            .collect();
+        settle(&bricks)
+    }
+
+    // ----------------------- Part 1 -----------------------
+
+    /// A brick is safe to remove if nothing rests on it alone.
+    #[solver(part1, graph)]
+    pub fn solve_part1(input: Supports) -> usize {
+        input
+            .above
+            .iter()
+            .filter(|above| above.iter().all(|&j| input.below[j].len() > 1))
+            .count()
+    }
+
+    // ----------------------- Part 2 -----------------------
+
+    /// Removing a brick drops everything above whose supports have all dropped, in order of height.
+    #[solver(part2, graph)]
+    pub fn solve_part2(input: Supports) -> usize {
+        let n = input.below.len();
+        (0..n)
+            .map(|start| {
+                let mut fallen = vec![false; n];
+                fallen[start] = true;
+                let mut queue = VecDeque::from([start]);
+                let mut count = 0;
+                while let Some(i) = queue.pop_front() {
+                    for &j in &input.above[i] {
+                        if !fallen[j] && input.below[j].iter().all(|&k| fallen[k]) {
+                            fallen[j] = true;
+                            count += 1;
+                            queue.push_back(j);
+                        }
+                    }
+                }
+                count
+            })
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::solutions::*;
+    use aoc_zen_runner_macros::aoc_case;
+
+    #[aoc_case(5, 7)]
+    const INPUT1: &str = "1,0,1~1,2,1
+0,0,2~2,0,2
+0,2,3~2,2,3
+0,0,4~0,2,4
+2,0,5~2,2,5
+0,1,6~2,1,6
+1,1,8~1,1,9";
+
+    #[test]
+    pub fn test_supports() {
+        let supports = input_generator(INPUT1);
+        // A holds up B and C; G rests on F alone once it has fallen.
+        assert_eq!(vec![1, 2], supports.above[0]);
+        assert_eq!(vec![5], supports.below[6]);
+    }
+}
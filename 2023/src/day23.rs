@@ -0,0 +1,138 @@
+use aoc_common::grid::{Dir, Grid, Pos};
+use aoc_zen_runner_macros::{aoc, generator, solver};
+
+/// The trail map boiled down to its junctions, with the start and end counted as junctions too.
+pub struct Junctions {
+    /// For each junction, the junctions reachable along a single corridor and the corridor's length.
+    pub edges: Vec<Vec<(usize, usize)>>,
+    pub start: usize,
+    pub end: usize,
+}
+
+fn slope_allows(cell: u8, dir: Dir) -> bool {
+    match cell {
+        b'^' => dir == Dir::North,
+        b'>' => dir == Dir::East,
+        b'v' => dir == Dir::South,
+        b'<' => dir == Dir::West,
+        _ => true,
+    }
+}
+
+/// Compress `grid` to its junctions. With `slippery`, a corridor can only be walked in the direction of its
+/// slopes.
+pub fn junctions(grid: &Grid<u8>, slippery: bool) -> Junctions {
+    let open = |p: Pos| grid[p] != b'#';
+    let start = Pos::new(0, grid.row(0).iter().position(|&b| b == b'.').expect("No start"));
+    let last = grid.rows() - 1;
+    let end = Pos::new(last, grid.row(last).iter().position(|&b| b == b'.').expect("No end"));
+    let nodes: Vec<Pos> = grid
+        .positions()
+        .filter(|&p| p == start || p == end || open(p) && grid.neighbors4(p).filter(|&(_, n)| open(n)).count() > 2)
+        .collect();
+    let index = |p: Pos| nodes.iter().position(|&n| n == p);
+
+    let mut edges = vec![Vec::new(); nodes.len()];
+    for (from, &node) in nodes.iter().enumerate() {
+        for (first_dir, first) in grid.neighbors4(node).filter(|&(_, n)| open(n)) {
+            let (mut prev, mut pos, mut len) = (node, first, 1);
+            let mut passable = !slippery || slope_allows(grid[pos], first_dir);
+            while index(pos).is_none() {
+                let Some((dir, next)) = grid.neighbors4(pos).find(|&(_, n)| open(n) && n != prev) else {
+                    passable = false;
+                    break;
+                };
+                passable &= !slippery || slope_allows(grid[next], dir);
+                (prev, pos, len) = (pos, next, len + 1);
+            }
+            if passable {
+                edges[from].push((index(pos).unwrap(), len));
+            }
+        }
+    }
+    Junctions {
+        edges,
+        start: index(start).unwrap(),
+        end: index(end).unwrap(),
+    }
+}
+
+/// Longest path from `at` to the end that doesn't revisit any junction in `visited`.
+fn longest(graph: &Junctions, at: usize, visited: u64) -> Option<usize> {
+    if at == graph.end {
+        return Some(0);
+    }
+    graph.edges[at]
+        .iter()
+        .filter(|&&(next, _)| visited & 1 << next == 0)
+        .filter_map(|&(next, len)| longest(graph, next, visited | 1 << next).map(|rest| rest + len))
+        .max()
+}
+
+#[aoc(2023, day23)]
+pub mod solutions {
+    use super::*;
+
+    #[generator(gen)]
+    pub fn input_generator(input: &str) -> Grid<u8> {
+        Grid::parse_bytes(input)
+    }
+
+    // ----------------------- Part 1 -----------------------
+
+    #[solver(part1, junctions)]
+    pub fn solve_part1(input: Grid<u8>) -> usize {
+        let graph = junctions(&input, true);
+        longest(&graph, graph.start, 1 << graph.start).expect("No way to the end")
+    }
+
+    // ----------------------- Part 2 -----------------------
+
+    #[solver(part2, junctions)]
+    pub fn solve_part2(input: Grid<u8>) -> usize {
+        let graph = junctions(&input, false);
+        assert!(graph.edges.len() <= 64, "Too many junctions for a bitmask");
+        longest(&graph, graph.start, 1 << graph.start).expect("No way to the end")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::solutions::*;
+    use super::*;
+    use aoc_zen_runner_macros::aoc_case;
+
+    #[aoc_case(94, 154)]
+    const INPUT1: &str = "#.#####################
+#.......#########...###
+#######.#########.#.###
+###.....#.>.>.###.#.###
+###v#####.#v#.###.#.###
+###.>...#.#.#.....#...#
+###v###.#.#.#########.#
+###...#.#.#.......#...#
+#####.#.#.#######.#.###
+#.....#.#.#.......#...#
+#.#####.#.#.#########v#
+#.#...#...#...###...>.#
+#.#.#v#######v###.###v#
+#...#.>.#...>.>.#.###.#
+#####v#.#.###v#.#.###.#
+#.....#...#...#.#.#...#
+#.#########.###.#.#.###
+#...###...#...#...#.###
+###.###.#.###v#####v###
+#...#...#.#.>.>.#.>.###
+#.###.###.#.###.#.#v###
+#.....###...###...#...#
+#####################.#";
+
+    #[test]
+    pub fn test_junctions() {
+        let graph = junctions(&input_generator(INPUT1), false);
+        // Seven junctions plus the start and end, and a single corridor of 15 steps out of the start.
+        assert_eq!(9, graph.edges.len());
+        let lengths: Vec<usize> = graph.edges[graph.start].iter().map(|&(_, len)| len).collect();
+        assert_eq!(vec![15], lengths);
+    }
+}
@@ -0,0 +1,191 @@
+use aoc_common::params::Params as _;
+use aoc_zen_runner_macros::{aoc, generator, solver};
+use num::{BigInt, BigRational, Signed, Zero};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Hailstone {
+    pub pos: [i64; 3],
+    pub vel: [i64; 3],
+}
+
+/// Whether the paths of `a` and `b` in the X-Y plane cross at some point in both their futures, inside `lo..=hi`
+/// on both axes. Exact: the crossing times are compared as fractions over the shared denominator.
+fn paths_cross(a: &Hailstone, b: &Hailstone, lo: i64, hi: i64) -> bool {
+    let [ax, ay, _] = a.pos.map(i128::from);
+    let [avx, avy, _] = a.vel.map(i128::from);
+    let [bx, by, _] = b.pos.map(i128::from);
+    let [bvx, bvy, _] = b.vel.map(i128::from);
+    let det = avx * bvy - avy * bvx;
+    if det == 0 {
+        return false;
+    }
+    // a crosses at time t = t_num / det, and b at s = s_num / det.
+    let (dx, dy) = (bx - ax, by - ay);
+    let (t_num, s_num) = (dx * bvy - dy * bvx, dx * avy - dy * avx);
+    let sign = det.signum();
+    if t_num * sign < 0 || s_num * sign < 0 {
+        return false;
+    }
+    let within = |p: i128, v: i128| {
+        let scaled = (p * det + t_num * v) * sign;
+        (lo as i128 * det * sign..=hi as i128 * det * sign).contains(&scaled)
+    };
+    within(ax, avx) && within(ay, avy)
+}
+
+/// The matrix that takes a vector `P` to `P × w`.
+fn cross_matrix(w: [i64; 3]) -> [[i64; 3]; 3] {
+    [[0, w[2], -w[1]], [-w[2], 0, w[0]], [w[1], -w[0], 0]]
+}
+
+fn cross(u: [i64; 3], v: [i64; 3]) -> [i128; 3] {
+    let [u, v] = [u, v].map(|a| a.map(i128::from));
+    [
+        u[1] * v[2] - u[2] * v[1],
+        u[2] * v[0] - u[0] * v[2],
+        u[0] * v[1] - u[1] * v[0],
+    ]
+}
+
+/// Solve the linear system whose augmented rows are `rows` exactly, or `None` if it is singular.
+fn solve_linear(mut rows: Vec<Vec<BigRational>>) -> Option<Vec<BigRational>> {
+    let n = rows.len();
+    for col in 0..n {
+        let pivot = (col..n).find(|&r| !rows[r][col].is_zero())?;
+        rows.swap(col, pivot);
+        let lead = rows[col][col].clone();
+        for v in rows[col].iter_mut() {
+            *v /= lead.clone();
+        }
+        let pivot_row = rows[col].clone();
+        for (r, row) in rows.iter_mut().enumerate() {
+            let factor = row[col].clone();
+            if r == col || factor.is_zero() {
+                continue;
+            }
+            for (v, p) in row.iter_mut().zip(&pivot_row).skip(col) {
+                *v -= p * &factor;
+            }
+        }
+    }
+    Some(rows.into_iter().map(|row| row[n].clone()).collect())
+}
+
+/// Position and velocity of a rock that hits every hailstone. For rock `P + tV` to meet stone `p + tv`,
+/// `(P - p) × (V - v) = 0`. The `P × V` term is common to every stone, so subtracting the equations for two stones
+/// leaves three linear equations in `P` and `V`:
+/// `P × (v_j - v_i) + (p_j - p_i) × V = p_j × v_j - p_i × v_i`.
+/// Two pairs of stones give six equations for the six unknowns; try successive stones until they're independent.
+pub fn throw(stones: &[Hailstone]) -> Option<([BigRational; 3], [BigRational; 3])> {
+    let sub = |a: [i64; 3], b: [i64; 3]| [a[0] - b[0], a[1] - b[1], a[2] - b[2]];
+    for w in stones.windows(3) {
+        let mut rows = Vec::new();
+        for (a, b) in [(&w[0], &w[1]), (&w[0], &w[2])] {
+            let p_coef = cross_matrix(sub(b.vel, a.vel));
+            // (p_j - p_i) × V = -(V × (p_j - p_i)).
+            let v_coef = cross_matrix(sub(b.pos, a.pos)).map(|row| row.map(|c| -c));
+            let (cb, ca) = (cross(b.pos, b.vel), cross(a.pos, a.vel));
+            for r in 0..3 {
+                let mut row: Vec<BigRational> = p_coef[r]
+                    .iter()
+                    .chain(&v_coef[r])
+                    .map(|&c| BigRational::from_integer(BigInt::from(c)))
+                    .collect();
+                row.push(BigRational::from_integer(BigInt::from(cb[r] - ca[r])));
+                rows.push(row);
+            }
+        }
+        if let Some(x) = solve_linear(rows) {
+            return Some((
+                [x[0].clone(), x[1].clone(), x[2].clone()],
+                [x[3].clone(), x[4].clone(), x[5].clone()],
+            ));
+        }
+    }
+    None
+}
+
+#[aoc(2023, day24)]
+pub mod solutions {
+    use super::*;
+
+    aoc_common::params! {
+        pub struct Params {
+            /// Bounds of the test area on both the X and Y axes.
+            test_min: i64 = 200_000_000_000_000,
+            test_max: i64 = 400_000_000_000_000,
+        }
+    }
+
+    #[generator(gen)]
+    pub fn input_generator(input: &str) -> Vec<Hailstone> {
+        input
+            .lines()
+            .filter(|ln| !ln.trim().is_empty())
+            .map(|ln| {
+                let (pos, vel) = ln.split_once('@').expect("Bad hailstone");
+                let triple = |s: &str| -> [i64; 3] {
+                    s.split(',')
+                        .map(|n| n.trim().parse().unwrap())
+                        .collect::<Vec<_>>()
+                        .try_into()
+                        .unwrap()
+                };
+                Hailstone {
+                    pos: triple(pos),
+                    vel: triple(vel),
+                }
+            })
+            .collect()
+    }
+
+    // ----------------------- Part 1 -----------------------
+
+    #[solver(part1, pairs)]
+    pub fn solve_part1(input: Vec<Hailstone>) -> usize {
+        let Params { test_min, test_max } = Params::current();
+        input
+            .iter()
+            .enumerate()
+            .flat_map(|(i, a)| input[i + 1..].iter().map(move |b| (a, b)))
+            .filter(|(a, b)| paths_cross(a, b, test_min, test_max))
+            .count()
+    }
+
+    // ----------------------- Part 2 -----------------------
+
+    #[solver(part2, rational)]
+    pub fn solve_part2(input: Vec<Hailstone>) -> i64 {
+        let (pos, _) = throw(&input).expect("No rock hits every hailstone");
+        let sum = pos.iter().fold(BigRational::zero(), |acc, c| acc + c);
+        assert!(
+            sum.is_integer() && !sum.is_negative(),
+            "Rock starts off the integer grid: {}",
+            sum
+        );
+        sum.to_integer().try_into().expect("Answer out of range")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::solutions::*;
+    use super::*;
+    use aoc_common::param_case;
+
+    const INPUT1: &str = "19, 13, 30 @ -2,  1, -2
+18, 19, 22 @ -1, -1, -2
+20, 25, 34 @ -2, -2, -4
+12, 31, 28 @ -1, -2, -1
+20, 19, 15 @  1, -5, -3";
+
+    param_case!(aoc_test_input1, INPUT1, { test_min: 7, test_max: 27 }, 2, 47);
+
+    #[test]
+    pub fn test_throw() {
+        let (pos, vel) = throw(&input_generator(INPUT1)).unwrap();
+        let ints = |v: [BigRational; 3]| v.map(|c| i64::try_from(c.to_integer()).unwrap());
+        assert_eq!([24, 13, 10], ints(pos));
+        assert_eq!([-3, 1, 2], ints(vel));
+    }
+}
@@ -1,57 +1,167 @@
-use aoc_zen_runner_macros::{aoc, generator, solution, solver};
-use std::io::Write;
+use std::collections::{HashMap, VecDeque};
 
-#[aoc(2023, day25)]
-pub mod solutions {
-    use super::*;
-
-    #[generator(gen)]
-    pub fn input_generator(input: &str) -> Vec<i32> {
-        Vec::new()
-    }
+use aoc_common::graph_export::{self, GraphExport};
+use aoc_zen_runner_macros::{aoc, generator, solver};
 
-    // ----------------------- Part 1 -----------------------
+pub struct Graph {
+    pub names: Vec<String>,
+    pub adj: Vec<Vec<usize>>,
+}
 
-    #[solver(part1, draft_solvr)]
-    pub fn solve_part1(input: Vec<i32>) -> u32 {
-        0
+impl Graph {
+    /// Every wire once, lower-numbered component first.
+    pub fn edges(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.adj
+            .iter()
+            .enumerate()
+            .flat_map(|(a, ns)| ns.iter().filter(move |&&b| a < b).map(move |&b| (a, b)))
     }
 
-    #[solution(part1, draft_soln)]
-    pub fn part1_draft(input: &str) -> u32 {
-        let mut ofile = std::fs::File::create("25_out.txt").unwrap();
+    /// The wiring diagram for export, with the wires in `highlight` drawn in red.
+    pub fn export(&self, highlight: &[(usize, usize)]) -> GraphExport {
+        let mut rv = GraphExport::from_adjacency(&self.names, &self.adj, false);
+        for &(a, b) in highlight {
+            rv.highlight_edge(a, b);
+        }
+        rv
+    }
+}
 
-        writeln!(&ofile, "graph {{").unwrap();
-        let h = input.lines().map(|ln| ln.split_once(":").unwrap());
-        for (k, v) in h {
-            let v = v.split_ascii_whitespace();
-            for elem in v {
-                writeln!(&ofile, "{} -- {}", &k, elem).unwrap()
+/// Which components end up on `s`'s side of a minimum cut between `s` and `t`, if that cut is at most `max_cut`
+/// wires. Every wire carries one unit of flow either way, so the cut is found by pushing at most `max_cut + 1`
+/// augmenting paths.
+pub fn min_cut_side(graph: &Graph, s: usize, t: usize, max_cut: usize) -> Option<Vec<bool>> {
+    // Net flow from `a` to `b`; the wire's residual capacity that way is `1 - flow`.
+    let mut flow: HashMap<(usize, usize), i32> = HashMap::new();
+    for _ in 0..=max_cut {
+        let mut prev: Vec<Option<usize>> = vec![None; graph.adj.len()];
+        let mut seen = vec![false; graph.adj.len()];
+        seen[s] = true;
+        let mut queue = VecDeque::from([s]);
+        while let Some(a) = queue.pop_front() {
+            for &b in &graph.adj[a] {
+                if !seen[b] && flow.get(&(a, b)).copied().unwrap_or(0) < 1 {
+                    seen[b] = true;
+                    prev[b] = Some(a);
+                    queue.push_back(b);
+                }
             }
         }
-        writeln!(&ofile, "}}").unwrap();
+        if !seen[t] {
+            return Some(seen);
+        }
 
-        0
+        let mut b = t;
+        while let Some(a) = prev[b] {
+            *flow.entry((a, b)).or_default() += 1;
+            *flow.entry((b, a)).or_default() -= 1;
+            b = a;
+        }
     }
+    None
+}
 
-    // ----------------------- Part 2 -----------------------
+#[aoc(2023, day25)]
+pub mod solutions {
+    use super::*;
 
-    #[solver(part2, draft_solvr)]
-    pub fn solve_part2(input: Vec<i32>) -> u32 {
-        0
+    #[generator(gen)]
+    pub fn input_generator(input: &str) -> Graph {
+        let mut index: HashMap<String, usize> = HashMap::new();
+        let mut graph = Graph {
+            names: Vec::new(),
+            adj: Vec::new(),
+        };
+        let mut id = |name: &str, graph: &mut Graph| {
+            *index.entry(name.to_string()).or_insert_with(|| {
+                graph.names.push(name.to_string());
+                graph.adj.push(Vec::new());
+                graph.names.len() - 1
+            })
+        };
+        for ln in input.lines().filter(|ln| !ln.trim().is_empty()) {
+            let (k, v) = ln.split_once(':').expect("Bad wiring line");
+            let a = id(k.trim(), &mut graph);
+            for other in v.split_ascii_whitespace() {
+                let b = id(other, &mut graph);
+                graph.adj[a].push(b);
+                graph.adj[b].push(a);
+            }
+        }
+        graph
     }
 
-    #[solution(part2, draft_soln)]
-    pub fn part2_draft(input: &str) -> u32 {
-        0
+    // ----------------------- Part 1 -----------------------
+
+    /// Some pair of components sits either side of the three-wire cut, and the max flow between them is three. A
+    /// component with three wires or fewer has a cut that small around it alone, so cuts leaving one component by
+    /// itself are skipped, and if the source is such a component the next one is tried.
+    #[solver(part1, maxflow)]
+    pub fn solve_part1(input: Graph) -> usize {
+        let n = input.adj.len();
+        let side = (0..n)
+            .flat_map(|s| (s + 1..n).map(move |t| (s, t)))
+            .filter_map(|(s, t)| min_cut_side(&input, s, t, 3))
+            .find(|side| (2..n - 1).contains(&side.iter().filter(|&&s| s).count()))
+            .expect("No cut of three wires or fewer splitting the components");
+
+        graph_export::emit(|| {
+            let cut: Vec<(usize, usize)> = input.edges().filter(|&(a, b)| side[a] != side[b]).collect();
+            input.export(&cut)
+        });
+
+        let left = side.iter().filter(|&&s| s).count();
+        left * (n - left)
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use aoc_zen_runner_macros::aoc_case;
     use super::solutions::*;
+    use super::*;
+    use aoc_zen_runner_macros::aoc_case;
+
+    #[aoc_case(54)]
+    const input1: &str = "jqt: rhn xhk nvd
+rsh: frs pzl lsr
+xhk: hfx
+cmg: qnr nvd lhk bvb
+rhn: xhk bvb hfx
+bvb: xhk hfx
+pzl: lsr hfx nvd
+qnr: nvd
+ntq: jqt hfx bvb xhk
+nvd: lhk
+lsr: lhk
+rzs: qnr cmg lsr rsh
+frs: qnr lhk lsr
+";
 
-    #[aoc_case(0, 0)]
-    const input1: &str = "";
+    /// `aaa` has a single wire, so every flow from it is cut off straight away, and `qqq` could be cut off by itself
+    /// with three.
+    #[test]
+    pub fn test_small_components() {
+        let wiring = format!("aaa: jqt\nqqq: cmg nvd lhk\n{}", input1);
+        assert_eq!(7 * 10, solve_part1(input_generator(&wiring)));
+    }
+
+    #[test]
+    pub fn test_cut_wires() {
+        let graph = input_generator(input1);
+        let side = min_cut_side(&graph, 0, graph.names.iter().position(|n| n == "cmg").unwrap(), 3).unwrap();
+        let mut cut: Vec<(&str, &str)> = graph
+            .edges()
+            .filter(|&(a, b)| side[a] != side[b])
+            .map(|(a, b)| {
+                let (a, b) = (graph.names[a].as_str(), graph.names[b].as_str());
+                (a.min(b), a.max(b))
+            })
+            .collect();
+        cut.sort();
+        assert_eq!(vec![("bvb", "cmg"), ("hfx", "pzl"), ("jqt", "nvd")], cut);
+        let export = graph.export(&[(0, 1)]);
+        assert_eq!(33, export.edges.len());
+        assert_eq!(1, export.edges.iter().filter(|e| e.highlight).count());
+        assert!(export.to_dot().starts_with("graph {\n    \"jqt\";\n"));
+    }
 }
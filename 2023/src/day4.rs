@@ -209,15 +209,53 @@ pub mod solutions {
     }
 }
 
+#[allow(dead_code)]
+pub mod gen {
+    use rand::seq::index::sample;
+    use rand::seq::SliceRandom;
+    use rand::Rng;
+
+    /// Cards in a real input.
+    pub const REAL_SIZE: usize = 200;
+
+    /// `size` scratchcards with 10 winning numbers and 25 numbers each, laid out like the real input.
+    ///
+    /// Most cards have no matches and the rest have at most 4, so the part 2 card counts stay within a `u32`.
+    pub fn input(rng: &mut impl Rng, size: usize) -> String {
+        let mut rv = String::new();
+        for card in 1..=size {
+            let matches = if rng.gen_bool(0.6) { 0 } else { rng.gen_range(1..=4) };
+            let numbers: Vec<usize> = sample(rng, 99, 35 - matches).into_iter().map(|n| n + 1).collect();
+            let winning = &numbers[..10];
+            let mut have: Vec<usize> = numbers[10..].iter().chain(&winning[..matches]).copied().collect();
+            have.shuffle(rng);
+
+            let fmt = |ns: &[usize]| ns.iter().map(|n| format!("{:2}", n)).collect::<Vec<_>>().join(" ");
+            rv.push_str(&format!("Card {:3}: {} | {}\n", card, fmt(winning), fmt(&have)));
+        }
+        rv
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use aoc_common::differential_test;
     use aoc_zen_runner_macros::aoc_case;
 
     #[aoc_case(13, 30)]
     const input1: &str = "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
-    Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
-    Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
-    Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
-    Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
-    Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11";
+Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
+Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
+Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
+Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
+Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11";
+
+    differential_test!(
+        test_solvers_agree,
+        part1: [input1],
+        part2: [input1],
+        gen: super::gen::input,
+        size: 200,
+        cases: 50,
+    );
 }
@@ -1,178 +1,11 @@
-use std::ops::Sub;
-use std::{cmp::max, cmp::min, ops::RangeInclusive};
-use std::{mem::take, ops::Add};
+use std::{cmp::min, ops::RangeInclusive};
 
 use itertools::Itertools;
 
+use aoc_common::interval_map::IntervalMap;
+use aoc_common::ranges::RangeSet;
 use aoc_zen_runner_macros::{aoc, solution};
 
-
-fn range_overlaps<T>(a: &RangeInclusive<T>, b: &RangeInclusive<T>) -> bool
-where
-    T: Ord,
-{
-    !(a.end() < b.start() || b.end() < a.start())
-}
-
-fn range_adjacent<T>(a: &RangeInclusive<T>, b: &RangeInclusive<T>) -> bool
-where
-    T: Ord + Add<T> + From<u64> + Copy,
-    <T as std::ops::Add>::Output: PartialEq<T>,
-{
-    *a.end() + 1.into() == *b.start() || *b.end() + 1.into() == *a.start()
-}
-
-fn range_intersect<T>(a: &RangeInclusive<T>, b: &RangeInclusive<T>) -> Option<RangeInclusive<T>>
-where
-    T: Ord,
-    T: Clone,
-    T: Copy,
-{
-    if !range_overlaps(a, b) { return None; }
-    if a == b { return Some(a.clone()); }
-
-    let rv_start: T = *max(a.start(), b.start());
-    let rv_end: T = *min(a.end(), b.end());
-
-    Some(rv_start..=rv_end)
-}
-
-fn range_sub<T>(a: &RangeInclusive<T>, b: &RangeInclusive<T>) -> Vec<RangeInclusive<T>>
-where
-    T: Ord,
-    T: Clone,
-    T: Copy,
-    T: Sub<u64, Output = T>
-{
-    if a == b {
-        return vec![];
-    }
-
-    if b.start() < a.start() && a.end() < b.end() {
-        return vec![];
-    }
-
-    if !range_overlaps(a, b) {
-        return vec![a.clone()];
-    }
-
-    if a.start() < b.start() && b.end() < a.end() {
-        panic!("Time for a restructure!");
-    }
-
-    let range_start = if a.start() < b.start() {
-        *a.start()
-    } else {
-        *b.end()
-    };
-
-    let range_end = if a.start() < b.start() {
-        *b.start() - 1
-    } else {
-        *a.end() - 0
-    };
-
-    return vec![range_start..=range_end];
-}
-
-#[derive(Debug, Default)]
-pub struct RangeLine {
-    ranges_set: Vec<RangeInclusive<u64>>,
-}
-
-impl RangeLine {
-    fn new() -> RangeLine {
-        RangeLine {
-            ranges_set: Vec::new(),
-        }
-    }
-
-    fn set_range(&mut self, x: &RangeInclusive<u64>) {
-        let mut x = x.clone();
-        for idx in 0..self.ranges_set.len() {
-            while idx < self.ranges_set.len()
-                && (range_overlaps(&x, &self.ranges_set[idx])
-                    || range_adjacent(&x, &self.ranges_set[idx]))
-            {
-                let old = self.ranges_set.remove(idx);
-                let existing_min = *old.start();
-                let existing_max = *old.end();
-                let new_min = min(*x.start(), existing_min);
-                let new_max = max(*x.end(), existing_max);
-                x = new_min..=new_max;
-            }
-        }
-        self.ranges_set.push(x);
-    }
-
-    fn unset_range(&mut self, x: &RangeInclusive<u64>) {
-        let mut new_ranges: Vec<RangeInclusive<u64>> = Vec::with_capacity(self.ranges_set.capacity());
-        for r in &self.ranges_set {
-            if range_intersect(&r, x).is_some() {
-                for new_r in range_sub(r, x) {
-                    if new_r.is_empty() {
-                        continue;
-                    } else {
-                        new_ranges.push(new_r);
-                    }
-                }
-            } else {
-                new_ranges.push(r.clone());
-            }
-        }
-        self.ranges_set = new_ranges;
-    }
-
-    fn set_range_line(&mut self, x: &RangeLine) {
-        for r in &x.ranges_set {
-            self.set_range(r);
-        }
-    }
-
-    fn unset_point(&mut self, pt: u64) {
-        for idx in 0..self.ranges_set.len() {
-            if self.ranges_set[idx].contains(&pt) {
-                let range_a = *self.ranges_set[idx].start()..=(pt - 1);
-                let range_b = (pt + 1)..=*(self.ranges_set[idx].end());
-                self.ranges_set[idx] = range_a;
-                self.set_range(&range_b);
-                return;
-            }
-        }
-    }
-
-    fn len(&self) -> u64 {
-        self.ranges_set
-            .iter()
-            .map(|r| {
-                let e = *r.end();
-                let s = *r.start();
-                (e.abs_diff(s) + 1) as u64
-            })
-            .sum()
-    }
-
-    fn normalize(&mut self) {
-        let ranges = take(&mut self.ranges_set);
-        for r in ranges {
-            self.set_range(&r);
-        }
-    }
-
-    fn ranges_count(&self) -> usize {
-        self.ranges_set.len()
-    }
-
-    fn contains(&self, point: u64) -> bool {
-        self.ranges_set.iter().any(|r| r.contains(&point))
-    }
-
-    fn raw<'a>(&'a self) -> &'a Vec<RangeInclusive<u64>> {
-        &self.ranges_set
-    }
-}
-
-
 #[derive(PartialEq, Eq, Hash, Debug)]
 pub enum Category {
     Seed,
@@ -188,72 +21,13 @@ pub enum Category {
 #[derive(Default)]
 pub struct InputP1 {
     pub seeds: Vec<u64>,
-    pub seed_to_soil: RangesMap,
-    pub soil_to_fertilizer: RangesMap,
-    pub fertilizer_to_water: RangesMap,
-    pub water_to_light: RangesMap,
-    pub light_to_temperature: RangesMap,
-    pub temperature_to_humidity: RangesMap,
-    pub humidity_to_location: RangesMap,
-}
-
-#[derive(Default)]
-pub struct RangesMap {
-    items: Vec<InpRangeLine>,
-}
-
-impl RangesMap {
-    fn get(&self, item: u64) -> u64 {
-        for rl in &self.items {
-            if rl.in_start <= item && item < rl.in_start + rl.length {
-                return item - rl.in_start + rl.out_start;
-            }
-        }
-
-        item
-    }
-
-    fn get_range(&self, items: RangeInclusive<u64>) -> RangeLine {
-        let mut rv = RangeLine { ranges_set: vec![] };
-        let mut req_rangeline = RangeLine { ranges_set: vec![items.clone()] };
-
-        // Build up the input line
-        'a: while req_rangeline.ranges_count() > 0 {
-            let current = req_rangeline.ranges_set[0].to_owned();
-
-            for rl in &self.items {
-                let rlr = rl.get_in_range();
-
-                if let Some(r_intersect) = range_intersect(&current, &rlr) {
-                    req_rangeline.unset_range(&rlr);
-                    let delta = rl.out_start - rl.in_start;
-                    let out_range = (r_intersect.start() + delta)..=(r_intersect.end() + delta);
-                    rv.set_range(&out_range);
-                    continue 'a;
-                }
-            }
-
-            // If we get all the way here, we didn't intersect at all.
-            req_rangeline.unset_range(&(*current.start()..=*current.end()));
-            rv.set_range(&current);
-        }
-
-        rv
-    }
-
-    fn add_range(&mut self, rl: InpRangeLine) {
-        self.items.push(rl)
-    }
-
-    fn get_range_line(&self, item_line: &RangeLine) -> RangeLine {
-        let mut rv = RangeLine::new();
-        for r in &item_line.ranges_set {
-            let res = self.get_range(r.clone());
-            rv.set_range_line(&res);
-        }
-
-        rv
-    }
+    pub seed_to_soil: IntervalMap<u64>,
+    pub soil_to_fertilizer: IntervalMap<u64>,
+    pub fertilizer_to_water: IntervalMap<u64>,
+    pub water_to_light: IntervalMap<u64>,
+    pub light_to_temperature: IntervalMap<u64>,
+    pub temperature_to_humidity: IntervalMap<u64>,
+    pub humidity_to_location: IntervalMap<u64>,
 }
 
 #[derive(Default)]
@@ -343,25 +117,25 @@ pub mod solutions {
 
                 match current_map {
                     Category::Seed => {
-                        rv.seed_to_soil.add_range(rl);
+                        rv.seed_to_soil.insert(rl.get_in_range(), rl.out_start);
                     }
                     Category::Soil => {
-                        rv.soil_to_fertilizer.add_range(rl);
+                        rv.soil_to_fertilizer.insert(rl.get_in_range(), rl.out_start);
                     }
                     Category::Fertilizer => {
-                        rv.fertilizer_to_water.add_range(rl);
+                        rv.fertilizer_to_water.insert(rl.get_in_range(), rl.out_start);
                     }
                     Category::Water => {
-                        rv.water_to_light.add_range(rl);
+                        rv.water_to_light.insert(rl.get_in_range(), rl.out_start);
                     }
                     Category::Light => {
-                        rv.light_to_temperature.add_range(rl);
+                        rv.light_to_temperature.insert(rl.get_in_range(), rl.out_start);
                     }
                     Category::Temperature => {
-                        rv.temperature_to_humidity.add_range(rl);
+                        rv.temperature_to_humidity.insert(rl.get_in_range(), rl.out_start);
                     }
                     Category::Humidity => {
-                        rv.humidity_to_location.add_range(rl);
+                        rv.humidity_to_location.insert(rl.get_in_range(), rl.out_start);
                     }
                     Category::Location => {
                         unreachable!(
@@ -400,18 +174,33 @@ pub mod solutions {
         tally
     }
 
+    #[solution(part1, composed)]
+    pub fn solve_part1_composed(input: &str) -> u64 {
+        let input = p1_parse_basic(input);
+        let almanac = input
+            .seed_to_soil
+            .then(&input.soil_to_fertilizer)
+            .then(&input.fertilizer_to_water)
+            .then(&input.water_to_light)
+            .then(&input.light_to_temperature)
+            .then(&input.temperature_to_humidity)
+            .then(&input.humidity_to_location);
+
+        input.seeds.iter().map(|&s| almanac.get(s)).min().unwrap_or(u64::MAX)
+    }
+
     // ----------------------- Part 2 -----------------------
 
     #[derive(Default)]
     pub struct InputP2 {
         pub seeds: Vec<RangeInclusive<u64>>,
-        pub seed_to_soil: RangesMap,
-        pub soil_to_fertilizer: RangesMap,
-        pub fertilizer_to_water: RangesMap,
-        pub water_to_light: RangesMap,
-        pub light_to_temperature: RangesMap,
-        pub temperature_to_humidity: RangesMap,
-        pub humidity_to_location: RangesMap,
+        pub seed_to_soil: IntervalMap<u64>,
+        pub soil_to_fertilizer: IntervalMap<u64>,
+        pub fertilizer_to_water: IntervalMap<u64>,
+        pub water_to_light: IntervalMap<u64>,
+        pub light_to_temperature: IntervalMap<u64>,
+        pub temperature_to_humidity: IntervalMap<u64>,
+        pub humidity_to_location: IntervalMap<u64>,
     }
 
     pub fn p2_parse_basic(input: &str) -> InputP2 {
@@ -474,25 +263,25 @@ pub mod solutions {
 
                 match current_map {
                     Category::Seed => {
-                        rv.seed_to_soil.add_range(rl);
+                        rv.seed_to_soil.insert(rl.get_in_range(), rl.out_start);
                     }
                     Category::Soil => {
-                        rv.soil_to_fertilizer.add_range(rl);
+                        rv.soil_to_fertilizer.insert(rl.get_in_range(), rl.out_start);
                     }
                     Category::Fertilizer => {
-                        rv.fertilizer_to_water.add_range(rl);
+                        rv.fertilizer_to_water.insert(rl.get_in_range(), rl.out_start);
                     }
                     Category::Water => {
-                        rv.water_to_light.add_range(rl);
+                        rv.water_to_light.insert(rl.get_in_range(), rl.out_start);
                     }
                     Category::Light => {
-                        rv.light_to_temperature.add_range(rl);
+                        rv.light_to_temperature.insert(rl.get_in_range(), rl.out_start);
                     }
                     Category::Temperature => {
-                        rv.temperature_to_humidity.add_range(rl);
+                        rv.temperature_to_humidity.insert(rl.get_in_range(), rl.out_start);
                     }
                     Category::Humidity => {
-                        rv.humidity_to_location.add_range(rl);
+                        rv.humidity_to_location.insert(rl.get_in_range(), rl.out_start);
                     }
                     Category::Location => {
                         unreachable!(
@@ -571,13 +360,82 @@ pub mod solutions {
 
         tally
     }
+
+    #[solution(part2, composed)]
+    pub fn solve_part2_composed(input: &str) -> u64 {
+        let input = p2_parse_basic(input);
+        let almanac = input
+            .seed_to_soil
+            .then(&input.soil_to_fertilizer)
+            .then(&input.fertilizer_to_water)
+            .then(&input.water_to_light)
+            .then(&input.light_to_temperature)
+            .then(&input.temperature_to_humidity)
+            .then(&input.humidity_to_location);
+
+        let seeds: RangeSet<u64> = input.seeds.into_iter().collect();
+        almanac.map_set(&seeds).min().unwrap_or(u64::MAX)
+    }
+}
+
+#[allow(dead_code)]
+pub mod gen {
+    use rand::seq::SliceRandom;
+    use rand::Rng;
+
+    /// Ranges per map in a real input.
+    pub const REAL_SIZE: usize = 30;
+
+    const MAPS: [&str; 7] = [
+        "seed-to-soil",
+        "soil-to-fertilizer",
+        "fertilizer-to-water",
+        "water-to-light",
+        "light-to-temperature",
+        "temperature-to-humidity",
+        "humidity-to-location",
+    ];
+
+    /// An almanac over the real input's 32-bit value space, with `size` entries per map.
+    ///
+    /// Each map shuffles a contiguous block of ranges among themselves, as the real maps do. Seed ranges are at
+    /// most 1,000 long so the seed-by-seed part 2 solver can still check them.
+    pub fn input(rng: &mut impl Rng, size: usize) -> String {
+        let seeds: Vec<String> = (0..10)
+            .flat_map(|_| [rng.gen_range(0..u32::MAX as u64 - 1_000), rng.gen_range(1..=1_000)])
+            .map(|n| n.to_string())
+            .collect();
+        let mut rv = format!("seeds: {}\n", seeds.join(" "));
+
+        for name in MAPS {
+            let mut cuts: Vec<u64> = (0..=size).map(|_| rng.gen_range(0..=u32::MAX as u64)).collect();
+            cuts.sort_unstable();
+            cuts.dedup();
+            let mut sources: Vec<(u64, u64)> = cuts.windows(2).map(|w| (w[0], w[1] - w[0])).collect();
+            sources.shuffle(rng);
+
+            // Lay the shuffled source ranges end to end to get their destinations.
+            let mut dst = cuts[0];
+            let mut lines: Vec<String> = Vec::new();
+            for (src, len) in sources {
+                lines.push(format!("{} {} {}", dst, src, len));
+                dst += len;
+            }
+            lines.shuffle(rng);
+            rv.push_str(&format!("\n{} map:\n", name));
+            for ln in lines {
+                rv.push_str(&ln);
+                rv.push('\n');
+            }
+        }
+        rv
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::solutions::*;
-    use super::*;
-    use test_case::test_case;
+    use aoc_common::differential_test;
     use aoc_zen_runner_macros::aoc_case;
 
     #[aoc_case(35, 46)]
@@ -616,21 +474,12 @@ mod tests {
     56 93 4
     ";
 
-    #[test_case(5..=10, 7..=12, Some(7..=10); "overlap second-larger")]
-    #[test_case(3..=7, 5..=10, Some(5..=7); "overlap first-larger")]
-    #[test_case(3..=12, 5..=10, Some(5..=10); "overlap first-encompassing")]
-    #[test_case(7..=14, 3..=122, Some(7..=14); "overlap second-encompassing")]
-    #[test_case(7..=14, 22..=122, None; "No intersection")]
-    pub fn test_range_intersect(a: RangeInclusive<u64>, b: RangeInclusive<u64>, exp: Option<RangeInclusive<u64>>) {
-        assert_eq!(exp, range_intersect(&a, &b));
-    }
-
-    #[test_case(5..=10, 7..=12, vec![5..=6]; "overlap second-larger")]
-    #[test_case(3..=7, 5..=10, vec![3..=4]; "overlap first-larger")]
-    // #[test_case(3..=12, 5..=10, Some(5..=10); "overlap first-encompassing")]
-    #[test_case(7..=14, 3..=122, vec![]; "overlap second-encompassing")]
-    #[test_case(7..=14, 22..=122, vec![7..=14]; "No intersection")]
-    pub fn test_range_sub(a: RangeInclusive<u64>, b: RangeInclusive<u64>, exp: Vec<RangeInclusive<u64>>) {
-        assert_eq!(exp, range_sub(&a, &b));
-    }
+    differential_test!(
+        test_solvers_agree,
+        part1: [input1],
+        part2: [input1],
+        gen: super::gen::input,
+        size: 30,
+        cases: 20,
+    );
 }
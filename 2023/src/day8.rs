@@ -1,5 +1,6 @@
 use std::collections::HashMap;
 
+use aoc_common::graph_export::{self, Edge, GraphExport, Node};
 use aoc_zen_runner_macros::{aoc, generator, solution, solver};
 
 pub struct Input {
@@ -7,6 +8,36 @@ pub struct Input {
     nodes: HashMap<String, (String, String)>
 }
 
+impl Input {
+    /// The network for export, with each node's left and right turns as `L` and `R` edges. `walk` is the node left
+    /// and the instruction followed at each step, and only the edge taken is highlighted, even where `L` and `R` lead
+    /// to the same node.
+    pub fn export(&self, walk: &[(&str, char)]) -> GraphExport {
+        let mut rv = GraphExport::new(true);
+        let mut labels: Vec<&String> = self.nodes.keys().collect();
+        labels.sort();
+        for label in &labels {
+            rv.add_node(Node::new(label.as_str()));
+        }
+        let mut turns = HashMap::new();
+        for label in labels {
+            let (l, r) = &self.nodes[label];
+            let from = rv.add_node(Node::new(label.as_str()));
+            let (to_l, to_r) = (rv.add_node(Node::new(l.as_str())), rv.add_node(Node::new(r.as_str())));
+            turns.insert((label.as_str(), 'L'), rv.add_edge(Edge::new(from, to_l).label("L")));
+            turns.insert((label.as_str(), 'R'), rv.add_edge(Edge::new(from, to_r).label("R")));
+        }
+        for step in walk {
+            let Some(&edge) = turns.get(step) else { continue };
+            rv.edges[edge].highlight = true;
+            let (from, to) = (rv.edges[edge].from, rv.edges[edge].to);
+            rv.highlight_node(from);
+            rv.highlight_node(to);
+        }
+        rv
+    }
+}
+
 #[aoc(2023, day8)]
 pub mod solutions {
     use num::integer::lcm;
@@ -39,13 +70,19 @@ pub mod solutions {
     pub fn solve_part1(input: Input) -> usize {
         let instrs = input.instructions.chars().cycle().enumerate();
         let mut current = "AAA";
+        let recording = graph_export::enabled();
+        let mut walk = vec![];
 
         for (idx, step) in instrs {
             // println!("Step {}, Node: {}, instr: {}", idx, &current, &step);
             if current == "ZZZ" {
+                graph_export::emit(|| input.export(&walk));
                 return idx;
             }
 
+            if recording {
+                walk.push((current, step));
+            }
             let opts = &input.nodes[current];
             match step {
                 'L' => { current = &opts.0; }
@@ -121,6 +158,27 @@ AAA = (BBB, BBB)
 BBB = (AAA, ZZZ)
 ZZZ = (ZZZ, ZZZ)";
 
+    #[test]
+    pub fn test_export() {
+        let graph = input_generator(INPUT1).export(&[("AAA", 'R'), ("CCC", 'L')]);
+        assert_eq!(14, graph.edges.len());
+        let walked: Vec<_> = graph
+            .edges
+            .iter()
+            .filter(|e| e.highlight)
+            .map(|e| (graph.nodes[e.from].id.as_str(), graph.nodes[e.to].id.as_str(), e.label.as_deref()))
+            .collect();
+        assert_eq!(vec![("AAA", "CCC", Some("R")), ("CCC", "ZZZ", Some("L"))], walked);
+    }
+
+    #[test]
+    pub fn test_export_same_turns() {
+        let graph = input_generator("L\n\nAAA = (ZZZ, ZZZ)\nZZZ = (ZZZ, ZZZ)").export(&[("AAA", 'L')]);
+        let walked: Vec<_> = graph.edges.iter().filter(|e| e.highlight).map(|e| e.label.as_deref()).collect();
+        assert_eq!(vec![Some("L")], walked);
+        assert!(graph.nodes.iter().all(|n| n.highlight));
+    }
+
     #[test]
     pub fn p1_test2() {
         assert_eq!(6, solve_part1(input_generator(INPUT2)));
@@ -1,3 +1,4 @@
+use aoc_common::series;
 use aoc_zen_runner_macros::{aoc, generator, solution, solver};
 use itertools::Itertools;
 
@@ -5,28 +6,9 @@ use itertools::Itertools;
 pub mod solutions {
     use super::*;
 
+    /// The next item in the series we're iterating over (not the delta).
     pub fn extend_series_once(series: &mut impl Iterator<Item = i64>) -> i64 {
-        // Returns the next item in the series we're iterating over.
-        // DO NOT RETURN THE DELTA.
-        let first = series.next().unwrap();
-        let mut all_zero = true;
-        let mut last = first;
-
-        let series_diffs = series
-            .scan(first, |prev, curr| {
-                let diff = curr - *prev;
-                *prev = curr;
-                last = curr;
-                all_zero = all_zero && (diff == 0);
-                Some(diff)
-            })
-            .collect_vec();
-
-        if all_zero {
-            last
-        } else {
-            extend_series_once(&mut series_diffs.into_iter()) + last
-        }
+        series::next_term(&series.collect_vec())
     }
 
     // ----------------------- Part 1 -----------------------
@@ -0,0 +1,77 @@
+use aoc_common::grid::{Grid, Pos};
+use aoc_zen_runner_macros::{aoc, generator, solver};
+
+/// For every position, how many distinct hiking trails lead from it to each peak, keyed by the peak's index in
+/// `peaks`. Trails climb one unit of height per step.
+fn trails_to_peaks(grid: &Grid<u8>) -> Vec<Vec<(usize, usize)>> {
+    let index = |p: Pos| p.row * grid.cols() + p.col;
+    let mut rv: Vec<Vec<(usize, usize)>> = vec![Vec::new(); grid.rows() * grid.cols()];
+    let mut by_height: Vec<Pos> = grid.positions().collect();
+    by_height.sort_by_key(|&p| std::cmp::Reverse(grid[p]));
+    for p in by_height {
+        if grid[p] == 9 {
+            rv[index(p)] = vec![(index(p), 1)];
+            continue;
+        }
+        let mut counts: Vec<(usize, usize)> = Vec::new();
+        for (_, n) in grid.neighbors4(p).filter(|&(_, n)| grid[n] == grid[p] + 1) {
+            for &(peak, ways) in &rv[index(n)] {
+                match counts.iter_mut().find(|(k, _)| *k == peak) {
+                    Some((_, w)) => *w += ways,
+                    None => counts.push((peak, ways)),
+                }
+            }
+        }
+        rv[index(p)] = counts;
+    }
+    rv
+}
+
+#[aoc(2024, day10)]
+pub mod solutions {
+    use super::*;
+
+    #[generator(gen)]
+    pub fn input_generator(input: &str) -> Grid<u8> {
+        Grid::parse_with(input, |b| b - b'0')
+    }
+
+    // ----------------------- Part 1 -----------------------
+
+    #[solver(part1, dp)]
+    pub fn solve_part1(input: Grid<u8>) -> usize {
+        let trails = trails_to_peaks(&input);
+        input
+            .positions()
+            .filter(|&p| input[p] == 0)
+            .map(|p| trails[p.row * input.cols() + p.col].len())
+            .sum()
+    }
+
+    // ----------------------- Part 2 -----------------------
+
+    #[solver(part2, dp)]
+    pub fn solve_part2(input: Grid<u8>) -> usize {
+        let trails = trails_to_peaks(&input);
+        input
+            .positions()
+            .filter(|&p| input[p] == 0)
+            .flat_map(|p| trails[p.row * input.cols() + p.col].iter().map(|&(_, ways)| ways))
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_zen_runner_macros::aoc_case;
+
+    #[aoc_case(36, 81)]
+    const INPUT1: &str = "89010123
+78121874
+87430965
+96549874
+45678903
+32019012
+01329801
+10456732";
+}
@@ -0,0 +1,76 @@
+use std::collections::HashMap;
+
+use aoc_zen_runner_macros::{aoc, generator, solver};
+
+/// One blink's worth of changes to a single stone.
+fn blink(stone: u64) -> (u64, Option<u64>) {
+    if stone == 0 {
+        return (1, None);
+    }
+    let digits = stone.ilog10() + 1;
+    if digits.is_multiple_of(2) {
+        let half = 10u64.pow(digits / 2);
+        (stone / half, Some(stone % half))
+    } else {
+        (stone * 2024, None)
+    }
+}
+
+/// Stones only ever split, never interact, and the same numbers come up over and over, so track a count per number.
+fn stones_after(stones: &[u64], blinks: usize) -> u64 {
+    let mut counts: HashMap<u64, u64> = HashMap::new();
+    for &s in stones {
+        *counts.entry(s).or_default() += 1;
+    }
+    for _ in 0..blinks {
+        let mut next = HashMap::with_capacity(counts.len());
+        for (stone, n) in counts {
+            let (a, b) = blink(stone);
+            *next.entry(a).or_default() += n;
+            if let Some(b) = b {
+                *next.entry(b).or_default() += n;
+            }
+        }
+        counts = next;
+    }
+    counts.values().sum()
+}
+
+#[aoc(2024, day11)]
+pub mod solutions {
+    use super::*;
+
+    #[generator(gen)]
+    pub fn input_generator(input: &str) -> Vec<u64> {
+        input.split_ascii_whitespace().map(|n| n.parse().unwrap()).collect()
+    }
+
+    // ----------------------- Part 1 -----------------------
+
+    #[solver(part1, counts)]
+    pub fn solve_part1(input: Vec<u64>) -> u64 {
+        stones_after(&input, 25)
+    }
+
+    // ----------------------- Part 2 -----------------------
+
+    #[solver(part2, counts)]
+    pub fn solve_part2(input: Vec<u64>) -> u64 {
+        stones_after(&input, 75)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_zen_runner_macros::aoc_case;
+
+    #[aoc_case(55312)]
+    const INPUT1: &str = "125 17";
+
+    #[test]
+    pub fn test_blinks() {
+        assert_eq!(7, stones_after(&[0, 1, 10, 99, 999], 1));
+        assert_eq!(22, stones_after(&[125, 17], 6));
+    }
+}
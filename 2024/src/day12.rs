@@ -0,0 +1,106 @@
+use aoc_common::grid::{Dir, Grid, Pos};
+use aoc_zen_runner_macros::{aoc, generator, solver};
+
+/// Area, perimeter and number of sides of one region.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Region {
+    pub area: usize,
+    pub perimeter: usize,
+    pub sides: usize,
+}
+
+/// Every region of same-letter plots. A region has as many sides as corners, and each plot contributes one corner
+/// for each pair of adjacent directions that are both fenced, or both open but with the diagonal fenced.
+fn regions(grid: &Grid<u8>) -> Vec<Region> {
+    let same = |p: Pos, d_row: isize, d_col: isize| {
+        p.offset(d_row, d_col)
+            .and_then(|q| grid.get(q))
+            .is_some_and(|&c| c == grid[p])
+    };
+    let mut seen = Grid::new(grid.rows(), grid.cols(), false);
+    let mut rv = Vec::new();
+    for start in grid.positions() {
+        if seen[start] {
+            continue;
+        }
+        seen[start] = true;
+        let mut region = Region::default();
+        let mut stack = vec![start];
+        while let Some(p) = stack.pop() {
+            region.area += 1;
+            for d in Dir::ALL {
+                let (d_row, d_col) = d.delta();
+                let (c_row, c_col) = d.rotate_cw().delta();
+                let (a, b) = (same(p, d_row, d_col), same(p, c_row, c_col));
+                if !a && !b || a && b && !same(p, d_row + c_row, d_col + c_col) {
+                    region.sides += 1;
+                }
+                if !a {
+                    region.perimeter += 1;
+                } else {
+                    let n = grid.step(p, d).unwrap();
+                    if !seen[n] {
+                        seen[n] = true;
+                        stack.push(n);
+                    }
+                }
+            }
+        }
+        rv.push(region);
+    }
+    rv
+}
+
+#[aoc(2024, day12)]
+pub mod solutions {
+    use super::*;
+
+    #[generator(gen)]
+    pub fn input_generator(input: &str) -> Grid<u8> {
+        Grid::parse_bytes(input)
+    }
+
+    // ----------------------- Part 1 -----------------------
+
+    #[solver(part1, flood)]
+    pub fn solve_part1(input: Grid<u8>) -> usize {
+        regions(&input).iter().map(|r| r.area * r.perimeter).sum()
+    }
+
+    // ----------------------- Part 2 -----------------------
+
+    #[solver(part2, flood)]
+    pub fn solve_part2(input: Grid<u8>) -> usize {
+        regions(&input).iter().map(|r| r.area * r.sides).sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_zen_runner_macros::aoc_case;
+
+    #[aoc_case(140, 80)]
+    const INPUT1: &str = "AAAA
+BBCD
+BBCC
+EEEC";
+
+    #[aoc_case(772, 436)]
+    const INPUT2: &str = "OOOOO
+OXOXO
+OOOOO
+OXOXO
+OOOOO";
+
+    #[aoc_case(1930, 1206)]
+    const INPUT3: &str = "RRRRIICCFF
+RRRRIICCCF
+VVRRRCCFFF
+VVRCCCJFFF
+VVVVCJJCFE
+VVIVCCJJEE
+VVIIICJJEE
+MIIIIIJJEE
+MIIISIJEEE
+MMMISSJEEE";
+}
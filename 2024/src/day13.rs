@@ -0,0 +1,94 @@
+use aoc_zen_runner_macros::{aoc, generator, solver};
+use regex::Regex;
+
+#[derive(Debug, Clone, Copy)]
+pub struct Machine {
+    pub a: (i64, i64),
+    pub b: (i64, i64),
+    pub prize: (i64, i64),
+}
+
+impl Machine {
+    /// Tokens to win the prize, if it can be won. The two buttons give two linear equations in two unknowns, so
+    /// there is at most one way to win unless the buttons are parallel, which the input never has.
+    fn tokens(&self, offset: i64) -> Option<i64> {
+        let (px, py) = (self.prize.0 + offset, self.prize.1 + offset);
+        let det = self.a.0 * self.b.1 - self.a.1 * self.b.0;
+        assert_ne!(0, det, "Parallel buttons");
+        let a = (px * self.b.1 - py * self.b.0) / det;
+        let b = (self.a.0 * py - self.a.1 * px) / det;
+        let hits = a >= 0 && b >= 0 && (a * self.a.0 + b * self.b.0, a * self.a.1 + b * self.b.1) == (px, py);
+        hits.then_some(3 * a + b)
+    }
+}
+
+#[aoc(2024, day13)]
+pub mod solutions {
+    use super::*;
+
+    #[generator(gen)]
+    pub fn input_generator(input: &str) -> Vec<Machine> {
+        let re = Regex::new(r"X[+=](\d+), Y[+=](\d+)").unwrap();
+        let numbers: Vec<(i64, i64)> = re
+            .captures_iter(input)
+            .map(|c| (c[1].parse().unwrap(), c[2].parse().unwrap()))
+            .collect();
+        numbers
+            .chunks_exact(3)
+            .map(|m| Machine {
+                a: m[0],
+                b: m[1],
+                prize: m[2],
+            })
+            .collect()
+    }
+
+    // ----------------------- Part 1 -----------------------
+
+    #[solver(part1, cramer)]
+    pub fn solve_part1(input: Vec<Machine>) -> i64 {
+        input.iter().filter_map(|m| m.tokens(0)).sum()
+    }
+
+    // ----------------------- Part 2 -----------------------
+
+    #[solver(part2, cramer)]
+    pub fn solve_part2(input: Vec<Machine>) -> i64 {
+        input.iter().filter_map(|m| m.tokens(10_000_000_000_000)).sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::solutions::*;
+    use super::*;
+    use aoc_zen_runner_macros::aoc_case;
+
+    #[aoc_case(480)]
+    const INPUT1: &str = "Button A: X+94, Y+34
+Button B: X+22, Y+67
+Prize: X=8400, Y=5400
+
+Button A: X+26, Y+66
+Button B: X+67, Y+21
+Prize: X=12748, Y=12176
+
+Button A: X+17, Y+86
+Button B: X+84, Y+37
+Prize: X=7870, Y=6450
+
+Button A: X+69, Y+23
+Button B: X+27, Y+71
+Prize: X=18641, Y=10279";
+
+    #[test]
+    pub fn test_far_prizes() {
+        // Only the second and fourth machines can be won once the prizes move.
+        let machines = input_generator(INPUT1);
+        let winnable: Vec<bool> = machines
+            .iter()
+            .map(|m| m.tokens(10_000_000_000_000).is_some())
+            .collect();
+        assert_eq!(vec![false, true, false, true], winnable);
+    }
+}
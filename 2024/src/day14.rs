@@ -0,0 +1,112 @@
+use aoc_common::params::Params as _;
+use aoc_zen_runner_macros::{aoc, generator, solver};
+use regex::Regex;
+
+#[derive(Debug, Clone, Copy)]
+pub struct Robot {
+    pub pos: (i64, i64),
+    pub vel: (i64, i64),
+}
+
+impl Robot {
+    fn after(&self, seconds: i64, (width, height): (i64, i64)) -> (i64, i64) {
+        (
+            (self.pos.0 + seconds * self.vel.0).rem_euclid(width),
+            (self.pos.1 + seconds * self.vel.1).rem_euclid(height),
+        )
+    }
+}
+
+#[aoc(2024, day14)]
+pub mod solutions {
+    use super::*;
+
+    aoc_common::params! {
+        pub struct Params {
+            width: i64 = 101,
+            height: i64 = 103,
+        }
+    }
+
+    #[generator(gen)]
+    pub fn input_generator(input: &str) -> Vec<Robot> {
+        let re = Regex::new(r"p=(-?\d+),(-?\d+) v=(-?\d+),(-?\d+)").unwrap();
+        re.captures_iter(input)
+            .map(|c| {
+                let n = |i: usize| c[i].parse::<i64>().unwrap();
+                Robot {
+                    pos: (n(1), n(2)),
+                    vel: (n(3), n(4)),
+                }
+            })
+            .collect()
+    }
+
+    // ----------------------- Part 1 -----------------------
+
+    #[solver(part1, quadrants)]
+    pub fn solve_part1(input: Vec<Robot>) -> usize {
+        let Params { width, height } = Params::current();
+        let mut quadrants = [0; 4];
+        for (x, y) in input.iter().map(|r| r.after(100, (width, height))) {
+            if x != width / 2 && y != height / 2 {
+                quadrants[(x > width / 2) as usize * 2 + (y > height / 2) as usize] += 1;
+            }
+        }
+        quadrants.iter().product()
+    }
+
+    // ----------------------- Part 2 -----------------------
+
+    /// The robots were placed by working backwards from the picture, which is the first time no two robots share
+    /// a tile. Every robot is back where it started after `width * height` seconds.
+    #[solver(part2, distinct)]
+    pub fn solve_part2(input: Vec<Robot>) -> i64 {
+        let Params { width, height } = Params::current();
+        let mut seen = vec![usize::MAX; (width * height) as usize];
+        (0..width * height)
+            .find(|&t| {
+                input.iter().all(|r| {
+                    let (x, y) = r.after(t, (width, height));
+                    let cell = &mut seen[(y * width + x) as usize];
+                    let free = *cell != t as usize;
+                    *cell = t as usize;
+                    free
+                })
+            })
+            .expect("Robots never spread out")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::solutions::*;
+    use aoc_common::param_case;
+    use aoc_common::params;
+
+    const INPUT1: &str = "p=0,4 v=3,-3
+p=6,3 v=-1,-3
+p=10,3 v=-1,2
+p=2,0 v=2,-1
+p=0,0 v=1,3
+p=3,0 v=-2,-2
+p=7,6 v=-1,-3
+p=3,0 v=-1,-2
+p=9,3 v=2,3
+p=7,3 v=-1,2
+p=2,4 v=2,-3
+p=9,5 v=-3,-3";
+
+    param_case!(aoc_test_input1, INPUT1, { width: 11, height: 7 }, 12);
+
+    #[test]
+    pub fn test_spread_out() {
+        // Three robots that only stop overlapping once the two at the origin have moved apart.
+        let robots = input_generator("p=0,0 v=1,0\np=0,0 v=2,0\np=5,5 v=0,1");
+        let overrides = vec![
+            ("width".to_string(), "11".to_string()),
+            ("height".to_string(), "7".to_string()),
+        ];
+        assert_eq!(1, params::with_overrides(overrides, || solve_part2(robots.clone())));
+    }
+}
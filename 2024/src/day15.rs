@@ -0,0 +1,180 @@
+use aoc_common::grid::{Dir, Grid, Pos};
+use aoc_zen_runner_macros::{aoc, generator, solver};
+
+#[derive(Debug, Clone)]
+pub struct Warehouse {
+    pub grid: Grid<u8>,
+    pub moves: Vec<Dir>,
+}
+
+impl Warehouse {
+    /// The same warehouse with everything but the robot twice as wide.
+    fn widen(&self) -> Warehouse {
+        let cells: Vec<u8> = self
+            .grid
+            .iter()
+            .flat_map(|&b| match b {
+                b'O' => *b"[]",
+                b'@' => *b"@.",
+                b => [b, b],
+            })
+            .collect();
+        Warehouse {
+            grid: Grid::from_vec(cells, self.grid.cols() * 2),
+            moves: self.moves.clone(),
+        }
+    }
+
+    /// Whether whatever is at `pos` can be pushed one step in `dir`, along with anything it pushes in turn.
+    fn can_push(&self, pos: Pos, dir: Dir) -> bool {
+        match self.grid[pos] {
+            b'.' => true,
+            b'#' => false,
+            b'[' | b']' if matches!(dir, Dir::North | Dir::South) => {
+                let other = self.other_half(pos);
+                self.can_push(pos.step(dir).unwrap(), dir) && self.can_push(other.step(dir).unwrap(), dir)
+            }
+            _ => self.can_push(pos.step(dir).unwrap(), dir),
+        }
+    }
+
+    fn other_half(&self, pos: Pos) -> Pos {
+        match self.grid[pos] {
+            b'[' => pos.step(Dir::East).unwrap(),
+            _ => pos.step(Dir::West).unwrap(),
+        }
+    }
+
+    /// Push whatever is at `pos` one step in `dir`; `can_push` must already have said yes.
+    fn push(&mut self, pos: Pos, dir: Dir) {
+        let cell = self.grid[pos];
+        if cell == b'.' {
+            return;
+        }
+        let next = pos.step(dir).unwrap();
+        if matches!(cell, b'[' | b']') && matches!(dir, Dir::North | Dir::South) {
+            let other = self.other_half(pos);
+            let other_next = other.step(dir).unwrap();
+            self.push(next, dir);
+            self.push(other_next, dir);
+            self.grid[other_next] = self.grid[other];
+            self.grid[other] = b'.';
+        } else {
+            self.push(next, dir);
+        }
+        self.grid[next] = cell;
+        self.grid[pos] = b'.';
+    }
+
+    /// Run every move and sum the GPS coordinates of the boxes left behind.
+    fn run(mut self) -> usize {
+        let mut robot = self.grid.positions().find(|&p| self.grid[p] == b'@').expect("No robot");
+        for dir in std::mem::take(&mut self.moves) {
+            if self.can_push(robot, dir) {
+                self.push(robot, dir);
+                robot = robot.step(dir).unwrap();
+            }
+        }
+        self.grid
+            .positions()
+            .filter(|&p| matches!(self.grid[p], b'O' | b'['))
+            .map(|p| 100 * p.row + p.col)
+            .sum()
+    }
+}
+
+#[aoc(2024, day15)]
+pub mod solutions {
+    use super::*;
+
+    #[generator(gen)]
+    pub fn input_generator(input: &str) -> Warehouse {
+        let (map, moves) = input.split_once("\n\n").expect("No blank line before the moves");
+        Warehouse {
+            grid: Grid::parse_bytes(map),
+            moves: moves
+                .bytes()
+                .filter(|b| !b.is_ascii_whitespace())
+                .map(|b| match b {
+                    b'^' => Dir::North,
+                    b'>' => Dir::East,
+                    b'v' => Dir::South,
+                    b'<' => Dir::West,
+                    _ => panic!("Bad move: {}", b as char),
+                })
+                .collect(),
+        }
+    }
+
+    // ----------------------- Part 1 -----------------------
+
+    #[solver(part1, push)]
+    pub fn solve_part1(input: Warehouse) -> usize {
+        input.run()
+    }
+
+    // ----------------------- Part 2 -----------------------
+
+    #[solver(part2, push)]
+    pub fn solve_part2(input: Warehouse) -> usize {
+        input.widen().run()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::solutions::*;
+    use super::*;
+    use aoc_zen_runner_macros::aoc_case;
+
+    #[aoc_case(2028)]
+    const INPUT1: &str = "########
+#..O.O.#
+##@.O..#
+#...O..#
+#.#.O..#
+#...O..#
+#......#
+########
+
+<^^>>>vv<v>>v<<";
+
+    #[aoc_case(10092, 9021)]
+    const INPUT2: &str = "##########
+#..O..O.O#
+#......O.#
+#.OO..O.O#
+#..O@..O.#
+#O#..O...#
+#O..O..O.#
+#.OO.O.OO#
+#....O...#
+##########
+
+<vv>^<v^>v>^vv^v>v<>v^v<v<^vv<<<^><<><>>v<vvv<>^v^>^<<<><<v<<<v^vv^v>^
+vvv<<^>^v^^><<>>><>^<<><^vv^^<>vvv<>><^^v>^>vv<>v<<<<v<^v>^<^^>>>^<v<v
+><>vv>v^v^<>><>>>><^^>vv>v<^^^>>v^v^<^^>v^^>v^<^v>v<>>v^v^<v>v^^<^^vv<
+<<v<^>>^^^^>>>v^<>vvv^><v<<<>^^^vv^<vvv>^>v<^^^^v<>^>vvvv><>>v^<<^^^^^
+^><^><>>><>^^<<^^v>>><^<v>^<vv>>v>>>^v><>^v><<<<v>>v<v<v>vvv>^<><<>^><
+^>><>^v<><^vvv<^^<><v<<<<<><^v<<<><<<^^<v<^^^><^>>^<v^><<<^>>^v<v^v<v^
+>^>>^v>vv>^<<^v<>><<><<v<<v><>v<^vv<<<>^^v^>^^>>><<^v>>v^v><^^>>^<>vv^
+<><^^>^^^<><vvvvv^v<v<<>^v<v>v<<^><<><<><<<^^<<<^<<>><<><^^^>^^<>^>v<>
+^^>vv<^v^v<vv>^<><v<^v>^^^>>>^^vvv^>vvv<>>>^<^>>>>>^<<^v>^vvv<>^<><<v>
+v^^>>><<^^<>>^v^<v^vv<>v^<<>^<^v^v><^<<<><<^<v><v<>vv>>v><v^<vv<>v^<<^";
+
+    #[test]
+    pub fn test_wide_boxes() {
+        // Three wide boxes pushed left and up as a stack; the GPS coordinates use their left halves.
+        assert_eq!(105 + 207 + 306, solve_part2(input_generator(INPUT3)));
+    }
+
+    const INPUT3: &str = "#######
+#...#.#
+#.....#
+#..OO@#
+#..O..#
+#.....#
+#######
+
+<vv<<^^<<^^";
+}
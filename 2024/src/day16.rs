@@ -0,0 +1,145 @@
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+use aoc_common::grid::{Dir, Grid, Pos};
+use aoc_zen_runner_macros::{aoc, generator, solver};
+
+pub struct Maze {
+    /// `true` for each wall.
+    pub walls: Grid<bool>,
+    pub start: Pos,
+    pub end: Pos,
+}
+
+/// Cheapest score to reach each (position, facing) state from `starts`, moving forwards if `forwards` and
+/// backwards otherwise. Indexed by `state_index`.
+fn scores(maze: &Maze, starts: &[(Pos, Dir)], forwards: bool) -> Vec<u64> {
+    let mut best = vec![u64::MAX; maze.walls.rows() * maze.walls.cols() * 4];
+    let index = |p: Pos, d: Dir| state_index(&maze.walls, p, d);
+    let mut heap = BinaryHeap::new();
+    for &(p, d) in starts {
+        best[index(p, d)] = 0;
+        heap.push(Reverse((0, p, d)));
+    }
+    while let Some(Reverse((score, p, d))) = heap.pop() {
+        if score > best[index(p, d)] {
+            continue;
+        }
+        let step = if forwards { d } else { -d };
+        let moved = maze
+            .walls
+            .step(p, step)
+            .filter(|&n| !maze.walls[n])
+            .map(|n| (score + 1, n, d));
+        for (s, n, nd) in moved
+            .into_iter()
+            .chain([(score + 1000, p, d.rotate_cw()), (score + 1000, p, d.rotate_ccw())])
+        {
+            if s < best[index(n, nd)] {
+                best[index(n, nd)] = s;
+                heap.push(Reverse((s, n, nd)));
+            }
+        }
+    }
+    best
+}
+
+fn state_index(grid: &Grid<bool>, p: Pos, d: Dir) -> usize {
+    (p.row * grid.cols() + p.col) * 4 + d as usize
+}
+
+#[aoc(2024, day16)]
+pub mod solutions {
+    use super::*;
+
+    #[generator(gen)]
+    pub fn input_generator(input: &str) -> Maze {
+        let grid = Grid::parse_bytes(input);
+        let find = |b: u8| grid.positions().find(|&p| grid[p] == b).unwrap();
+        Maze {
+            start: find(b'S'),
+            end: find(b'E'),
+            walls: grid.map(|&b| b == b'#'),
+        }
+    }
+
+    // ----------------------- Part 1 -----------------------
+
+    #[solver(part1, dijkstra)]
+    pub fn solve_part1(input: Maze) -> u64 {
+        let best = scores(&input, &[(input.start, Dir::East)], true);
+        Dir::ALL
+            .iter()
+            .map(|&d| best[state_index(&input.walls, input.end, d)])
+            .min()
+            .unwrap()
+    }
+
+    // ----------------------- Part 2 -----------------------
+
+    /// A tile is on some best path if the cheapest way there from the start plus the cheapest way on from there to
+    /// the end adds up to the best score.
+    #[solver(part2, dijkstra)]
+    pub fn solve_part2(input: Maze) -> usize {
+        let from_start = scores(&input, &[(input.start, Dir::East)], true);
+        let index = |p: Pos, d: Dir| state_index(&input.walls, p, d);
+        let target = Dir::ALL.iter().map(|&d| from_start[index(input.end, d)]).min().unwrap();
+        let ends: Vec<(Pos, Dir)> = Dir::ALL
+            .into_iter()
+            .filter(|&d| from_start[index(input.end, d)] == target)
+            .map(|d| (input.end, d))
+            .collect();
+        let to_end = scores(&input, &ends, false);
+        input
+            .walls
+            .positions()
+            .filter(|&p| {
+                Dir::ALL.iter().any(|&d| {
+                    let (a, b) = (from_start[index(p, d)], to_end[index(p, d)]);
+                    a != u64::MAX && b != u64::MAX && a + b == target
+                })
+            })
+            .count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_zen_runner_macros::aoc_case;
+
+    #[aoc_case(7036, 45)]
+    const INPUT1: &str = "###############
+#.......#....E#
+#.#.###.#.###.#
+#.....#.#...#.#
+#.###.#####.#.#
+#.#.#.......#.#
+#.#.#####.###.#
+#...........#.#
+###.#.#####.#.#
+#...#.....#.#.#
+#.#.#.###.#.#.#
+#.....#...#.#.#
+#.###.#.#.#.#.#
+#S..#.....#...#
+###############";
+
+    #[aoc_case(11048, 64)]
+    const INPUT2: &str = "#################
+#...#...#...#..E#
+#.#.#.#.#.#.#.#.#
+#.#.#.#...#...#.#
+#.#.#.#.###.#.#.#
+#...#.#.#.....#.#
+#.#.#.#.#.#####.#
+#.#...#.#.#.....#
+#.#.#####.#.###.#
+#.#.#.......#...#
+#.#.###.#####.###
+#.#.#...#.....#.#
+#.#.#.#####.###.#
+#.#.#.........#.#
+#.#.#.#########.#
+#S#.............#
+#################";
+}
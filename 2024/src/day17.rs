@@ -0,0 +1,117 @@
+use aoc_zen_runner_macros::{aoc, generator, solver};
+
+#[derive(Debug, Clone)]
+pub struct Computer {
+    pub registers: [u64; 3],
+    pub program: Vec<u8>,
+}
+
+impl Computer {
+    /// Run the program from the start with register A set to `a`, returning everything it outputs.
+    fn run(&self, a: u64) -> Vec<u8> {
+        let mut reg = self.registers;
+        reg[0] = a;
+        let mut out = Vec::new();
+        let mut ip = 0;
+        while ip + 1 < self.program.len() {
+            let (op, literal) = (self.program[ip], self.program[ip + 1] as u64);
+            let combo = || match literal {
+                0..=3 => literal,
+                4..=6 => reg[literal as usize - 4],
+                _ => panic!("Reserved combo operand"),
+            };
+            ip += 2;
+            match op {
+                0 => reg[0] >>= combo(),
+                1 => reg[1] ^= literal,
+                2 => reg[1] = combo() % 8,
+                3 if reg[0] != 0 => ip = literal as usize,
+                3 => {}
+                4 => reg[1] ^= reg[2],
+                5 => out.push((combo() % 8) as u8),
+                6 => reg[1] = reg[0] >> combo(),
+                7 => reg[2] = reg[0] >> combo(),
+                _ => panic!("Bad opcode: {}", op),
+            }
+        }
+        out
+    }
+}
+
+#[aoc(2024, day17)]
+pub mod solutions {
+    use super::*;
+
+    #[generator(gen)]
+    pub fn input_generator(input: &str) -> Computer {
+        let mut registers = [0; 3];
+        let mut program = Vec::new();
+        for ln in input.lines() {
+            if let Some((name, value)) = ln.strip_prefix("Register ").and_then(|r| r.split_once(": ")) {
+                registers[(name.as_bytes()[0] - b'A') as usize] = value.trim().parse().unwrap();
+            } else if let Some(p) = ln.strip_prefix("Program: ") {
+                program = p.trim().split(',').map(|n| n.parse().unwrap()).collect();
+            }
+        }
+        Computer { registers, program }
+    }
+
+    // ----------------------- Part 1 -----------------------
+
+    #[solver(part1, interpret)]
+    pub fn solve_part1(input: Computer) -> String {
+        let out: Vec<String> = input.run(input.registers[0]).iter().map(|n| n.to_string()).collect();
+        out.join(",")
+    }
+
+    // ----------------------- Part 2 -----------------------
+
+    /// The program loops shifting A right three bits at a time, printing one number per loop that depends only on
+    /// the bits of A not yet shifted away. So the last output is decided by A's top three bits, and we can pick A
+    /// three bits at a time from the top, matching ever longer tails of the program.
+    #[solver(part2, octal)]
+    pub fn solve_part2(input: Computer) -> u64 {
+        fn search(computer: &Computer, a: u64, matched: usize) -> Option<u64> {
+            if matched == computer.program.len() {
+                return Some(a);
+            }
+            (0..8).find_map(|digit| {
+                let next = a << 3 | digit;
+                let tail = &computer.program[computer.program.len() - matched - 1..];
+                (next != 0 && computer.run(next) == tail)
+                    .then(|| search(computer, next, matched + 1))
+                    .flatten()
+            })
+        }
+        search(&input, 0, 0).expect("No value of A makes the program output itself")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::solutions::*;
+    use super::*;
+    use aoc_zen_runner_macros::aoc_case;
+
+    #[aoc_case("4,6,3,5,6,3,5,2,1,0")]
+    const INPUT1: &str = "Register A: 729
+Register B: 0
+Register C: 0
+
+Program: 0,1,5,4,3,0";
+
+    #[aoc_case("5,7,3,0", 117440)]
+    const INPUT2: &str = "Register A: 2024
+Register B: 0
+Register C: 0
+
+Program: 0,3,5,4,3,0";
+
+    #[test]
+    pub fn test_instructions() {
+        let computer = input_generator("Register A: 10\nRegister B: 0\nRegister C: 0\n\nProgram: 5,0,5,1,5,4");
+        assert_eq!(vec![0, 1, 2], computer.run(10));
+        let computer = input_generator("Register A: 0\nRegister B: 29\nRegister C: 0\n\nProgram: 1,7,5,5");
+        assert_eq!(vec![2], computer.run(0));
+    }
+}
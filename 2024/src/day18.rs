@@ -0,0 +1,119 @@
+use std::collections::VecDeque;
+
+use aoc_common::grid::{Grid, Pos};
+use aoc_common::params::Params as _;
+use aoc_zen_runner_macros::{aoc, generator, solver};
+
+/// Fewest steps from the top left to the bottom right of `blocked`, if there's a way through.
+fn shortest_path(blocked: &Grid<bool>) -> Option<usize> {
+    let end = Pos::new(blocked.rows() - 1, blocked.cols() - 1);
+    let mut dist = Grid::new(blocked.rows(), blocked.cols(), usize::MAX);
+    dist[(0, 0)] = 0;
+    let mut queue = VecDeque::from([Pos::new(0, 0)]);
+    while let Some(p) = queue.pop_front() {
+        if p == end {
+            return Some(dist[p]);
+        }
+        for (_, n) in blocked.neighbors4(p) {
+            if !blocked[n] && dist[n] == usize::MAX {
+                dist[n] = dist[p] + 1;
+                queue.push_back(n);
+            }
+        }
+    }
+    None
+}
+
+#[aoc(2024, day18)]
+pub mod solutions {
+    use super::*;
+
+    aoc_common::params! {
+        pub struct Params {
+            /// Largest coordinate in the memory space.
+            size: usize = 70,
+            /// Bytes fallen before part 1's walk.
+            fallen: usize = 1024,
+        }
+    }
+
+    fn memory_after(bytes: &[Pos], fallen: usize) -> Grid<bool> {
+        let size = Params::current().size;
+        let mut grid = Grid::new(size + 1, size + 1, false);
+        for &p in &bytes[..fallen] {
+            grid[p] = true;
+        }
+        grid
+    }
+
+    /// Positions of the falling bytes, as (row = Y, col = X).
+    #[generator(gen)]
+    pub fn input_generator(input: &str) -> Vec<Pos> {
+        input
+            .lines()
+            .filter(|ln| !ln.trim().is_empty())
+            .map(|ln| {
+                let (x, y) = ln.trim().split_once(',').expect("Bad byte position");
+                Pos::new(y.parse().unwrap(), x.parse().unwrap())
+            })
+            .collect()
+    }
+
+    // ----------------------- Part 1 -----------------------
+
+    #[solver(part1, bfs)]
+    pub fn solve_part1(input: Vec<Pos>) -> usize {
+        shortest_path(&memory_after(&input, Params::current().fallen)).expect("No way out")
+    }
+
+    // ----------------------- Part 2 -----------------------
+
+    /// Binary search for the first byte that leaves no way through.
+    #[solver(part2, bisect)]
+    pub fn solve_part2(input: Vec<Pos>) -> String {
+        let (mut lo, mut hi) = (0, input.len());
+        while lo < hi {
+            let mid = (lo + hi) / 2;
+            if shortest_path(&memory_after(&input, mid + 1)).is_some() {
+                lo = mid + 1;
+            } else {
+                hi = mid;
+            }
+        }
+        let p = input.get(lo).expect("The way out is never cut off");
+        format!("{},{}", p.col, p.row)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::param_case;
+
+    const INPUT1: &str = "5,4
+4,2
+4,5
+3,0
+2,1
+6,3
+2,4
+1,5
+0,6
+3,3
+2,6
+5,1
+1,2
+5,5
+2,5
+6,5
+1,4
+0,4
+6,4
+1,1
+6,1
+1,0
+0,5
+1,6
+2,0";
+
+    param_case!(aoc_test_input1, INPUT1, { size: 6, fallen: 12 }, 22, "6,1");
+}
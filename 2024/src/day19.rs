@@ -0,0 +1,74 @@
+use aoc_zen_runner_macros::{aoc, generator, solver};
+
+pub struct Onsen {
+    pub towels: Vec<String>,
+    pub designs: Vec<String>,
+}
+
+impl Onsen {
+    /// How many ways `design` can be made by lining up towels, by the number of ways to make each prefix.
+    fn arrangements(&self, design: &str) -> u64 {
+        let mut ways = vec![0u64; design.len() + 1];
+        ways[0] = 1;
+        for end in 1..=design.len() {
+            ways[end] = self
+                .towels
+                .iter()
+                .filter(|t| design[..end].ends_with(t.as_str()))
+                .map(|t| ways[end - t.len()])
+                .sum();
+        }
+        ways[design.len()]
+    }
+}
+
+#[aoc(2024, day19)]
+pub mod solutions {
+    use super::*;
+
+    #[generator(gen)]
+    pub fn input_generator(input: &str) -> Onsen {
+        let mut lines = input.lines().map(str::trim).filter(|ln| !ln.is_empty());
+        let towels = lines
+            .next()
+            .expect("No towels")
+            .split(", ")
+            .map(str::to_string)
+            .collect();
+        Onsen {
+            towels,
+            designs: lines.map(str::to_string).collect(),
+        }
+    }
+
+    // ----------------------- Part 1 -----------------------
+
+    #[solver(part1, prefixes)]
+    pub fn solve_part1(input: Onsen) -> usize {
+        input.designs.iter().filter(|d| input.arrangements(d) > 0).count()
+    }
+
+    // ----------------------- Part 2 -----------------------
+
+    #[solver(part2, prefixes)]
+    pub fn solve_part2(input: Onsen) -> u64 {
+        input.designs.iter().map(|d| input.arrangements(d)).sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_zen_runner_macros::aoc_case;
+
+    #[aoc_case(6, 16)]
+    const INPUT1: &str = "r, wr, b, g, bwu, rb, gb, br
+
+brwrr
+bggr
+gbbr
+rrbgbr
+ubwu
+bwurrg
+brgr
+bbrgwb";
+}
@@ -0,0 +1,97 @@
+use std::collections::VecDeque;
+
+use aoc_common::grid::{Grid, Pos};
+use aoc_common::params::Params as _;
+use aoc_zen_runner_macros::{aoc, generator, solver};
+
+/// The racetrack has a single path from start to end; these are its tiles in order.
+fn race_path(grid: &Grid<u8>) -> Vec<Pos> {
+    let start = grid.positions().find(|&p| grid[p] == b'S').expect("No start");
+    let mut dist = Grid::new(grid.rows(), grid.cols(), usize::MAX);
+    dist[start] = 0;
+    let mut path = Vec::new();
+    let mut queue = VecDeque::from([start]);
+    while let Some(p) = queue.pop_front() {
+        path.push(p);
+        for (_, n) in grid.neighbors4(p) {
+            if grid[n] != b'#' && dist[n] == usize::MAX {
+                dist[n] = dist[p] + 1;
+                queue.push_back(n);
+            }
+        }
+    }
+    path
+}
+
+#[aoc(2024, day20)]
+pub mod solutions {
+    use super::*;
+
+    aoc_common::params! {
+        pub struct Params {
+            /// Only count cheats that save at least this many picoseconds.
+            min_saving: usize = 100,
+        }
+    }
+
+    /// Cheats of up to `max_cheat` picoseconds that save at least `min_saving`. A cheat joins two tiles of the
+    /// path within `max_cheat` of each other, so compare every pair of tiles far enough apart along the path.
+    fn cheats(path: &[Pos], max_cheat: usize) -> usize {
+        let min_saving = Params::current().min_saving;
+        (0..path.len())
+            .map(|i| {
+                path.iter()
+                    .enumerate()
+                    .skip(i + min_saving)
+                    .filter(|&(j, &q)| {
+                        let d = path[i].manhattan(q);
+                        d <= max_cheat && j - i >= min_saving + d
+                    })
+                    .count()
+            })
+            .sum()
+    }
+
+    #[generator(gen)]
+    pub fn input_generator(input: &str) -> Vec<Pos> {
+        race_path(&Grid::parse_bytes(input))
+    }
+
+    // ----------------------- Part 1 -----------------------
+
+    #[solver(part1, pairs)]
+    pub fn solve_part1(input: Vec<Pos>) -> usize {
+        cheats(&input, 2)
+    }
+
+    // ----------------------- Part 2 -----------------------
+
+    #[solver(part2, pairs)]
+    pub fn solve_part2(input: Vec<Pos>) -> usize {
+        cheats(&input, 20)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::param_case;
+
+    const INPUT1: &str = "###############
+#...#...#.....#
+#.#.#.#.#.###.#
+#S#...#.#.#...#
+#######.#.#.###
+#######.#.#...#
+#######.#.###.#
+###..E#...#...#
+###.#######.###
+#...###...#...#
+#.#####.#.###.#
+#.#...#.#.#...#
+#.#.#.#.#.#.###
+#...#...#...###
+###############";
+
+    param_case!(aoc_test_input1, INPUT1, { min_saving: 64 }, 1, 86);
+    param_case!(aoc_test_input1_small_savings, INPUT1, { min_saving: 20 }, 5, 1449);
+}
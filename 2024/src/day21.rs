@@ -0,0 +1,126 @@
+use std::collections::HashMap;
+
+use aoc_zen_runner_macros::{aoc, generator, solver};
+
+const NUMERIC: [&str; 4] = ["789", "456", "123", " 0A"];
+const DIRECTIONAL: [&str; 2] = [" ^A", "<v>"];
+
+fn key_position(pad: &[&str], key: u8) -> (i64, i64) {
+    pad.iter()
+        .enumerate()
+        .find_map(|(r, row)| row.bytes().position(|b| b == key).map(|c| (r as i64, c as i64)))
+        .unwrap_or_else(|| panic!("No key {}", key as char))
+}
+
+/// A chain of robots, each typing on the keypad below with the one above directing it.
+struct Chain {
+    /// Fewest presses to move from one key to another and press it, on a directional keypad with `depth`
+    /// directional keypads above it.
+    memo: HashMap<(u8, u8, usize), u64>,
+}
+
+impl Chain {
+    /// Fewest presses on the keypad `depth` levels up to type `keys` on `pad`, starting and ending on `A`.
+    fn presses(&mut self, pad: &[&str], keys: &[u8], depth: usize) -> u64 {
+        let mut from = b'A';
+        let mut rv = 0;
+        for &to in keys {
+            rv += self.step(pad, from, to, depth);
+            from = to;
+        }
+        rv
+    }
+
+    /// Moving between two keys, it's always best to do all the moves in one direction together, and there are at
+    /// most two such orders, one of which may cross the gap.
+    fn step(&mut self, pad: &[&str], from: u8, to: u8, depth: usize) -> u64 {
+        if depth == 0 {
+            return 1;
+        }
+        let directional = pad.len() == DIRECTIONAL.len();
+        if directional {
+            if let Some(&n) = self.memo.get(&(from, to, depth)) {
+                return n;
+            }
+        }
+
+        let ((fr, fc), (tr, tc)) = (key_position(pad, from), key_position(pad, to));
+        let gap = key_position(pad, b' ');
+        let vertical = vec![if tr > fr { b'v' } else { b'^' }; fr.abs_diff(tr) as usize];
+        let horizontal = vec![if tc > fc { b'>' } else { b'<' }; fc.abs_diff(tc) as usize];
+        let mut best = u64::MAX;
+        // Horizontal first turns the corner at (fr, tc); vertical first turns it at (tr, fc).
+        for (corner, first, second) in [((fr, tc), &horizontal, &vertical), ((tr, fc), &vertical, &horizontal)] {
+            if corner == gap {
+                continue;
+            }
+            let keys: Vec<u8> = first.iter().chain(second).copied().chain([b'A']).collect();
+            best = best.min(self.presses(&DIRECTIONAL, &keys, depth - 1));
+        }
+
+        if directional {
+            self.memo.insert((from, to, depth), best);
+        }
+        best
+    }
+}
+
+#[aoc(2024, day21)]
+pub mod solutions {
+    use super::*;
+
+    fn complexity(codes: &[String], robots: usize) -> u64 {
+        let mut chain = Chain { memo: HashMap::new() };
+        codes
+            .iter()
+            .map(|code| {
+                let numeric: u64 = code.trim_end_matches('A').parse().unwrap();
+                numeric * chain.presses(&NUMERIC, code.as_bytes(), robots + 1)
+            })
+            .sum()
+    }
+
+    #[generator(gen)]
+    pub fn input_generator(input: &str) -> Vec<String> {
+        input
+            .lines()
+            .map(str::trim)
+            .filter(|ln| !ln.is_empty())
+            .map(str::to_string)
+            .collect()
+    }
+
+    // ----------------------- Part 1 -----------------------
+
+    #[solver(part1, memo)]
+    pub fn solve_part1(input: Vec<String>) -> u64 {
+        complexity(&input, 2)
+    }
+
+    // ----------------------- Part 2 -----------------------
+
+    #[solver(part2, memo)]
+    pub fn solve_part2(input: Vec<String>) -> u64 {
+        complexity(&input, 25)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_zen_runner_macros::aoc_case;
+
+    #[aoc_case(126384, 154115708116294)]
+    const INPUT1: &str = "029A
+980A
+179A
+456A
+379A";
+
+    #[test]
+    pub fn test_presses() {
+        let mut chain = Chain { memo: HashMap::new() };
+        assert_eq!(68, chain.presses(&NUMERIC, b"029A", 3));
+        assert_eq!(12, chain.presses(&NUMERIC, b"029A", 1));
+    }
+}
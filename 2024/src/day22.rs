@@ -0,0 +1,80 @@
+use aoc_zen_runner_macros::{aoc, generator, solver};
+
+fn next_secret(mut n: u64) -> u64 {
+    const PRUNE: u64 = 1 << 24;
+    n = (n ^ (n << 6)) % PRUNE;
+    n = (n ^ (n >> 5)) % PRUNE;
+    (n ^ (n << 11)) % PRUNE
+}
+
+#[aoc(2024, day22)]
+pub mod solutions {
+    use super::*;
+
+    #[generator(gen)]
+    pub fn input_generator(input: &str) -> Vec<u64> {
+        input.split_ascii_whitespace().map(|n| n.parse().unwrap()).collect()
+    }
+
+    // ----------------------- Part 1 -----------------------
+
+    #[solver(part1, iterate)]
+    pub fn solve_part1(input: Vec<u64>) -> u64 {
+        input.iter().map(|&n| (0..2000).fold(n, |n, _| next_secret(n))).sum()
+    }
+
+    // ----------------------- Part 2 -----------------------
+
+    /// Each price change is in -9..=9, so a run of four packs into a base-19 index. Total up the price each buyer
+    /// first sells at for every run, and take the best.
+    #[solver(part2, sequences)]
+    pub fn solve_part2(input: Vec<u64>) -> u64 {
+        const RUNS: usize = 19 * 19 * 19 * 19;
+        let mut totals = vec![0u64; RUNS];
+        let mut last_buyer = vec![usize::MAX; RUNS];
+        for (buyer, &n) in input.iter().enumerate() {
+            let mut secret = n;
+            let mut run = 0;
+            for i in 0..2000 {
+                let next = next_secret(secret);
+                let (price, next_price) = (secret % 10, next % 10);
+                run = (run * 19 + (9 + next_price - price) as usize) % RUNS;
+                secret = next;
+                if i >= 3 && last_buyer[run] != buyer {
+                    last_buyer[run] = buyer;
+                    totals[run] += next_price;
+                }
+            }
+        }
+        totals.into_iter().max().unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_zen_runner_macros::aoc_case;
+
+    #[aoc_case(37327623)]
+    const INPUT1: &str = "1
+10
+100
+2024";
+
+    #[aoc_case(37990510, 23)]
+    const INPUT2: &str = "1
+2
+3
+2024";
+
+    #[test]
+    pub fn test_next_secret() {
+        let secrets: Vec<u64> = (0..3)
+            .scan(123, |n, _| {
+                *n = next_secret(*n);
+                Some(*n)
+            })
+            .collect();
+        assert_eq!(vec![15887950, 16495136, 527345], secrets);
+    }
+}
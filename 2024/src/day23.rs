@@ -0,0 +1,145 @@
+use std::collections::{BTreeSet, HashMap};
+
+use aoc_zen_runner_macros::{aoc, generator, solver};
+
+pub struct Lan {
+    pub names: Vec<String>,
+    /// Neighbours of each computer, by index into `names`.
+    pub links: Vec<BTreeSet<usize>>,
+}
+
+impl Lan {
+    /// Bron–Kerbosch with pivoting, keeping the biggest clique found.
+    fn largest_clique(
+        &self,
+        clique: &mut Vec<usize>,
+        mut candidates: BTreeSet<usize>,
+        mut excluded: BTreeSet<usize>,
+        best: &mut Vec<usize>,
+    ) {
+        if candidates.is_empty() {
+            if excluded.is_empty() && clique.len() > best.len() {
+                *best = clique.clone();
+            }
+            return;
+        }
+        let pivot = *candidates
+            .union(&excluded)
+            .max_by_key(|&&v| self.links[v].len())
+            .unwrap();
+        let choices: Vec<usize> = candidates.difference(&self.links[pivot]).copied().collect();
+        for v in choices {
+            clique.push(v);
+            self.largest_clique(
+                clique,
+                candidates.intersection(&self.links[v]).copied().collect(),
+                excluded.intersection(&self.links[v]).copied().collect(),
+                best,
+            );
+            clique.pop();
+            candidates.remove(&v);
+            excluded.insert(v);
+        }
+    }
+}
+
+#[aoc(2024, day23)]
+pub mod solutions {
+    use super::*;
+
+    #[generator(gen)]
+    pub fn input_generator(input: &str) -> Lan {
+        let mut index: HashMap<&str, usize> = HashMap::new();
+        let mut lan = Lan {
+            names: Vec::new(),
+            links: Vec::new(),
+        };
+        for ln in input.lines().map(str::trim).filter(|ln| !ln.is_empty()) {
+            let (a, b) = ln.split_once('-').expect("Bad link");
+            let [a, b] = [a, b].map(|name| {
+                *index.entry(name).or_insert_with(|| {
+                    lan.names.push(name.to_string());
+                    lan.links.push(BTreeSet::new());
+                    lan.names.len() - 1
+                })
+            });
+            lan.links[a].insert(b);
+            lan.links[b].insert(a);
+        }
+        lan
+    }
+
+    // ----------------------- Part 1 -----------------------
+
+    /// Each triangle is counted once, from its lowest-numbered computer.
+    #[solver(part1, triangles)]
+    pub fn solve_part1(input: Lan) -> usize {
+        let chief = |v: usize| input.names[v].starts_with('t');
+        let mut rv = 0;
+        for (a, links) in input.links.iter().enumerate() {
+            for &b in links.range(a + 1..) {
+                for &c in input.links[b].range(b + 1..) {
+                    if links.contains(&c) && (chief(a) || chief(b) || chief(c)) {
+                        rv += 1;
+                    }
+                }
+            }
+        }
+        rv
+    }
+
+    // ----------------------- Part 2 -----------------------
+
+    #[solver(part2, bron_kerbosch)]
+    pub fn solve_part2(input: Lan) -> String {
+        let mut best = Vec::new();
+        input.largest_clique(
+            &mut Vec::new(),
+            (0..input.names.len()).collect(),
+            BTreeSet::new(),
+            &mut best,
+        );
+        let mut names: Vec<&str> = best.iter().map(|&v| input.names[v].as_str()).collect();
+        names.sort();
+        names.join(",")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_zen_runner_macros::aoc_case;
+
+    #[aoc_case(7, "co,de,ka,ta")]
+    const INPUT1: &str = "kh-tc
+qp-kh
+de-cg
+ka-co
+yn-aq
+qp-ub
+cg-tb
+vc-aq
+tb-ka
+wh-tc
+yn-cg
+kh-ub
+ta-co
+de-co
+tc-td
+tb-wq
+wh-td
+ta-ka
+td-qp
+aq-cg
+wq-ub
+ub-vc
+de-ta
+wq-aq
+wq-vc
+wh-yn
+ka-de
+kh-ta
+co-tc
+wh-qp
+tb-vc
+td-yn";
+}
@@ -0,0 +1,219 @@
+use std::collections::{BTreeSet, HashMap};
+
+use aoc_zen_runner_macros::{aoc, generator, solver};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Op {
+    And,
+    Or,
+    Xor,
+}
+
+#[derive(Debug, Clone)]
+pub struct Gate {
+    pub inputs: [String; 2],
+    pub op: Op,
+    pub output: String,
+}
+
+pub struct Device {
+    pub initial: HashMap<String, bool>,
+    pub gates: Vec<Gate>,
+}
+
+impl Device {
+    fn value(&self, wire: &str, by_output: &HashMap<&str, &Gate>, memo: &mut HashMap<String, bool>) -> bool {
+        if let Some(&v) = self.initial.get(wire).or_else(|| memo.get(wire)) {
+            return v;
+        }
+        let gate = by_output[wire];
+        let a = self.value(&gate.inputs[0], by_output, memo);
+        let b = self.value(&gate.inputs[1], by_output, memo);
+        let v = match gate.op {
+            Op::And => a & b,
+            Op::Or => a | b,
+            Op::Xor => a ^ b,
+        };
+        memo.insert(wire.to_string(), v);
+        v
+    }
+}
+
+fn is_input(wire: &str) -> bool {
+    wire.starts_with('x') || wire.starts_with('y')
+}
+
+#[aoc(2024, day24)]
+pub mod solutions {
+    use super::*;
+
+    #[generator(gen)]
+    pub fn input_generator(input: &str) -> Device {
+        let mut initial = HashMap::new();
+        let mut gates = Vec::new();
+        for ln in input.lines().map(str::trim).filter(|ln| !ln.is_empty()) {
+            if let Some((wire, value)) = ln.split_once(": ") {
+                initial.insert(wire.to_string(), value == "1");
+                continue;
+            }
+            let [a, op, b, _, output] = ln.split_ascii_whitespace().collect::<Vec<_>>()[..] else {
+                panic!("Bad gate: {}", ln);
+            };
+            let op = match op {
+                "AND" => Op::And,
+                "OR" => Op::Or,
+                "XOR" => Op::Xor,
+                _ => panic!("Unknown gate: {}", op),
+            };
+            gates.push(Gate {
+                inputs: [a.to_string(), b.to_string()],
+                op,
+                output: output.to_string(),
+            });
+        }
+        Device { initial, gates }
+    }
+
+    // ----------------------- Part 1 -----------------------
+
+    #[solver(part1, simulate)]
+    pub fn solve_part1(input: Device) -> u64 {
+        let by_output: HashMap<&str, &Gate> = input.gates.iter().map(|g| (g.output.as_str(), g)).collect();
+        let mut memo = HashMap::new();
+        let mut z_wires: Vec<&str> = by_output.keys().copied().filter(|w| w.starts_with('z')).collect();
+        z_wires.sort();
+        z_wires
+            .iter()
+            .rev()
+            .fold(0, |acc, w| acc << 1 | input.value(w, &by_output, &mut memo) as u64)
+    }
+
+    // ----------------------- Part 2 -----------------------
+
+    /// The device is meant to be a ripple-carry adder, where every gate has a fixed role. Rather than search for
+    /// the swaps, list every wire whose gate breaks the pattern:
+    /// - every `z` but the final carry comes from an XOR;
+    /// - an XOR that doesn't take `x` and `y` is a sum, so outputs a `z`;
+    /// - an XOR of `x` and `y` (past bit 0) feeds another XOR;
+    /// - an AND (past bit 0) feeds an OR.
+    #[solver(part2, adder_rules)]
+    pub fn solve_part2(input: Device) -> String {
+        let last_z = input
+            .gates
+            .iter()
+            .map(|g| g.output.as_str())
+            .filter(|w| w.starts_with('z'))
+            .max()
+            .unwrap();
+        let feeds = |wire: &str, op: Op| {
+            input
+                .gates
+                .iter()
+                .any(|g| g.op == op && g.inputs.iter().any(|i| i == wire))
+        };
+        let first_bit = |g: &Gate| g.inputs.iter().any(|i| i.ends_with("00"));
+
+        let mut wrong = BTreeSet::new();
+        for g in &input.gates {
+            let out = g.output.as_str();
+            let bad = match g.op {
+                _ if out.starts_with('z') && out != last_z && g.op != Op::Xor => true,
+                Op::Xor if !is_input(&g.inputs[0]) => !out.starts_with('z'),
+                Op::Xor => !first_bit(g) && !feeds(out, Op::Xor),
+                Op::And => !first_bit(g) && !feeds(out, Op::Or),
+                Op::Or => false,
+            };
+            if bad {
+                wrong.insert(out);
+            }
+        }
+        wrong.into_iter().collect::<Vec<_>>().join(",")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::solutions::*;
+    use super::*;
+    use aoc_zen_runner_macros::aoc_case;
+
+    #[aoc_case(4)]
+    const INPUT1: &str = "x00: 1
+x01: 1
+x02: 1
+y00: 0
+y01: 1
+y02: 0
+
+x00 AND y00 -> z00
+x01 XOR y01 -> z01
+x02 OR y02 -> z02";
+
+    #[aoc_case(2024)]
+    const INPUT2: &str = "x00: 1
+x01: 0
+x02: 1
+x03: 1
+x04: 0
+y00: 1
+y01: 1
+y02: 1
+y03: 1
+y04: 1
+
+ntg XOR fgs -> mjb
+y02 OR x01 -> tnw
+kwq OR kpj -> z05
+x00 OR x03 -> fst
+tgd XOR rvg -> z01
+vdt OR tnw -> bfw
+bfw AND frj -> z10
+ffh OR nrd -> bqk
+y00 AND y03 -> djm
+y03 OR y00 -> psh
+bqk OR frj -> z08
+tnw OR fst -> frj
+gnj AND tgd -> z11
+bfw XOR mjb -> z00
+x03 OR x00 -> vdt
+gnj AND wpb -> z02
+x04 AND y00 -> kjc
+djm OR pbm -> qhw
+nrd AND vdt -> hwm
+kjc AND fst -> rvg
+y04 OR y02 -> fgs
+y01 AND x02 -> pbm
+ntg OR kjc -> kwq
+psh XOR fgs -> tgd
+qhw XOR tgd -> z09
+pbm OR djm -> kpj
+x03 XOR y03 -> ffh
+x00 XOR y04 -> ntg
+bfw OR bqk -> z06
+nrd XOR fgs -> wpb
+frj XOR qhw -> z04
+bqk OR frj -> z07
+y03 OR x01 -> nrd
+hwm AND bqk -> z03
+tgd XOR rvg -> z12
+tnw OR pbm -> gnj";
+
+    /// A two-bit adder with the outputs of the second bit's carry AND and sum XOR swapped.
+    const SWAPPED: &str = "x00: 1
+x01: 1
+y00: 1
+y01: 0
+
+x00 XOR y00 -> z00
+x00 AND y00 -> c00
+x01 XOR y01 -> s01
+x01 AND y01 -> z01
+s01 XOR c00 -> a01
+s01 AND c00 -> b01
+a01 OR b01 -> z02";
+
+    #[test]
+    pub fn test_swapped_wires() {
+        assert_eq!("a01,z01", solve_part2(input_generator(SWAPPED)));
+    }
+}
@@ -0,0 +1,98 @@
+use aoc_zen_runner_macros::{aoc, generator, solver};
+
+/// A lock or key schematic, as the height of each of its five columns.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Schematic {
+    pub is_lock: bool,
+    pub heights: [u8; 5],
+}
+
+#[aoc(2024, day25)]
+pub mod solutions {
+    use super::*;
+
+    #[generator(gen)]
+    pub fn input_generator(input: &str) -> Vec<Schematic> {
+        input
+            .split("\n\n")
+            .filter(|block| !block.trim().is_empty())
+            .map(|block| {
+                let rows: Vec<&[u8]> = block.lines().map(|ln| ln.trim().as_bytes()).collect();
+                let mut heights = [0; 5];
+                for row in &rows[1..rows.len() - 1] {
+                    for (h, &b) in heights.iter_mut().zip(row.iter()) {
+                        *h += (b == b'#') as u8;
+                    }
+                }
+                Schematic {
+                    is_lock: rows[0][0] == b'#',
+                    heights,
+                }
+            })
+            .collect()
+    }
+
+    // ----------------------- Part 1 -----------------------
+
+    #[solver(part1, pairs)]
+    pub fn solve_part1(input: Vec<Schematic>) -> usize {
+        let (locks, keys): (Vec<Schematic>, Vec<Schematic>) = input.into_iter().partition(|s| s.is_lock);
+        locks
+            .iter()
+            .map(|lock| {
+                keys.iter()
+                    .filter(|key| lock.heights.iter().zip(&key.heights).all(|(l, k)| l + k <= 5))
+                    .count()
+            })
+            .sum()
+    }
+
+    // There is no part 2 puzzle on the last day.
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_zen_runner_macros::aoc_case;
+
+    #[aoc_case(3)]
+    const INPUT1: &str = "#####
+.####
+.####
+.####
+.#.#.
+.#...
+.....
+
+#####
+##.##
+.#.##
+...##
+...#.
+...#.
+.....
+
+.....
+#....
+#....
+#...#
+#.#.#
+#.###
+#####
+
+.....
+.....
+#.#..
+###..
+###.#
+###.#
+#####
+
+.....
+.....
+.....
+#....
+#.#..
+#.#.#
+#####";
+}
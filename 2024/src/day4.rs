@@ -0,0 +1,69 @@
+use aoc_common::grid::{Grid, Pos};
+use aoc_zen_runner_macros::{aoc, generator, solver};
+
+#[aoc(2024, day4)]
+pub mod solutions {
+    use super::*;
+
+    const DIRECTIONS: [(isize, isize); 8] = [(-1, -1), (-1, 0), (-1, 1), (0, -1), (0, 1), (1, -1), (1, 0), (1, 1)];
+
+    /// Whether `word` is spelled out from `start`, one `(d_row, d_col)` step per letter.
+    fn spells(grid: &Grid<u8>, start: Pos, (d_row, d_col): (isize, isize), word: &[u8]) -> bool {
+        word.iter().enumerate().all(|(i, &b)| {
+            start
+                .offset(d_row * i as isize, d_col * i as isize)
+                .and_then(|p| grid.get(p))
+                .is_some_and(|&c| c == b)
+        })
+    }
+
+    #[generator(gen)]
+    pub fn input_generator(input: &str) -> Grid<u8> {
+        Grid::parse_bytes(input)
+    }
+
+    // ----------------------- Part 1 -----------------------
+
+    #[solver(part1, scan)]
+    pub fn solve_part1(input: Grid<u8>) -> usize {
+        input
+            .positions()
+            .filter(|&p| input[p] == b'X')
+            .map(|p| DIRECTIONS.iter().filter(|&&d| spells(&input, p, d, b"XMAS")).count())
+            .sum()
+    }
+
+    // ----------------------- Part 2 -----------------------
+
+    /// Every X-MAS has an `A` at its centre with `M` and `S` at opposite ends of both diagonals.
+    #[solver(part2, scan)]
+    pub fn solve_part2(input: Grid<u8>) -> usize {
+        let is_mas = |a: u8, b: u8| (a == b'M' && b == b'S') || (a == b'S' && b == b'M');
+        (1..input.rows().saturating_sub(1))
+            .flat_map(|r| (1..input.cols() - 1).map(move |c| Pos::new(r, c)))
+            .filter(|&p| input[p] == b'A')
+            .filter(|&p| {
+                let (r, c) = (p.row, p.col);
+                is_mas(input[(r - 1, c - 1)], input[(r + 1, c + 1)])
+                    && is_mas(input[(r - 1, c + 1)], input[(r + 1, c - 1)])
+            })
+            .count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_zen_runner_macros::aoc_case;
+
+    #[aoc_case(18, 9)]
+    const INPUT1: &str = "MMMSXXMASM
+MSAMXMSMSA
+AMXSXMAAMM
+MSAMASMSMX
+XMASAMXAMM
+XXAMMXXAMA
+SMSMSASXSS
+SAXAMASAAA
+MAMMMXMMMM
+MXMXAXMASX";
+}
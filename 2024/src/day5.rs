@@ -0,0 +1,109 @@
+use std::cmp::Ordering;
+use std::collections::HashSet;
+
+use aoc_zen_runner_macros::{aoc, generator, solver};
+
+pub struct Manual {
+    /// Every `(before, after)` page ordering rule.
+    pub rules: HashSet<(u32, u32)>,
+    pub updates: Vec<Vec<u32>>,
+}
+
+impl Manual {
+    fn compare(&self, a: u32, b: u32) -> Ordering {
+        if self.rules.contains(&(a, b)) {
+            Ordering::Less
+        } else if self.rules.contains(&(b, a)) {
+            Ordering::Greater
+        } else {
+            Ordering::Equal
+        }
+    }
+
+    fn in_order(&self, update: &[u32]) -> bool {
+        update.is_sorted_by(|&a, &b| self.compare(a, b) != Ordering::Greater)
+    }
+}
+
+#[aoc(2024, day5)]
+pub mod solutions {
+    use super::*;
+
+    #[generator(gen)]
+    pub fn input_generator(input: &str) -> Manual {
+        let mut rules = HashSet::new();
+        let mut updates = Vec::new();
+        for ln in input.lines().map(str::trim).filter(|ln| !ln.is_empty()) {
+            if let Some((a, b)) = ln.split_once('|') {
+                rules.insert((a.parse().unwrap(), b.parse().unwrap()));
+            } else {
+                updates.push(ln.split(',').map(|n| n.parse().unwrap()).collect());
+            }
+        }
+        Manual { rules, updates }
+    }
+
+    // ----------------------- Part 1 -----------------------
+
+    #[solver(part1, rules)]
+    pub fn solve_part1(input: Manual) -> u32 {
+        input
+            .updates
+            .iter()
+            .filter(|u| input.in_order(u))
+            .map(|u| u[u.len() / 2])
+            .sum()
+    }
+
+    // ----------------------- Part 2 -----------------------
+
+    /// The rules cover every pair of pages within an update, so they sort it directly.
+    #[solver(part2, rules)]
+    pub fn solve_part2(input: Manual) -> u32 {
+        input
+            .updates
+            .iter()
+            .filter(|u| !input.in_order(u))
+            .map(|u| {
+                let mut u = u.clone();
+                u.sort_by(|&a, &b| input.compare(a, b));
+                u[u.len() / 2]
+            })
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_zen_runner_macros::aoc_case;
+
+    #[aoc_case(143, 123)]
+    const INPUT1: &str = "47|53
+97|13
+97|61
+97|47
+75|29
+61|13
+75|53
+29|13
+97|29
+53|29
+61|53
+97|53
+61|29
+47|13
+75|47
+97|75
+47|61
+75|61
+47|29
+75|13
+53|13
+
+75,47,61,53,29
+97,61,53,29,13
+75,29,13
+75,97,47,61,53
+61,13,29
+97,13,75,29,47";
+}
@@ -0,0 +1,77 @@
+use aoc_common::grid::{Dir, Grid, Pos};
+use aoc_zen_runner_macros::{aoc, generator, solver};
+
+pub struct Lab {
+    /// `true` for each obstruction.
+    pub grid: Grid<bool>,
+    pub start: Pos,
+}
+
+/// Every position the guard visits before walking off the map, or `None` if they end up walking in a loop.
+/// `extra` is one more obstruction to place first.
+fn patrol(lab: &Lab, extra: Option<Pos>) -> Option<Vec<Pos>> {
+    let blocked = |p: Pos| lab.grid[p] || Some(p) == extra;
+    // Directions the guard has already left each position in, one bit each.
+    let mut seen: Grid<u8> = lab.grid.map(|_| 0);
+    let (mut pos, mut dir) = (lab.start, Dir::North);
+    while seen[pos] & 1 << dir as u8 == 0 {
+        seen[pos] |= 1 << dir as u8;
+        match lab.grid.step(pos, dir) {
+            None => return Some(seen.positions().filter(|&p| seen[p] != 0).collect()),
+            Some(next) if blocked(next) => dir = dir.rotate_cw(),
+            Some(next) => pos = next,
+        }
+    }
+    None
+}
+
+#[aoc(2024, day6)]
+pub mod solutions {
+    use super::*;
+
+    #[generator(gen)]
+    pub fn input_generator(input: &str) -> Lab {
+        let grid = Grid::parse_bytes(input);
+        let start = grid.positions().find(|&p| grid[p] == b'^').expect("No guard");
+        Lab {
+            grid: grid.map(|&b| b == b'#'),
+            start,
+        }
+    }
+
+    // ----------------------- Part 1 -----------------------
+
+    #[solver(part1, walk)]
+    pub fn solve_part1(input: Lab) -> usize {
+        patrol(&input, None).expect("Guard walks in a loop").len()
+    }
+
+    // ----------------------- Part 2 -----------------------
+
+    /// A new obstruction only changes the route if it's somewhere on the original route.
+    #[solver(part2, walk)]
+    pub fn solve_part2(input: Lab) -> usize {
+        let route = patrol(&input, None).expect("Guard walks in a loop");
+        route
+            .into_iter()
+            .filter(|&p| p != input.start && patrol(&input, Some(p)).is_none())
+            .count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_zen_runner_macros::aoc_case;
+
+    #[aoc_case(41, 6)]
+    const INPUT1: &str = "....#.....
+.........#
+..........
+..#.......
+.......#..
+..........
+.#..^.....
+........#.
+#.........
+......#...";
+}
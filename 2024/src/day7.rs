@@ -0,0 +1,86 @@
+use aoc_zen_runner_macros::{aoc, generator, solver};
+
+pub struct Equation {
+    pub target: u64,
+    pub operands: Vec<u64>,
+}
+
+/// Whether some choice of operators between `operands`, evaluated left to right, makes `target`. Works backwards
+/// from the last operand, which prunes far more than working forwards.
+fn solvable(target: u64, operands: &[u64], concat: bool) -> bool {
+    let (&last, rest) = operands.split_last().unwrap();
+    if rest.is_empty() {
+        return target == last;
+    }
+    if target > last && solvable(target - last, rest, concat) {
+        return true;
+    }
+    if target.is_multiple_of(last) && solvable(target / last, rest, concat) {
+        return true;
+    }
+    if concat {
+        let shift = 10u64.pow(last.checked_ilog10().unwrap_or(0) + 1);
+        if target > last && target % shift == last && solvable(target / shift, rest, concat) {
+            return true;
+        }
+    }
+    false
+}
+
+#[aoc(2024, day7)]
+pub mod solutions {
+    use super::*;
+
+    #[generator(gen)]
+    pub fn input_generator(input: &str) -> Vec<Equation> {
+        input
+            .lines()
+            .filter(|ln| !ln.trim().is_empty())
+            .map(|ln| {
+                let (target, operands) = ln.split_once(':').expect("Bad equation");
+                Equation {
+                    target: target.parse().unwrap(),
+                    operands: operands.split_ascii_whitespace().map(|n| n.parse().unwrap()).collect(),
+                }
+            })
+            .collect()
+    }
+
+    // ----------------------- Part 1 -----------------------
+
+    #[solver(part1, backwards)]
+    pub fn solve_part1(input: Vec<Equation>) -> u64 {
+        input
+            .iter()
+            .filter(|e| solvable(e.target, &e.operands, false))
+            .map(|e| e.target)
+            .sum()
+    }
+
+    // ----------------------- Part 2 -----------------------
+
+    #[solver(part2, backwards)]
+    pub fn solve_part2(input: Vec<Equation>) -> u64 {
+        input
+            .iter()
+            .filter(|e| solvable(e.target, &e.operands, true))
+            .map(|e| e.target)
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_zen_runner_macros::aoc_case;
+
+    #[aoc_case(3749, 11387)]
+    const INPUT1: &str = "190: 10 19
+3267: 81 40 27
+83: 17 5
+156: 15 6
+7290: 6 8 6 15
+161011: 16 10 13
+192: 17 8 14
+21037: 9 7 18 13
+292: 11 6 16 20";
+}
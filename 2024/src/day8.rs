@@ -0,0 +1,90 @@
+use std::collections::{HashMap, HashSet};
+
+use aoc_common::grid::Pos;
+use aoc_zen_runner_macros::{aoc, generator, solver};
+
+pub struct City {
+    pub rows: usize,
+    pub cols: usize,
+    /// Antenna positions by frequency.
+    pub antennas: HashMap<u8, Vec<Pos>>,
+}
+
+impl City {
+    /// Antinodes from every pair of same-frequency antennas. `harmonics` picks which multiples of the pair's
+    /// separation, counted out from each antenna, to try.
+    fn antinodes(&self, harmonics: impl Iterator<Item = isize> + Clone) -> HashSet<Pos> {
+        let mut rv = HashSet::new();
+        for positions in self.antennas.values() {
+            for (i, &a) in positions.iter().enumerate() {
+                for &b in &positions[i + 1..] {
+                    let d_row = b.row as isize - a.row as isize;
+                    let d_col = b.col as isize - a.col as isize;
+                    for (from, sign) in [(b, 1), (a, -1)] {
+                        for k in harmonics.clone() {
+                            match from.offset(sign * k * d_row, sign * k * d_col) {
+                                Some(p) if p.row < self.rows && p.col < self.cols => rv.insert(p),
+                                _ => break,
+                            };
+                        }
+                    }
+                }
+            }
+        }
+        rv
+    }
+}
+
+#[aoc(2024, day8)]
+pub mod solutions {
+    use super::*;
+
+    #[generator(gen)]
+    pub fn input_generator(input: &str) -> City {
+        let lines: Vec<&str> = input.lines().filter(|ln| !ln.trim().is_empty()).collect();
+        let mut antennas: HashMap<u8, Vec<Pos>> = HashMap::new();
+        for (row, ln) in lines.iter().enumerate() {
+            for (col, b) in ln.bytes().enumerate().filter(|&(_, b)| b != b'.') {
+                antennas.entry(b).or_default().push(Pos::new(row, col));
+            }
+        }
+        City {
+            rows: lines.len(),
+            cols: lines.first().map_or(0, |ln| ln.len()),
+            antennas,
+        }
+    }
+
+    // ----------------------- Part 1 -----------------------
+
+    #[solver(part1, pairs)]
+    pub fn solve_part1(input: City) -> usize {
+        input.antinodes(1..=1).len()
+    }
+
+    // ----------------------- Part 2 -----------------------
+
+    #[solver(part2, pairs)]
+    pub fn solve_part2(input: City) -> usize {
+        input.antinodes(0..).len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_zen_runner_macros::aoc_case;
+
+    #[aoc_case(14, 34)]
+    const INPUT1: &str = "............
+........0...
+.....0......
+.......0....
+....0.......
+......A.....
+............
+............
+........A...
+.........A..
+............
+............";
+}
@@ -0,0 +1,93 @@
+use aoc_zen_runner_macros::{aoc, generator, solver};
+
+/// A run of blocks on the disk, holding file `id` or free space.
+#[derive(Debug, Clone, Copy)]
+pub struct Span {
+    pub start: usize,
+    pub len: usize,
+    pub id: Option<usize>,
+}
+
+fn checksum(start: usize, len: usize, id: usize) -> usize {
+    // Sum of positions start..start + len, times the file id.
+    id * (len * (2 * start + len - 1) / 2)
+}
+
+#[aoc(2024, day9)]
+pub mod solutions {
+    use super::*;
+
+    #[generator(gen)]
+    pub fn input_generator(input: &str) -> Vec<Span> {
+        let mut start = 0;
+        input
+            .trim()
+            .bytes()
+            .enumerate()
+            .map(|(i, b)| {
+                let len = (b - b'0') as usize;
+                let span = Span {
+                    start,
+                    len,
+                    id: (i % 2 == 0).then_some(i / 2),
+                };
+                start += len;
+                span
+            })
+            .collect()
+    }
+
+    // ----------------------- Part 1 -----------------------
+
+    /// Fill gaps from the left with blocks taken one at a time from the right.
+    #[solver(part1, blocks)]
+    pub fn solve_part1(input: Vec<Span>) -> usize {
+        let mut blocks: Vec<Option<usize>> = input.iter().flat_map(|s| std::iter::repeat_n(s.id, s.len)).collect();
+        let (mut left, mut right) = (0, blocks.len());
+        while left < right {
+            if blocks[left].is_some() {
+                left += 1;
+            } else if blocks[right - 1].is_none() {
+                right -= 1;
+            } else {
+                blocks.swap(left, right - 1);
+            }
+        }
+        blocks
+            .iter()
+            .enumerate()
+            .filter_map(|(i, id)| id.map(|id| i * id))
+            .sum()
+    }
+
+    // ----------------------- Part 2 -----------------------
+
+    /// Move each whole file, highest id first, into the leftmost gap before it that fits.
+    #[solver(part2, files)]
+    pub fn solve_part2(input: Vec<Span>) -> usize {
+        let mut gaps: Vec<Span> = input.iter().filter(|s| s.id.is_none()).copied().collect();
+        let mut rv = 0;
+        for file in input.iter().rev().filter(|s| s.id.is_some()) {
+            let mut start = file.start;
+            if let Some(gap) = gaps
+                .iter_mut()
+                .take_while(|g| g.start < file.start)
+                .find(|g| g.len >= file.len)
+            {
+                start = gap.start;
+                gap.start += file.len;
+                gap.len -= file.len;
+            }
+            rv += checksum(start, file.len, file.id.unwrap());
+        }
+        rv
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_zen_runner_macros::aoc_case;
+
+    #[aoc_case(1928, 2858)]
+    const INPUT1: &str = "2333133121414131402";
+}
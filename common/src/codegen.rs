@@ -0,0 +1,414 @@
+//! Build-script helpers that scan a year crate's solution files and write the code the `aoc` runner and the
+//! benchmarks include.
+
+use std::fmt::Write as _;
+use std::fs;
+use std::path::Path;
+
+/// Find the `(year, day)` of the `#[aoc(year, dayN)]` attribute in a solution file.
+pub fn parse_aoc_attr(source: &str) -> Option<(u32, u32)> {
+    let args = source.split("#[aoc(").nth(1)?.split(')').next()?;
+    let (year, day) = args.split_once(',')?;
+    let day = day.trim().strip_prefix("day")?;
+    Some((year.trim().parse().ok()?, day.parse().ok()?))
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Role {
+    Generator,
+    Solver(u8),
+    Solution(u8),
+}
+
+/// One `#[generator]`, `#[solver]` or `#[solution]` function, with whitespace stripped from its types.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Annotated {
+    pub role: Role,
+    pub slug: String,
+    pub name: String,
+    pub input: String,
+    pub output: String,
+}
+
+/// Everything the build scripts need from one solution file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SolutionFile {
+    pub stem: String,
+    pub year: u32,
+    pub day: u32,
+    pub module: String,
+    pub functions: Vec<Annotated>,
+    /// True if the file has a `gen` module with a `REAL_SIZE` for synthesising a real-sized input.
+    pub has_gen: bool,
+    /// Name of the struct declared with `params!` in the solutions module, if any.
+    pub params: Option<String>,
+}
+
+fn parse_role(attr: &str) -> Option<(Role, String)> {
+    let (kind, args) = attr.trim().strip_prefix("#[")?.split_once('(')?;
+    let args = args.split(')').next()?;
+    let mut args = args.split(',').map(str::trim);
+    let part = |p: &str| p.strip_prefix("part")?.parse::<u8>().ok();
+    match kind {
+        "generator" => Some((Role::Generator, args.next()?.to_string())),
+        "solver" => Some((Role::Solver(part(args.next()?)?), args.next()?.to_string())),
+        "solution" => Some((Role::Solution(part(args.next()?)?), args.next()?.to_string())),
+        _ => None,
+    }
+}
+
+fn parse_signature(sig: &str) -> Option<(String, String, String)> {
+    let sig: String = sig.split('{').next()?.chars().filter(|c| !c.is_whitespace()).collect();
+    let rest = sig.split("fn").nth(1)?;
+    let name_end = rest.find(['(', '<'])?;
+    let params_start = rest.find('(')?;
+    let (params, output) = rest[params_start + 1..].rsplit_once(")->")?;
+    let input = params.split_once(':')?.1.trim_end_matches(',');
+    Some((rest[..name_end].to_string(), input.to_string(), output.to_string()))
+}
+
+/// Scan one solution file. Returns `None` for files without an `#[aoc]` module.
+pub fn scan_source(stem: &str, source: &str) -> Option<SolutionFile> {
+    let (year, day) = parse_aoc_attr(source)?;
+    let module = source
+        .split("#[aoc(")
+        .nth(1)?
+        .split("mod ")
+        .nth(1)?
+        .split(|c: char| !c.is_alphanumeric() && c != '_')
+        .next()?
+        .to_string();
+
+    let mut functions = Vec::new();
+    let mut pending: Option<(Role, String)> = None;
+    let mut lines = source.lines().map(str::trim).filter(|l| !l.starts_with("//"));
+    while let Some(line) = lines.next() {
+        if let Some(role) = parse_role(line) {
+            pending = Some(role);
+            continue;
+        }
+        if !line.contains("fn ") {
+            continue;
+        }
+        let Some((role, slug)) = pending.take() else {
+            continue;
+        };
+        let mut sig = line.to_string();
+        while !sig.contains('{') {
+            match lines.next() {
+                Some(l) => sig.push_str(l),
+                None => break,
+            }
+        }
+        if let Some((name, input, output)) = parse_signature(&sig) {
+            functions.push(Annotated {
+                role,
+                slug,
+                name,
+                input,
+                output,
+            });
+        }
+    }
+
+    let has_gen = source.contains("pub mod gen") && source.contains("REAL_SIZE");
+    let params = source
+        .split("params! {")
+        .nth(1)
+        .and_then(|rest| rest.split("struct ").nth(1))
+        .and_then(|rest| rest.split(|c: char| !c.is_alphanumeric() && c != '_').next())
+        .map(str::to_string);
+    Some(SolutionFile {
+        stem: stem.to_string(),
+        year,
+        day,
+        module,
+        functions,
+        has_gen,
+        params,
+    })
+}
+
+impl SolutionFile {
+    /// `#[solution]` functions for a part.
+    pub fn solutions(&self, part: u8) -> impl Iterator<Item = &Annotated> {
+        self.functions.iter().filter(move |f| f.role == Role::Solution(part))
+    }
+
+    /// Every generator paired with every solver for a part that takes exactly its output type, as the
+    /// `#[aoc]` macro composes them.
+    pub fn pairs(&self, part: u8) -> Vec<(&Annotated, &Annotated)> {
+        let gens = self.functions.iter().filter(|f| f.role == Role::Generator);
+        gens.flat_map(|g| {
+            self.functions
+                .iter()
+                .filter(move |s| s.role == Role::Solver(part) && s.input == g.output)
+                .map(move |s| (g, s))
+        })
+        .collect()
+    }
+}
+
+/// Every `#[aoc]` solution file in `src_dir`, sorted by day.
+pub fn scan_dir(src_dir: &Path) -> Vec<SolutionFile> {
+    let mut found = Vec::new();
+    for entry in fs::read_dir(src_dir).expect("Could not read source directory") {
+        let path = entry.expect("Could not read directory entry").path();
+        let Some(stem) = path.file_stem().and_then(|s| s.to_str()) else {
+            continue;
+        };
+        if path.extension().is_none_or(|e| e != "rs") || stem == "lib" {
+            continue;
+        }
+        let source = fs::read_to_string(&path).expect("Could not read solution file");
+        if let Some(file) = scan_source(stem, &source) {
+            found.push(file);
+        }
+    }
+    found.sort_by_key(|f| (f.day, f.year));
+    found
+}
+
+/// Build-script entry point: write `days.rs` (the runner registry) and `benches.rs` (the criterion benchmarks)
+/// into `out_dir` for the solution files in `src_dir`.
+pub fn generate(src_dir: &Path, out_dir: &Path) {
+    let files = scan_dir(src_dir);
+    let crate_name = std::env::var("CARGO_PKG_NAME")
+        .expect("Not running in a build script")
+        .replace('-', "_");
+    fs::write(out_dir.join("days.rs"), registry_source(&files)).expect("Could not write registry");
+    fs::write(out_dir.join("benches.rs"), benches_source(&files, &crate_name)).expect("Could not write benches");
+}
+
+/// Each solution file is included into its own module, alongside a `register()` that exposes the solver lists
+/// the macro generates, with generator/solver pairs split so their generator can be timed on its own. The
+/// generated `days()` returns them sorted by day.
+pub fn registry_source(files: &[SolutionFile]) -> String {
+    let mut out = String::new();
+    for file in files {
+        let SolutionFile {
+            stem, year, day, module, ..
+        } = file;
+        let mut splits = String::new();
+        if let Some(params) = &file.params {
+            write!(
+                splits,
+                "\n            .with_params(<{module}::{params} as aoc_common::params::Params>::KEYS)"
+            )
+            .unwrap();
+        }
+        for part in [1, 2] {
+            for (g, s) in file.pairs(part) {
+                write!(
+                    splits,
+                    "\n            .with_split({part}, \"{} / {}\", |input| {{\n                aoc_common::registry::Timing::split(input, {module}::{}, {module}::{})\n            }})",
+                    g.slug, s.slug, g.name, s.name
+                )
+                .unwrap();
+            }
+        }
+        writeln!(
+            out,
+            r#"// Lints for these files are already reported by their per-day bin targets.
+#[allow(warnings, clippy::all)]
+pub mod {stem} {{
+    include!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/{stem}.rs"));
+
+    pub fn register() -> aoc_common::registry::Day {{
+        aoc_common::registry::Day::new({year}, {day}, AOC_RAW_INPUT)
+            .with_part1(&_gen_lists::P1_LABELS, &_gen_lists::P1_SOLUTIONS)
+            .with_part2(&_gen_lists::P2_LABELS, &_gen_lists::P2_SOLUTIONS){splits}
+    }}
+}}
+"#
+        )
+        .unwrap();
+    }
+    let calls: Vec<String> = files.iter().map(|f| format!("{}::register()", f.stem)).collect();
+    writeln!(
+        out,
+        "pub fn days() -> Vec<aoc_common::registry::Day> {{\n    vec![{}]\n}}",
+        calls.join(", ")
+    )
+    .unwrap();
+    out
+}
+
+/// A `bench_all(&mut Criterion)` with one group per day and part, named `<year>/day<NN>/part<N>`.
+///
+/// `#[solution]` functions are benchmarked by slug. Each generator/solver pair gets a `<gen>/<solver>/generate`
+/// and a `<gen>/<solver>/solve` benchmark, so parsing and solving are timed separately. Inputs come from the
+/// input store, then the compiled-in input, then the day's `gen::input` at `gen::REAL_SIZE`.
+pub fn benches_source(files: &[SolutionFile], crate_name: &str) -> String {
+    let mut out = String::new();
+    writeln!(out, "pub fn bench_all(c: &mut criterion::Criterion) {{").unwrap();
+    writeln!(out, "    let store = aoc_common::inputs::InputStore::from_env_or(concat!(env!(\"CARGO_MANIFEST_DIR\"), \"/../inputs\"));").unwrap();
+    for file in files {
+        let SolutionFile {
+            stem,
+            year,
+            day,
+            module,
+            ..
+        } = file;
+        let path = format!("{crate_name}::{stem}::{module}");
+        let fallback = if file.has_gen {
+            format!(
+                r#"Err(_) => {{
+                eprintln!("{year} day {day}: no input, benchmarking a synthetic one");
+                {crate_name}::{stem}::gen::input(&mut aoc_common::differential::seeded(0), {crate_name}::{stem}::gen::REAL_SIZE)
+            }}"#
+            )
+        } else {
+            format!("Err(_) => {{\n                eprintln!(\"{year} day {day}: no input, skipping\");\n                String::new()\n            }}")
+        };
+        writeln!(
+            out,
+            r#"
+    let input = match store.load_input({year}, {day}) {{
+        Ok(input) => input,
+        Err(_) if !{crate_name}::{stem}::register().input.is_empty() => {crate_name}::{stem}::register().input.to_string(),
+        {fallback}
+    }};
+    if !input.is_empty() {{"#
+        )
+        .unwrap();
+        for part in [1, 2] {
+            let pairs = file.pairs(part);
+            if file.solutions(part).next().is_none() && pairs.is_empty() {
+                continue;
+            }
+            writeln!(
+                out,
+                "        let mut group = c.benchmark_group(\"{year}/day{day:02}/part{part}\");"
+            )
+            .unwrap();
+            for s in file.solutions(part) {
+                writeln!(
+                    out,
+                    "        group.bench_function(\"{}\", |b| b.iter(|| {path}::{}(std::hint::black_box(&input))));",
+                    s.slug, s.name
+                )
+                .unwrap();
+            }
+            for (g, s) in pairs {
+                let id = format!("{}/{}", g.slug, s.slug);
+                writeln!(
+                    out,
+                    "        group.bench_function(\"{id}/generate\", |b| b.iter(|| {path}::{}(std::hint::black_box(&input))));",
+                    g.name
+                )
+                .unwrap();
+                writeln!(
+                    out,
+                    "        group.bench_function(\"{id}/solve\", |b| b.iter_batched(|| {path}::{}(&input), {path}::{}, criterion::BatchSize::SmallInput));",
+                    g.name, s.name
+                )
+                .unwrap();
+            }
+            writeln!(out, "        group.finish();").unwrap();
+        }
+        writeln!(out, "    }}").unwrap();
+    }
+    writeln!(out, "}}").unwrap();
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    const SOURCE: &str = r#"
+#[aoc(2022, day06)]
+pub mod solutions {
+    #[generator(gen)]
+    pub fn input_generator(input: &str) -> Vec<(u32, u32)> {
+        todo!()
+    }
+
+    #[generator(gen_p2)]
+    pub fn input_generator_p2(input: &str) -> GenData {
+        todo!()
+    }
+
+    // #[solver(part1, old)]
+    // pub fn old(input: Vec<i32>) -> u32 {
+
+    #[solver(part1, draft_solvr)]
+    pub fn solve_part1(input: Vec<(u32,u32)>) -> u32 {
+        todo!()
+    }
+
+    #[solver(part2, draft_solvr)]
+    pub fn solve_part2(
+        input: GenData,
+    ) -> u32 {
+        todo!()
+    }
+
+    #[solution(part2, bitbang)]
+    pub fn part2_bitbang<'a>(input: &'a str) -> usize {
+        todo!()
+    }
+}
+"#;
+
+    #[test_case("#[aoc(2023, day5)]\npub mod solutions {}", Some((2023, 5)); "plain")]
+    #[test_case("use x;\n#[aoc(2022,  day17 )]", Some((2022, 17)); "spacing")]
+    #[test_case("pub mod solutions {}", None; "missing")]
+    pub fn test_parse_aoc_attr(source: &str, expected: Option<(u32, u32)>) {
+        assert_eq!(expected, parse_aoc_attr(source));
+    }
+
+    #[test]
+    pub fn test_scan_source() {
+        let file = scan_source("day06", SOURCE).unwrap();
+        assert_eq!(
+            ("solutions", 2022, 6, false),
+            (file.module.as_str(), file.year, file.day, file.has_gen)
+        );
+
+        let names: Vec<_> = file
+            .functions
+            .iter()
+            .map(|f| (f.name.as_str(), f.input.as_str(), f.output.as_str()))
+            .collect();
+        assert_eq!(
+            vec![
+                ("input_generator", "&str", "Vec<(u32,u32)>"),
+                ("input_generator_p2", "&str", "GenData"),
+                ("solve_part1", "Vec<(u32,u32)>", "u32"),
+                ("solve_part2", "GenData", "u32"),
+                ("part2_bitbang", "&'astr", "usize"),
+            ],
+            names
+        );
+    }
+
+    #[test]
+    pub fn test_scan_params() {
+        assert_eq!(None, scan_source("day06", SOURCE).unwrap().params);
+        let source = "#[aoc(2022, day15)]\npub mod solutions {\n    aoc_common::params! {\n        pub struct Params {\n            target_y: i64 = 2_000_000,\n        }\n    }\n}";
+        let file = scan_source("day15", source).unwrap();
+        assert_eq!(Some("Params"), file.params.as_deref());
+        assert!(registry_source(&[file]).contains(".with_params(<solutions::Params as aoc_common::params::Params>::KEYS)"));
+    }
+
+    #[test]
+    pub fn test_pairs() {
+        let file = scan_source("day06", SOURCE).unwrap();
+        let slugs = |part| {
+            file.pairs(part)
+                .iter()
+                .map(|(g, s)| format!("{} / {}", g.slug, s.slug))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(vec!["gen / draft_solvr"], slugs(1));
+        assert_eq!(vec!["gen_p2 / draft_solvr"], slugs(2));
+        assert_eq!(
+            vec!["bitbang"],
+            file.solutions(2).map(|s| s.slug.as_str()).collect::<Vec<_>>()
+        );
+    }
+}
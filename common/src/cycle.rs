@@ -0,0 +1,117 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// A repeating stretch of a deterministic process: the state after step `start + len` is the state after `start`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cycle {
+    pub start: usize,
+    pub len: usize,
+}
+
+impl Cycle {
+    /// The step before the cycle repeats for the first time whose state matches step `n`.
+    pub fn equivalent(&self, n: usize) -> usize {
+        if n < self.start {
+            n
+        } else {
+            self.start + (n - self.start) % self.len
+        }
+    }
+
+    /// Extend a per-step total that grows by the same amount every time round the cycle, such as a tower's height.
+    /// `values[i]` is the total after step `i`, and must be known up to `start + len`.
+    pub fn extrapolate(&self, values: &[i64], n: usize) -> i64 {
+        if n < values.len() {
+            return values[n];
+        }
+        let per_cycle = values[self.start + self.len] - values[self.start];
+        let cycles = ((n - self.start) / self.len) as i64;
+        values[self.equivalent(n)] + cycles * per_cycle
+    }
+}
+
+/// Remembers a key for every step seen so far, and reports the cycle once a key comes round again.
+///
+/// The key must capture everything that determines the following steps.
+#[derive(Debug, Clone)]
+pub struct CycleDetector<K> {
+    seen: HashMap<K, usize>,
+}
+
+impl<K: Hash + Eq> CycleDetector<K> {
+    pub fn new() -> CycleDetector<K> {
+        CycleDetector { seen: HashMap::new() }
+    }
+
+    /// Record the key of the state after `step`. Returns the cycle if this state was seen at an earlier step.
+    pub fn observe(&mut self, step: usize, key: K) -> Option<Cycle> {
+        self.seen.insert(key, step).map(|start| Cycle {
+            start,
+            len: step - start,
+        })
+    }
+}
+
+impl<K: Hash + Eq> Default for CycleDetector<K> {
+    fn default() -> Self {
+        CycleDetector::new()
+    }
+}
+
+/// Apply `step` to `state` `n` times, skipping ahead once a state repeats. `step` must be deterministic.
+pub fn nth_state<S: Clone + Hash + Eq>(mut state: S, n: usize, mut step: impl FnMut(&S) -> S) -> S {
+    let mut detector = CycleDetector::new();
+    let mut history = vec![state.clone()];
+    detector.observe(0, state.clone());
+    for i in 1..=n {
+        state = step(&state);
+        if let Some(cycle) = detector.observe(i, state.clone()) {
+            return history.swap_remove(cycle.equivalent(n));
+        }
+        history.push(state.clone());
+    }
+    state
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case(3, 3; "before cycle")]
+    #[test_case(7, 3; "one lap")]
+    #[test_case(8, 4; "into second lap")]
+    #[test_case(1_000_000_000, 3 + (1_000_000_000 - 3) % 4; "far ahead")]
+    pub fn test_equivalent(n: usize, expected: usize) {
+        assert_eq!(expected, Cycle { start: 3, len: 4 }.equivalent(n));
+    }
+
+    #[test]
+    pub fn test_detect_and_extrapolate() {
+        // 0, 1, 2, then 3, 4, 5 repeating, with the total growing by 10 every lap.
+        let state = |i: usize| if i < 3 { i } else { 3 + (i - 3) % 3 };
+        let mut detector = CycleDetector::new();
+        let mut values = Vec::new();
+        let cycle = (0..)
+            .find_map(|i| {
+                values.push(i as i64 / 3 * 10 + state(i) as i64);
+                detector.observe(i, state(i))
+            })
+            .unwrap();
+        assert_eq!(Cycle { start: 3, len: 3 }, cycle);
+        for n in [4, 10, 100, 1000] {
+            assert_eq!(n as i64 / 3 * 10 + state(n) as i64, cycle.extrapolate(&values, n));
+        }
+    }
+
+    #[test]
+    pub fn test_nth_state() {
+        let step = |s: &u64| (s * s + 1) % 1009;
+        let mut expected = 7;
+        for _ in 0..5000 {
+            expected = step(&expected);
+        }
+        assert_eq!(expected, nth_state(7, 5000, step));
+        assert_eq!(7, nth_state(7, 0, step));
+    }
+}
@@ -0,0 +1,145 @@
+use std::fmt::Debug;
+use std::panic::{self, AssertUnwindSafe};
+
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+/// Lines of a failing input to include in a disagreement report.
+const INPUT_PREVIEW_LINES: usize = 20;
+
+/// The RNG handed to a day's `gen::input` for the given case number.
+pub fn seeded(seed: u64) -> StdRng {
+    StdRng::seed_from_u64(seed)
+}
+
+/// Run every solver on `input`, and describe how their answers differ if they do not all agree.
+///
+/// A solver that panics counts as disagreeing, and its panic message is shown in place of an answer.
+pub fn compare<T: PartialEq + Debug>(labels: &[&str], solvers: &[fn(&str) -> T], input: &str) -> Result<(), String> {
+    let answers: Vec<Result<T, String>> = solvers
+        .iter()
+        .map(|solver| {
+            panic::catch_unwind(AssertUnwindSafe(|| solver(input))).map_err(|e| {
+                let msg = e
+                    .downcast_ref::<&str>()
+                    .map(|s| s.to_string())
+                    .or_else(|| e.downcast_ref::<String>().cloned())
+                    .unwrap_or_default();
+                format!("panicked: {}", msg)
+            })
+        })
+        .collect();
+
+    if answers.iter().all(|a| a.is_ok() && *a == answers[0]) {
+        return Ok(());
+    }
+
+    let width = labels.iter().map(|l| l.len()).max().unwrap_or(0);
+    let mut report = String::from("solvers disagree:\n");
+    for (label, answer) in labels.iter().zip(&answers) {
+        let marker = if answer.is_ok() && *answer == answers[0] { ' ' } else { '!' };
+        let shown = match answer {
+            Ok(a) => format!("{:?}", a),
+            Err(e) => e.clone(),
+        };
+        report.push_str(&format!("  {} {:width$} => {}\n", marker, label, shown));
+    }
+    report.push_str("input:\n");
+    for line in input.lines().take(INPUT_PREVIEW_LINES) {
+        report.push_str(&format!("    {}\n", line));
+    }
+    if input.lines().count() > INPUT_PREVIEW_LINES {
+        report.push_str("    ...\n");
+    }
+    Err(report)
+}
+
+/// Check that every solver registered for a part agrees, on the given examples and on `cases` inputs from
+/// `gen(&mut rng, size)`.
+///
+/// ```ignore
+/// differential_test!(test_solvers_agree, part1: [INPUT1], part2: [INPUT1, INPUT2], gen: gen::input, size: 50, cases: 100);
+/// ```
+#[macro_export]
+macro_rules! differential_test {
+    (
+        $test:ident,
+        part1: [$($p1:expr),* $(,)?],
+        part2: [$($p2:expr),* $(,)?],
+        gen: $gen:path,
+        size: $size:expr,
+        cases: $cases:expr $(,)?
+    ) => {
+        #[test]
+        fn $test() {
+            let mut p1_inputs: Vec<(String, String)> = vec![$((stringify!($p1).to_string(), $p1.to_string())),*];
+            let mut p2_inputs: Vec<(String, String)> = vec![$((stringify!($p2).to_string(), $p2.to_string())),*];
+            for seed in 0..$cases {
+                let input = $gen(&mut $crate::differential::seeded(seed), $size);
+                let name = format!("{} seed {}", stringify!($gen), seed);
+                p1_inputs.push((name.clone(), input.clone()));
+                p2_inputs.push((name, input));
+            }
+
+            for (name, input) in &p1_inputs {
+                let result = $crate::differential::compare(
+                    &super::_gen_lists::P1_LABELS,
+                    &super::_gen_lists::P1_SOLUTIONS,
+                    input,
+                );
+                if let Err(report) = result {
+                    panic!("Part 1, {}: {}", name, report);
+                }
+            }
+            for (name, input) in &p2_inputs {
+                let result = $crate::differential::compare(
+                    &super::_gen_lists::P2_LABELS,
+                    &super::_gen_lists::P2_SOLUTIONS,
+                    input,
+                );
+                if let Err(report) = result {
+                    panic!("Part 2, {}: {}", name, report);
+                }
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn count_lines(input: &str) -> usize {
+        input.lines().count()
+    }
+
+    fn count_newlines(input: &str) -> usize {
+        input.matches('\n').count()
+    }
+
+    fn first_char(input: &str) -> usize {
+        input.chars().next().unwrap() as usize
+    }
+
+    #[test]
+    pub fn test_compare_agree() {
+        let solvers: [fn(&str) -> usize; 2] = [count_lines, count_newlines];
+        assert_eq!(Ok(()), compare(&["lines", "newlines"], &solvers, "a\nb\n"));
+    }
+
+    #[test]
+    pub fn test_compare_disagree() {
+        let solvers: [fn(&str) -> usize; 2] = [count_lines, count_newlines];
+        let report = compare(&["lines", "newlines"], &solvers, "a\nb").unwrap_err();
+        assert!(report.contains("    lines    => 2\n"), "{}", report);
+        assert!(report.contains("  ! newlines => 1\n"), "{}", report);
+        assert!(report.contains("input:\n    a\n    b\n"), "{}", report);
+    }
+
+    #[test]
+    pub fn test_compare_panic() {
+        let solvers: [fn(&str) -> usize; 2] = [count_lines, first_char];
+        let report = compare(&["lines", "first"], &solvers, "").unwrap_err();
+        assert!(report.contains("  ! first => panicked: called `Option::unwrap()`"), "{}", report);
+    }
+}
@@ -0,0 +1,442 @@
+//! Writing visualizer frames to image files, for `aoc run --export <path>`.
+//!
+//! Every cell of a frame becomes a `scale`-pixel square, coloured by a [`Palette`]. Titles aren't drawn. The file
+//! type follows the extension: `.gif` and `.apng` are animated, and `.png` is a still of the last frame.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::fs::{File, OpenOptions};
+use std::io::{self, BufWriter, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+
+use crate::visualize::{Cell, Color, Frame};
+
+pub type Rgb = [u8; 3];
+
+/// How to colour each kind of cell. Characters given with [`Palette::with`] win, then the cell's terminal colour,
+/// then blank cells (`.` and space) get the background and everything else the foreground.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Palette {
+    pub background: Rgb,
+    pub foreground: Rgb,
+    chars: HashMap<char, Rgb>,
+}
+
+impl Default for Palette {
+    fn default() -> Self {
+        Palette {
+            background: [0x0f, 0x0f, 0x23],
+            foreground: [0xcc, 0xcc, 0xcc],
+            chars: HashMap::new(),
+        }
+    }
+}
+
+impl Palette {
+    pub fn with(mut self, ch: char, rgb: Rgb) -> Palette {
+        self.chars.insert(ch, rgb);
+        self
+    }
+
+    pub fn rgb(&self, cell: &Cell) -> Rgb {
+        if let Some(&rgb) = self.chars.get(&cell.ch) {
+            return rgb;
+        }
+        match cell.color {
+            Some(color) => color_rgb(color),
+            None if cell.ch == '.' || cell.ch == ' ' => self.background,
+            None => self.foreground,
+        }
+    }
+}
+
+fn color_rgb(color: Color) -> Rgb {
+    match color {
+        Color::Red => [0xe0, 0x40, 0x40],
+        Color::Green => [0x40, 0xc0, 0x40],
+        Color::Yellow => [0xff, 0xd0, 0x40],
+        Color::Blue => [0x40, 0x80, 0xe0],
+        Color::Magenta => [0xc0, 0x50, 0xc0],
+        Color::Cyan => [0x40, 0xc0, 0xc0],
+        Color::White => [0xff, 0xff, 0xff],
+        Color::Gray => [0x70, 0x70, 0x70],
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Gif,
+    Apng,
+    Png,
+}
+
+impl Format {
+    pub fn from_path(path: &Path) -> io::Result<Format> {
+        match path
+            .extension()
+            .and_then(|e| e.to_str())
+            .map(str::to_ascii_lowercase)
+            .as_deref()
+        {
+            Some("gif") => Ok(Format::Gif),
+            Some("apng") => Ok(Format::Apng),
+            Some("png") => Ok(Format::Png),
+            _ => Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!(
+                    "can't tell the image type of {}, use .gif, .apng or .png",
+                    path.display()
+                ),
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Settings {
+    /// Pixels per cell, in each direction.
+    pub scale: usize,
+    /// Keep one frame in this many. The last frame is always kept.
+    pub every: usize,
+    /// Frames per second when played back.
+    pub fps: f64,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Settings {
+            scale: 4,
+            every: 1,
+            fps: 20.0,
+        }
+    }
+}
+
+/// Where kept frames go as they arrive.
+enum Sink {
+    Gif(gif::Encoder<BufWriter<File>>),
+    Png(png::Writer<BufWriter<File>>),
+}
+
+/// Writes frames to the file as they're recorded, so only the frame waiting on [`Settings::every`] stays in
+/// memory. The canvas takes the size of the first frame; larger frames after it are cropped.
+pub struct Recorder {
+    path: PathBuf,
+    format: Format,
+    settings: Settings,
+    colors: Vec<Rgb>,
+    /// Canvas width and height in pixels, once the first frame has arrived.
+    size: Option<(usize, usize)>,
+    sink: Option<Sink>,
+    /// The latest frame skipped by `every`, as palette indices, so the file still ends on the final state.
+    pending: Option<Vec<u8>>,
+    written: usize,
+    seen: usize,
+    cropped: bool,
+    /// The first write that failed. Recording stops there and [`Recorder::save`] reports it.
+    error: Option<io::Error>,
+}
+
+impl fmt::Debug for Recorder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Recorder")
+            .field("path", &self.path)
+            .field("format", &self.format)
+            .field("size", &self.size)
+            .field("written", &self.written)
+            .finish()
+    }
+}
+
+impl Recorder {
+    pub fn new(path: PathBuf, settings: Settings) -> io::Result<Recorder> {
+        Ok(Recorder {
+            format: Format::from_path(&path)?,
+            path,
+            settings,
+            colors: Vec::new(),
+            size: None,
+            sink: None,
+            pending: None,
+            written: 0,
+            seen: 0,
+            cropped: false,
+            error: None,
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn record(&mut self, frame: &Frame, palette: &Palette) {
+        if self.error.is_none() {
+            if let Err(e) = self.try_record(frame, palette) {
+                self.error = Some(e);
+            }
+        }
+    }
+
+    fn try_record(&mut self, frame: &Frame, palette: &Palette) -> io::Result<()> {
+        let canvas = self.canvas(frame, palette);
+        let keep = self.seen.is_multiple_of(self.settings.every.max(1)) && self.format != Format::Png;
+        self.seen += 1;
+        if keep {
+            self.pending = None;
+            self.write(canvas)
+        } else {
+            self.pending = Some(canvas);
+            Ok(())
+        }
+    }
+
+    /// How many frames the file will hold.
+    pub fn len(&self) -> usize {
+        self.written + self.pending.is_some() as usize
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Finish the file. Returns false, without creating it, if nothing was drawn.
+    pub fn save(mut self) -> io::Result<bool> {
+        if let Some(e) = self.error.take() {
+            return Err(e);
+        }
+        if let Some(canvas) = self.pending.take() {
+            self.write(canvas)?;
+        }
+        match self.sink.take() {
+            None => return Ok(false),
+            Some(Sink::Gif(encoder)) => encoder.into_inner()?.flush()?,
+            Some(Sink::Png(writer)) => {
+                writer.finish().map_err(io::Error::other)?;
+                if self.format == Format::Apng {
+                    set_frame_count(&self.path, self.written as u32)?;
+                }
+            }
+        }
+        Ok(true)
+    }
+
+    /// The palette index for `rgb`, adding it if it's new. GIF frames are indexed, so at most 256 colours fit.
+    fn index(&mut self, rgb: Rgb) -> u8 {
+        match self.colors.iter().position(|&c| c == rgb) {
+            Some(i) => i as u8,
+            None => {
+                assert!(self.colors.len() < 256, "frames use more than 256 colours");
+                self.colors.push(rgb);
+                (self.colors.len() - 1) as u8
+            }
+        }
+    }
+
+    /// `frame` scaled up onto the canvas, as palette indices. The first frame sets the canvas size.
+    fn canvas(&mut self, frame: &Frame, palette: &Palette) -> Vec<u8> {
+        let scale = self.settings.scale.max(1);
+        let cols = frame.rows.iter().map(Vec::len).max().unwrap_or(0);
+        let rows = frame.rows.len();
+        let (width, height) = *self.size.get_or_insert((cols.max(1) * scale, rows.max(1) * scale));
+        if (cols * scale > width || rows * scale > height) && !self.cropped {
+            self.cropped = true;
+            eprintln!(
+                "Frame {} is larger than the first one, cropping it to {}x{} cells in {}",
+                self.seen,
+                width / scale,
+                height / scale,
+                self.path.display()
+            );
+        }
+
+        let background = self.index(palette.background);
+        let mut pixels = vec![background; width * height];
+        for (r, row) in frame.rows.iter().enumerate().take(height / scale) {
+            for (c, cell) in row.iter().enumerate().take(width / scale) {
+                let index = self.index(palette.rgb(cell));
+                for y in r * scale..(r + 1) * scale {
+                    pixels[y * width + c * scale..y * width + (c + 1) * scale].fill(index);
+                }
+            }
+        }
+        pixels
+    }
+
+    fn delay_ms(&self) -> u16 {
+        (1000.0 / self.settings.fps).round().clamp(1.0, u16::MAX as f64) as u16
+    }
+
+    /// Write one canvas, creating the file on the first.
+    fn write(&mut self, canvas: Vec<u8>) -> io::Result<()> {
+        let (width, height) = self.size.expect("canvas written before its size was known");
+        if self.sink.is_none() {
+            self.sink = Some(self.open(width, height)?);
+        }
+        let delay = self.delay_ms().div_ceil(10);
+        match self.sink.as_mut().unwrap() {
+            Sink::Gif(encoder) => {
+                let mut frame = gif::Frame::from_indexed_pixels(width as u16, height as u16, canvas, None);
+                frame.palette = Some(self.colors.iter().flatten().copied().collect());
+                frame.delay = delay;
+                encoder.write_frame(&frame).map_err(io::Error::other)?;
+            }
+            Sink::Png(writer) => {
+                let rgb: Vec<u8> = canvas.iter().flat_map(|&i| self.colors[i as usize]).collect();
+                writer.write_image_data(&rgb).map_err(io::Error::other)?;
+            }
+        }
+        self.written += 1;
+        Ok(())
+    }
+
+    fn open(&self, width: usize, height: usize) -> io::Result<Sink> {
+        let file = BufWriter::new(File::create(&self.path)?);
+        match self.format {
+            Format::Gif => {
+                let mut encoder =
+                    gif::Encoder::new(file, width as u16, height as u16, &[]).map_err(io::Error::other)?;
+                encoder.set_repeat(gif::Repeat::Infinite).map_err(io::Error::other)?;
+                Ok(Sink::Gif(encoder))
+            }
+            Format::Apng | Format::Png => {
+                let mut encoder = png::Encoder::new(file, width as u32, height as u32);
+                encoder.set_color(png::ColorType::Rgb);
+                encoder.set_depth(png::BitDepth::Eight);
+                if self.format == Format::Apng {
+                    // The real count isn't known until the end; `set_frame_count` fills it in.
+                    encoder.set_animated(u32::MAX, 0).map_err(io::Error::other)?;
+                    encoder.set_frame_delay(self.delay_ms(), 1000).map_err(io::Error::other)?;
+                }
+                Ok(Sink::Png(encoder.write_header().map_err(io::Error::other)?))
+            }
+        }
+    }
+}
+
+/// Rewrite the frame count in an APNG's `acTL` chunk, and the chunk's CRC with it.
+fn set_frame_count(path: &Path, frames: u32) -> io::Result<()> {
+    let mut file = OpenOptions::new().read(true).write(true).open(path)?;
+    let mut head = vec![0; 256];
+    let read = file.read(&mut head)?;
+    let at = head[..read]
+        .windows(4)
+        .position(|w| w == b"acTL")
+        .filter(|&at| at + 16 <= read)
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "no acTL chunk in the APNG header"))?;
+    head[at + 4..at + 8].copy_from_slice(&frames.to_be_bytes());
+    let crc = crc32fast::hash(&head[at..at + 12]);
+    head[at + 12..at + 16].copy_from_slice(&crc.to_be_bytes());
+    file.seek(SeekFrom::Start(at as u64))?;
+    file.write_all(&head[at..at + 16])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame(rows: &[&str]) -> Frame {
+        let mut frame = Frame::new();
+        for row in rows {
+            frame.push_text(row);
+        }
+        frame
+    }
+
+    #[test]
+    pub fn test_palette() {
+        let palette = Palette::default().with('#', [1, 2, 3]);
+        assert_eq!([1, 2, 3], palette.rgb(&Cell::colored('#', Color::Red)));
+        assert_eq!(color_rgb(Color::Red), palette.rgb(&Cell::colored('o', Color::Red)));
+        assert_eq!(palette.background, palette.rgb(&Cell::new('.')));
+        assert_eq!(palette.foreground, palette.rgb(&Cell::new('x')));
+    }
+
+    #[test]
+    pub fn test_format() {
+        assert_eq!(Format::Gif, Format::from_path(Path::new("out/sand.GIF")).unwrap());
+        assert_eq!(Format::Apng, Format::from_path(Path::new("rope.apng")).unwrap());
+        assert_eq!(Format::Png, Format::from_path(Path::new("loop.png")).unwrap());
+        assert!(Format::from_path(Path::new("loop.jpg")).is_err());
+    }
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("aoc-export-{}-{}", name, std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    pub fn test_record_every() {
+        let dir = temp_dir("every");
+        let settings = Settings {
+            every: 3,
+            ..Settings::default()
+        };
+        let mut recorder = Recorder::new(dir.join("x.gif"), settings).unwrap();
+        for i in 0..5 {
+            recorder.record(&frame(&[&"#".repeat(i + 1)]), &Palette::default());
+        }
+        // Frames 0 and 3 are written, and the last one waits for `save`.
+        assert_eq!(2, recorder.written);
+        assert_eq!(3, recorder.len());
+        assert!(recorder.pending.is_some());
+
+        let mut still = Recorder::new(dir.join("x.png"), Settings::default()).unwrap();
+        for i in 0..5 {
+            still.record(&frame(&[&"#".repeat(i + 1)]), &Palette::default());
+        }
+        assert_eq!(0, still.written);
+        assert_eq!(1, still.len());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    pub fn test_crop_to_first_frame() {
+        let dir = temp_dir("crop");
+        let settings = Settings {
+            scale: 2,
+            ..Settings::default()
+        };
+        let mut recorder = Recorder::new(dir.join("x.gif"), settings).unwrap();
+        recorder.record(&frame(&["#."]), &Palette::default());
+        recorder.record(&frame(&["###", "###"]), &Palette::default());
+        assert_eq!(Some((4, 2)), recorder.size);
+        assert!(recorder.cropped);
+        recorder.save().unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    pub fn test_save() {
+        let dir = temp_dir("save");
+        for name in ["anim.gif", "anim.apng", "still.png"] {
+            let path = dir.join(name);
+            let mut recorder = Recorder::new(path.clone(), Settings::default()).unwrap();
+            recorder.record(&frame(&["#.", ".#"]), &Palette::default());
+            recorder.record(&frame(&["##", "#"]), &Palette::default());
+            recorder.record(&frame(&["..", "##"]), &Palette::default());
+            assert!(recorder.save().unwrap());
+            let bytes = std::fs::read(&path).unwrap();
+            assert!(bytes.starts_with(if name.ends_with("gif") { b"GIF89a" } else { b"\x89PNG" }));
+        }
+
+        let reader = png::Decoder::new(File::open(dir.join("anim.apng")).unwrap()).read_info().unwrap();
+        assert_eq!(3, reader.info().animation_control().unwrap().num_frames);
+        assert_eq!((8, 8), reader.info().size());
+        let mut frames = 0;
+        let mut reader = reader;
+        let mut buf = vec![0; reader.output_buffer_size()];
+        while reader.next_frame(&mut buf).is_ok() {
+            frames += 1;
+        }
+        assert_eq!(3, frames);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    pub fn test_nothing_drawn() {
+        let recorder = Recorder::new(PathBuf::from("never-written.gif"), Settings::default()).unwrap();
+        assert!(!recorder.save().unwrap());
+        assert!(!Path::new("never-written.gif").exists());
+    }
+}
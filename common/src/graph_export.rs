@@ -0,0 +1,488 @@
+//! Dumping puzzle graphs as Graphviz DOT or GraphML, to look at the structure behind a wrong answer.
+//!
+//! Build a [`GraphExport`] from whatever the day uses (a petgraph graph, an adjacency list, or by hand), attach
+//! attributes to nodes and edges, highlight the solution's path, and [`GraphExport::save`] it. The format follows
+//! the file extension.
+//!
+//! Solvers hand their graph to [`emit`], which writes it only while `aoc run --graph <path>` has a file waiting.
+
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Display;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+
+use petgraph::graph::{IndexType, NodeIndex};
+use petgraph::visit::EdgeRef;
+use petgraph::EdgeType;
+
+pub type Attrs = Vec<(String, String)>;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Node {
+    pub id: String,
+    pub label: Option<String>,
+    pub attrs: Attrs,
+    pub highlight: bool,
+}
+
+impl Node {
+    pub fn new(id: impl Into<String>) -> Node {
+        Node {
+            id: id.into(),
+            label: None,
+            attrs: Vec::new(),
+            highlight: false,
+        }
+    }
+
+    pub fn label(self, label: impl Into<String>) -> Node {
+        Node {
+            label: Some(label.into()),
+            ..self
+        }
+    }
+
+    pub fn attr(mut self, key: impl Into<String>, value: impl Display) -> Node {
+        self.attrs.push((key.into(), value.to_string()));
+        self
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Edge {
+    pub from: usize,
+    pub to: usize,
+    pub label: Option<String>,
+    pub attrs: Attrs,
+    pub highlight: bool,
+}
+
+impl Edge {
+    pub fn new(from: usize, to: usize) -> Edge {
+        Edge {
+            from,
+            to,
+            label: None,
+            attrs: Vec::new(),
+            highlight: false,
+        }
+    }
+
+    pub fn label(self, label: impl Into<String>) -> Edge {
+        Edge {
+            label: Some(label.into()),
+            ..self
+        }
+    }
+
+    pub fn attr(mut self, key: impl Into<String>, value: impl Display) -> Edge {
+        self.attrs.push((key.into(), value.to_string()));
+        self
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Dot,
+    GraphMl,
+}
+
+impl Format {
+    pub fn from_path(path: &Path) -> io::Result<Format> {
+        match path
+            .extension()
+            .and_then(|e| e.to_str())
+            .map(str::to_ascii_lowercase)
+            .as_deref()
+        {
+            Some("dot" | "gv") => Ok(Format::Dot),
+            Some("graphml") => Ok(Format::GraphMl),
+            _ => Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!(
+                    "can't tell the graph format of {}, use .dot, .gv or .graphml",
+                    path.display()
+                ),
+            )),
+        }
+    }
+}
+
+/// A graph ready to be written out. Nodes are numbered in the order they're added.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GraphExport {
+    pub directed: bool,
+    pub nodes: Vec<Node>,
+    pub edges: Vec<Edge>,
+    index: HashMap<String, usize>,
+}
+
+impl GraphExport {
+    pub fn new(directed: bool) -> GraphExport {
+        GraphExport {
+            directed,
+            ..GraphExport::default()
+        }
+    }
+
+    /// Every node and edge of a petgraph graph, with `node` and `edge` describing their weights. Node `i` here is
+    /// petgraph's node `i`.
+    pub fn from_petgraph<N, E, Ty: EdgeType, Ix: IndexType>(
+        graph: &petgraph::Graph<N, E, Ty, Ix>,
+        mut node: impl FnMut(NodeIndex<Ix>, &N) -> Node,
+        mut edge: impl FnMut(&E, Edge) -> Edge,
+    ) -> GraphExport {
+        let mut rv = GraphExport::new(graph.is_directed());
+        for idx in graph.node_indices() {
+            rv.add_node(node(idx, &graph[idx]));
+        }
+        for e in graph.edge_references() {
+            rv.add_edge(edge(e.weight(), Edge::new(e.source().index(), e.target().index())));
+        }
+        rv
+    }
+
+    /// A graph from node names and, for each node, the nodes it links to. Undirected links listed from both ends
+    /// become one edge.
+    pub fn from_adjacency(names: &[impl AsRef<str>], adj: &[Vec<usize>], directed: bool) -> GraphExport {
+        let mut rv = GraphExport::new(directed);
+        for name in names {
+            rv.add_node(Node::new(name.as_ref()));
+        }
+        for (a, ns) in adj.iter().enumerate() {
+            for &b in ns {
+                if directed || a < b || !adj[b].contains(&a) {
+                    rv.add_edge(Edge::new(a, b));
+                }
+            }
+        }
+        rv
+    }
+
+    /// Add `node`, or return the index of the node already using its id.
+    pub fn add_node(&mut self, node: Node) -> usize {
+        if let Some(&idx) = self.index.get(&node.id) {
+            return idx;
+        }
+        self.index.insert(node.id.clone(), self.nodes.len());
+        self.nodes.push(node);
+        self.nodes.len() - 1
+    }
+
+    pub fn node_index(&self, id: &str) -> Option<usize> {
+        self.index.get(id).copied()
+    }
+
+    pub fn add_edge(&mut self, edge: Edge) -> usize {
+        self.edges.push(edge);
+        self.edges.len() - 1
+    }
+
+    pub fn highlight_node(&mut self, idx: usize) {
+        self.nodes[idx].highlight = true;
+    }
+
+    /// Highlight every edge from `from` to `to` (or either way round, if undirected). Returns whether there were any.
+    pub fn highlight_edge(&mut self, from: usize, to: usize) -> bool {
+        let directed = self.directed;
+        let mut found = false;
+        for e in &mut self.edges {
+            if (e.from, e.to) == (from, to) || (!directed && (e.to, e.from) == (from, to)) {
+                e.highlight = true;
+                found = true;
+            }
+        }
+        found
+    }
+
+    /// Highlight the nodes of `path` and the edges between each consecutive pair.
+    pub fn highlight_path(&mut self, path: &[usize]) {
+        for &idx in path {
+            self.highlight_node(idx);
+        }
+        for pair in path.windows(2) {
+            self.highlight_edge(pair[0], pair[1]);
+        }
+    }
+
+    pub fn to_dot(&self) -> String {
+        let (kind, arrow) = if self.directed {
+            ("digraph", "->")
+        } else {
+            ("graph", "--")
+        };
+        let mut rv = format!("{} {{\n", kind);
+        for node in &self.nodes {
+            rv.push_str(&format!(
+                "    {}{};\n",
+                dot_quote(&node.id),
+                dot_attrs(&node.label, &node.attrs, node.highlight)
+            ));
+        }
+        for edge in &self.edges {
+            rv.push_str(&format!(
+                "    {} {} {}{};\n",
+                dot_quote(&self.nodes[edge.from].id),
+                arrow,
+                dot_quote(&self.nodes[edge.to].id),
+                dot_attrs(&edge.label, &edge.attrs, edge.highlight)
+            ));
+        }
+        rv.push_str("}\n");
+        rv
+    }
+
+    pub fn to_graphml(&self) -> String {
+        let node_keys = graphml_keys(self.nodes.iter().map(|n| &n.attrs));
+        let edge_keys = graphml_keys(self.edges.iter().map(|e| &e.attrs));
+
+        let mut rv = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        rv.push_str("<graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\">\n");
+        rv.push_str("  <key id=\"label\" for=\"all\" attr.name=\"label\" attr.type=\"string\"/>\n");
+        rv.push_str("  <key id=\"highlight\" for=\"all\" attr.name=\"highlight\" attr.type=\"boolean\">");
+        rv.push_str("<default>false</default></key>\n");
+        for (prefix, target, keys) in [("n", "node", &node_keys), ("e", "edge", &edge_keys)] {
+            for (name, ty) in keys {
+                rv.push_str(&format!(
+                    "  <key id=\"{}_{}\" for=\"{}\" attr.name=\"{}\" attr.type=\"{}\"/>\n",
+                    prefix,
+                    xml_escape(name),
+                    target,
+                    xml_escape(name),
+                    ty
+                ));
+            }
+        }
+        rv.push_str(&format!(
+            "  <graph id=\"G\" edgedefault=\"{}\">\n",
+            if self.directed { "directed" } else { "undirected" }
+        ));
+        for node in &self.nodes {
+            rv.push_str(&format!("    <node id=\"{}\">", xml_escape(&node.id)));
+            rv.push_str(&graphml_data("n", &node.label, &node.attrs, node.highlight));
+            rv.push_str("</node>\n");
+        }
+        for edge in &self.edges {
+            rv.push_str(&format!(
+                "    <edge source=\"{}\" target=\"{}\">",
+                xml_escape(&self.nodes[edge.from].id),
+                xml_escape(&self.nodes[edge.to].id)
+            ));
+            rv.push_str(&graphml_data("e", &edge.label, &edge.attrs, edge.highlight));
+            rv.push_str("</edge>\n");
+        }
+        rv.push_str("  </graph>\n</graphml>\n");
+        rv
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        let text = match Format::from_path(path)? {
+            Format::Dot => self.to_dot(),
+            Format::GraphMl => self.to_graphml(),
+        };
+        std::fs::write(path, text)
+    }
+}
+
+/// The file waiting for a graph, and how writing the last one went.
+struct Target {
+    path: PathBuf,
+    written: Option<io::Result<()>>,
+}
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static TARGET: Mutex<Option<Target>> = Mutex::new(None);
+
+/// Write the graph from every later [`emit`] to `path` until [`finish`]. Fails if the path isn't DOT or GraphML.
+pub fn record(path: PathBuf) -> io::Result<()> {
+    Format::from_path(&path)?;
+    *TARGET.lock().unwrap() = Some(Target { path, written: None });
+    ENABLED.store(true, Ordering::Relaxed);
+    Ok(())
+}
+
+/// Whether a graph is wanted. Worth checking before collecting extra data just for the export.
+pub fn enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// Write the graph `build` returns, if one is wanted. `build` only runs then, so it can do the extra work itself.
+pub fn emit(build: impl FnOnce() -> GraphExport) {
+    if !enabled() {
+        return;
+    }
+    let graph = build();
+    if let Some(target) = TARGET.lock().unwrap().as_mut() {
+        target.written = Some(graph.save(&target.path));
+    }
+}
+
+/// Stop writing graphs. Returns false if nothing was emitted since [`record`], or the error writing it.
+pub fn finish() -> io::Result<bool> {
+    ENABLED.store(false, Ordering::Relaxed);
+    match TARGET.lock().unwrap().take().and_then(|t| t.written) {
+        Some(written) => written.map(|_| true),
+        None => Ok(false),
+    }
+}
+
+fn dot_quote(s: &str) -> String {
+    format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+fn dot_attrs(label: &Option<String>, attrs: &Attrs, highlight: bool) -> String {
+    let mut parts: Vec<String> = Vec::new();
+    if let Some(label) = label {
+        parts.push(format!("label={}", dot_quote(label)));
+    }
+    parts.extend(attrs.iter().map(|(k, v)| format!("{}={}", k, dot_quote(v))));
+    if highlight {
+        parts.push("color=red, penwidth=3".to_string());
+    }
+    if parts.is_empty() {
+        String::new()
+    } else {
+        format!(" [{}]", parts.join(", "))
+    }
+}
+
+fn xml_escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+/// Every attribute name used, with the narrowest GraphML type that holds all of its values.
+fn graphml_keys<'a>(all: impl Iterator<Item = &'a Attrs>) -> BTreeMap<&'a str, &'static str> {
+    let mut keys: BTreeMap<&str, &str> = BTreeMap::new();
+    for (k, v) in all.flatten() {
+        let ty = if v.parse::<i64>().is_ok() {
+            "long"
+        } else if v.parse::<f64>().is_ok() {
+            "double"
+        } else {
+            "string"
+        };
+        let entry = keys.entry(k.as_str()).or_insert(ty);
+        *entry = match (*entry, ty) {
+            (a, b) if a == b => a,
+            ("long", "double") | ("double", "long") => "double",
+            _ => "string",
+        };
+    }
+    keys
+}
+
+fn graphml_data(prefix: &str, label: &Option<String>, attrs: &Attrs, highlight: bool) -> String {
+    let mut rv = String::new();
+    if let Some(label) = label {
+        rv.push_str(&format!("<data key=\"label\">{}</data>", xml_escape(label)));
+    }
+    for (k, v) in attrs {
+        rv.push_str(&format!(
+            "<data key=\"{}_{}\">{}</data>",
+            prefix,
+            xml_escape(k),
+            xml_escape(v)
+        ));
+    }
+    if highlight {
+        rv.push_str("<data key=\"highlight\">true</data>");
+    }
+    rv
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn triangle() -> GraphExport {
+        GraphExport::from_adjacency(&["a", "b", "c"], &[vec![1, 2], vec![0, 2], vec![0, 1]], false)
+    }
+
+    #[test]
+    pub fn test_from_adjacency() {
+        let graph = triangle();
+        assert_eq!(3, graph.edges.len());
+        assert_eq!(Some(2), graph.node_index("c"));
+
+        let directed = GraphExport::from_adjacency(&["a", "b"], &[vec![1], vec![0]], true);
+        assert_eq!(2, directed.edges.len());
+    }
+
+    #[test]
+    pub fn test_dot() {
+        let mut graph = triangle();
+        graph.nodes[0].attrs.push(("flow".to_string(), "13".to_string()));
+        graph.highlight_path(&[2, 0]);
+        assert_eq!(
+            "graph {\n    \"a\" [flow=\"13\", color=red, penwidth=3];\n    \"b\";\n    \"c\" [color=red, penwidth=3];\n    \
+             \"a\" -- \"b\";\n    \"a\" -- \"c\" [color=red, penwidth=3];\n    \"b\" -- \"c\";\n}\n",
+            graph.to_dot()
+        );
+    }
+
+    #[test]
+    pub fn test_graphml() {
+        let mut graph = GraphExport::new(true);
+        let a = graph.add_node(Node::new("A&1").attr("height", 3));
+        let b = graph.add_node(Node::new("B").attr("height", 2.5));
+        assert_eq!(a, graph.add_node(Node::new("A&1")));
+        graph.add_edge(Edge::new(a, b).label("L"));
+        assert!(graph.highlight_edge(a, b));
+        assert!(!graph.highlight_edge(b, a));
+
+        let xml = graph.to_graphml();
+        assert!(xml.contains("<key id=\"n_height\" for=\"node\" attr.name=\"height\" attr.type=\"double\"/>"));
+        assert!(xml.contains("<graph id=\"G\" edgedefault=\"directed\">"));
+        assert!(xml.contains("<node id=\"A&amp;1\"><data key=\"n_height\">3</data></node>"));
+        assert!(xml.contains(
+            "<edge source=\"A&amp;1\" target=\"B\"><data key=\"label\">L</data><data key=\"highlight\">true</data></edge>"
+        ));
+    }
+
+    #[test]
+    pub fn test_from_petgraph() {
+        let mut pg: petgraph::Graph<char, u32> = petgraph::Graph::new();
+        let a = pg.add_node('a');
+        let b = pg.add_node('b');
+        pg.add_edge(a, b, 7);
+        let graph = GraphExport::from_petgraph(
+            &pg,
+            |idx, &c| Node::new(format!("n{}", idx.index())).label(c.to_string()),
+            |&w, e| e.attr("weight", w),
+        );
+        assert!(graph.directed);
+        assert_eq!("digraph {\n    \"n0\" [label=\"a\"];\n    \"n1\" [label=\"b\"];\n    \"n0\" -> \"n1\" [weight=\"7\"];\n}\n", graph.to_dot());
+    }
+
+    #[test]
+    pub fn test_format() {
+        assert_eq!(Format::Dot, Format::from_path(Path::new("x.gv")).unwrap());
+        assert_eq!(Format::GraphMl, Format::from_path(Path::new("x.GraphML")).unwrap());
+        assert!(Format::from_path(Path::new("x.txt")).is_err());
+    }
+
+    #[test]
+    pub fn test_emit() {
+        emit(|| panic!("built without a target"));
+        assert!(record(PathBuf::from("x.txt")).is_err());
+
+        let path = std::env::temp_dir().join(format!("aoc-graph-{}.dot", std::process::id()));
+        record(path.clone()).unwrap();
+        assert!(enabled());
+        emit(triangle);
+        assert!(finish().unwrap());
+        assert!(!enabled());
+        assert!(std::fs::read_to_string(&path).unwrap().starts_with("graph {"));
+        std::fs::remove_file(&path).unwrap();
+
+        record(path.clone()).unwrap();
+        assert!(!finish().unwrap());
+        assert!(!path.exists());
+    }
+}
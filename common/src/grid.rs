@@ -0,0 +1,485 @@
+use std::fmt::Display;
+use std::ops::{Index, IndexMut, Neg};
+
+/// Zero-indexed (row, column) coordinate within a grid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct Pos {
+    pub row: usize,
+    pub col: usize,
+}
+
+impl Pos {
+    pub const fn new(row: usize, col: usize) -> Pos {
+        Pos { row, col }
+    }
+
+    /// Move by the given row/column deltas, or `None` if that would leave the first quadrant.
+    pub fn offset(self, d_row: isize, d_col: isize) -> Option<Pos> {
+        Some(Pos {
+            row: self.row.checked_add_signed(d_row)?,
+            col: self.col.checked_add_signed(d_col)?,
+        })
+    }
+
+    pub fn step(self, dir: Dir) -> Option<Pos> {
+        let (d_row, d_col) = dir.delta();
+        self.offset(d_row, d_col)
+    }
+
+    pub fn manhattan(self, other: Pos) -> usize {
+        self.row.abs_diff(other.row) + self.col.abs_diff(other.col)
+    }
+}
+
+impl From<(usize, usize)> for Pos {
+    fn from((row, col): (usize, usize)) -> Self {
+        Pos { row, col }
+    }
+}
+
+impl From<Pos> for (usize, usize) {
+    fn from(p: Pos) -> Self {
+        (p.row, p.col)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Dir {
+    North,
+    East,
+    South,
+    West,
+}
+
+impl Dir {
+    pub const ALL: [Dir; 4] = [Dir::North, Dir::East, Dir::South, Dir::West];
+
+    /// (row, column) delta of a single step in this direction.
+    pub const fn delta(self) -> (isize, isize) {
+        match self {
+            Dir::North => (-1, 0),
+            Dir::East => (0, 1),
+            Dir::South => (1, 0),
+            Dir::West => (0, -1),
+        }
+    }
+
+    pub const fn rotate_cw(self) -> Dir {
+        match self {
+            Dir::North => Dir::East,
+            Dir::East => Dir::South,
+            Dir::South => Dir::West,
+            Dir::West => Dir::North,
+        }
+    }
+
+    pub const fn rotate_ccw(self) -> Dir {
+        match self {
+            Dir::North => Dir::West,
+            Dir::East => Dir::North,
+            Dir::South => Dir::East,
+            Dir::West => Dir::South,
+        }
+    }
+
+    pub const fn opposite(self) -> Dir {
+        match self {
+            Dir::North => Dir::South,
+            Dir::East => Dir::West,
+            Dir::South => Dir::North,
+            Dir::West => Dir::East,
+        }
+    }
+}
+
+impl Neg for Dir {
+    type Output = Dir;
+
+    fn neg(self) -> Self::Output {
+        self.opposite()
+    }
+}
+
+const NEIGHBORS_8: [(isize, isize); 8] = [(-1, -1), (-1, 0), (-1, 1), (0, -1), (0, 1), (1, -1), (1, 0), (1, 1)];
+
+/// Dense, row-major 2D grid.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    rows: usize,
+    cols: usize,
+}
+
+impl<T> Grid<T> {
+    pub fn from_vec(cells: Vec<T>, cols: usize) -> Grid<T> {
+        assert!(cols > 0 || cells.is_empty(), "A grid with cells must have at least one column.");
+        assert_eq!(0, cells.len() % cols.max(1), "Cell count is not a multiple of the column count.");
+        let rows = cells.len().checked_div(cols).unwrap_or(0);
+        Grid { cells, rows, cols }
+    }
+
+    /// Parse one row per non-blank line, converting each byte with `f`.
+    pub fn parse_with(input: &str, mut f: impl FnMut(u8) -> T) -> Grid<T> {
+        let mut cells = Vec::with_capacity(input.len());
+        let mut cols = 0;
+        for ln in input.lines().map(|l| l.trim_end_matches('\r')).filter(|l| !l.is_empty()) {
+            if cols == 0 {
+                cols = ln.len();
+            }
+            assert_eq!(cols, ln.len(), "Grid rows must all be the same width: '{}'", ln);
+            cells.extend(ln.bytes().map(&mut f));
+        }
+        Grid::from_vec(cells, cols)
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    pub fn in_bounds(&self, pos: Pos) -> bool {
+        pos.row < self.rows && pos.col < self.cols
+    }
+
+    pub fn get(&self, pos: impl Into<Pos>) -> Option<&T> {
+        let pos = pos.into();
+        self.in_bounds(pos).then(|| &self.cells[pos.row * self.cols + pos.col])
+    }
+
+    pub fn get_mut(&mut self, pos: impl Into<Pos>) -> Option<&mut T> {
+        let pos = pos.into();
+        if self.in_bounds(pos) {
+            Some(&mut self.cells[pos.row * self.cols + pos.col])
+        } else {
+            None
+        }
+    }
+
+    /// The neighbouring position in `dir`, if it is inside the grid.
+    pub fn step(&self, pos: Pos, dir: Dir) -> Option<Pos> {
+        pos.step(dir).filter(|p| self.in_bounds(*p))
+    }
+
+    pub fn neighbors4(&self, pos: Pos) -> impl Iterator<Item = (Dir, Pos)> + '_ {
+        Dir::ALL.into_iter().filter_map(move |d| self.step(pos, d).map(|p| (d, p)))
+    }
+
+    pub fn neighbors8(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        NEIGHBORS_8
+            .into_iter()
+            .filter_map(move |(dr, dc)| pos.offset(dr, dc))
+            .filter(|p| self.in_bounds(*p))
+    }
+
+    pub fn row(&self, row: usize) -> &[T] {
+        &self.cells[row * self.cols..(row + 1) * self.cols]
+    }
+
+    pub fn row_mut(&mut self, row: usize) -> &mut [T] {
+        &mut self.cells[row * self.cols..(row + 1) * self.cols]
+    }
+
+    pub fn col(&self, col: usize) -> impl DoubleEndedIterator<Item = &T> + ExactSizeIterator + '_ {
+        assert!(col < self.cols, "Column {} out of bounds.", col);
+        self.cells[col..].iter().step_by(self.cols)
+    }
+
+    /// Cells from `start` heading down and to the right.
+    pub fn diagonal(&self, start: Pos) -> impl Iterator<Item = &T> + '_ {
+        (0..).map_while(move |i| self.get((start.row + i, start.col + i)))
+    }
+
+    /// Cells from `start` heading down and to the left.
+    pub fn anti_diagonal(&self, start: Pos) -> impl Iterator<Item = &T> + '_ {
+        (0..=start.col).map_while(move |i| self.get((start.row + i, start.col - i)))
+    }
+
+    pub fn iter_rows(&self) -> impl Iterator<Item = &[T]> + '_ {
+        self.cells.chunks(self.cols.max(1))
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, T> {
+        self.cells.iter()
+    }
+
+    pub fn iter_mut(&mut self) -> std::slice::IterMut<'_, T> {
+        self.cells.iter_mut()
+    }
+
+    pub fn positions(&self) -> impl Iterator<Item = Pos> {
+        let cols = self.cols;
+        (0..self.rows).flat_map(move |r| (0..cols).map(move |c| Pos::new(r, c)))
+    }
+
+    pub fn indexed_iter(&self) -> impl Iterator<Item = (Pos, &T)> + '_ {
+        self.positions().zip(self.cells.iter())
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            cells: self.cells.iter().map(f).collect(),
+            rows: self.rows,
+            cols: self.cols,
+        }
+    }
+
+    pub fn view(&self) -> GridView<'_, T> {
+        GridView {
+            grid: self,
+            transposed: false,
+            flip_rows: false,
+            flip_cols: false,
+        }
+    }
+
+    pub fn transposed(&self) -> GridView<'_, T> {
+        self.view().transpose()
+    }
+
+    pub fn rotated_cw(&self) -> GridView<'_, T> {
+        self.view().rotate_cw()
+    }
+
+    pub fn rotated_ccw(&self) -> GridView<'_, T> {
+        self.view().rotate_ccw()
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn new(rows: usize, cols: usize, fill: T) -> Grid<T> {
+        Grid {
+            cells: vec![fill; rows * cols],
+            rows,
+            cols,
+        }
+    }
+}
+
+impl Grid<u8> {
+    pub fn parse_bytes(input: &str) -> Grid<u8> {
+        Grid::parse_with(input, |b| b)
+    }
+}
+
+impl Grid<char> {
+    pub fn parse_chars(input: &str) -> Grid<char> {
+        Grid::parse_with(input, char::from)
+    }
+}
+
+impl<T, P: Into<Pos>> Index<P> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: P) -> &Self::Output {
+        let pos = pos.into();
+        assert!(self.in_bounds(pos), "Position {:?} is outside of the grid.", pos);
+        &self.cells[pos.row * self.cols + pos.col]
+    }
+}
+
+impl<T, P: Into<Pos>> IndexMut<P> for Grid<T> {
+    fn index_mut(&mut self, pos: P) -> &mut Self::Output {
+        let pos = pos.into();
+        assert!(self.in_bounds(pos), "Position {:?} is outside of the grid.", pos);
+        &mut self.cells[pos.row * self.cols + pos.col]
+    }
+}
+
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.view().fmt(f)
+    }
+}
+
+/// Read-only view of a grid under some combination of transposition and flips. Creating or composing
+/// views never copies the underlying cells.
+#[derive(Debug)]
+pub struct GridView<'a, T> {
+    grid: &'a Grid<T>,
+    transposed: bool,
+    flip_rows: bool,
+    flip_cols: bool,
+}
+
+impl<T> Clone for GridView<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for GridView<'_, T> {}
+
+impl<'a, T> GridView<'a, T> {
+    pub fn rows(&self) -> usize {
+        if self.transposed {
+            self.grid.cols
+        } else {
+            self.grid.rows
+        }
+    }
+
+    pub fn cols(&self) -> usize {
+        if self.transposed {
+            self.grid.rows
+        } else {
+            self.grid.cols
+        }
+    }
+
+    /// Position in the underlying grid that this view shows at `pos`.
+    fn source_pos(&self, pos: Pos) -> Option<Pos> {
+        if pos.row >= self.rows() || pos.col >= self.cols() {
+            return None;
+        }
+        let row = if self.flip_rows { self.rows() - 1 - pos.row } else { pos.row };
+        let col = if self.flip_cols { self.cols() - 1 - pos.col } else { pos.col };
+        if self.transposed {
+            Some(Pos::new(col, row))
+        } else {
+            Some(Pos::new(row, col))
+        }
+    }
+
+    pub fn get(&self, pos: impl Into<Pos>) -> Option<&'a T> {
+        self.source_pos(pos.into()).map(|p| &self.grid[p])
+    }
+
+    pub fn transpose(self) -> GridView<'a, T> {
+        GridView {
+            grid: self.grid,
+            transposed: !self.transposed,
+            flip_rows: self.flip_cols,
+            flip_cols: self.flip_rows,
+        }
+    }
+
+    pub fn flip_rows(self) -> GridView<'a, T> {
+        GridView {
+            flip_rows: !self.flip_rows,
+            ..self
+        }
+    }
+
+    pub fn flip_cols(self) -> GridView<'a, T> {
+        GridView {
+            flip_cols: !self.flip_cols,
+            ..self
+        }
+    }
+
+    pub fn rotate_cw(self) -> GridView<'a, T> {
+        self.transpose().flip_cols()
+    }
+
+    pub fn rotate_ccw(self) -> GridView<'a, T> {
+        self.transpose().flip_rows()
+    }
+
+    pub fn row(self, row: usize) -> impl DoubleEndedIterator<Item = &'a T> + ExactSizeIterator + 'a {
+        assert!(row < self.rows(), "Row {} out of bounds.", row);
+        (0..self.cols()).map(move |c| self.get(Pos::new(row, c)).unwrap())
+    }
+
+    pub fn col(self, col: usize) -> impl DoubleEndedIterator<Item = &'a T> + ExactSizeIterator + 'a {
+        assert!(col < self.cols(), "Column {} out of bounds.", col);
+        (0..self.rows()).map(move |r| self.get(Pos::new(r, col)).unwrap())
+    }
+
+    pub fn iter_rows(self) -> impl Iterator<Item = impl DoubleEndedIterator<Item = &'a T> + 'a> + 'a {
+        (0..self.rows()).map(move |r| self.row(r))
+    }
+
+    pub fn to_grid(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        let cells = self.iter_rows().flatten().cloned().collect();
+        Grid::from_vec(cells, self.cols())
+    }
+}
+
+impl<'a, T, P: Into<Pos>> Index<P> for GridView<'a, T> {
+    type Output = T;
+
+    fn index(&self, pos: P) -> &Self::Output {
+        let pos = pos.into();
+        self.get(pos)
+            .unwrap_or_else(|| panic!("Position {:?} is outside of the grid view.", pos))
+    }
+}
+
+impl<'a, T: Display> Display for GridView<'a, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for row in self.iter_rows() {
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    const SAMPLE: &str = "abc\ndef\n";
+
+    fn sample() -> Grid<char> {
+        Grid::parse_chars(SAMPLE)
+    }
+
+    #[test]
+    pub fn test_parse_and_display() {
+        let g = sample();
+        assert_eq!((2, 3), (g.rows(), g.cols()));
+        assert_eq!('f', g[(1, 2)]);
+        assert_eq!(SAMPLE, g.to_string());
+    }
+
+    #[test_case(|g| g.transposed(), "ad\nbe\ncf\n"; "transpose")]
+    #[test_case(|g| g.rotated_cw(), "da\neb\nfc\n"; "rotate cw")]
+    #[test_case(|g| g.rotated_ccw(), "cf\nbe\nad\n"; "rotate ccw")]
+    #[test_case(|g| g.rotated_cw().rotate_cw().rotate_cw(), "cf\nbe\nad\n"; "rotate cw three times")]
+    #[test_case(|g| g.view().flip_rows(), "def\nabc\n"; "flip rows")]
+    #[test_case(|g| g.transposed().transpose(), "abc\ndef\n"; "transpose twice")]
+    pub fn test_views(f: fn(&Grid<char>) -> GridView<'_, char>, expected: &str) {
+        let g = sample();
+        assert_eq!(expected, f(&g).to_string());
+        assert_eq!(expected, f(&g).to_grid().to_string());
+    }
+
+    #[test]
+    pub fn test_neighbors() {
+        let g = sample();
+        let corner = g.neighbors4(Pos::new(0, 0)).collect::<Vec<_>>();
+        assert_eq!(vec![(Dir::East, Pos::new(0, 1)), (Dir::South, Pos::new(1, 0))], corner);
+        assert_eq!(5, g.neighbors8(Pos::new(0, 1)).count());
+        assert_eq!(3, g.neighbors8(Pos::new(1, 2)).count());
+    }
+
+    #[test]
+    pub fn test_lines() {
+        let g = Grid::parse_chars("abcd\nefgh\nijkl\n");
+        assert_eq!("bfj", g.col(1).collect::<String>());
+        assert_eq!("bgl", g.diagonal(Pos::new(0, 1)).collect::<String>());
+        assert_eq!("cfi", g.anti_diagonal(Pos::new(0, 2)).collect::<String>());
+        assert_eq!("lhd", g.transposed().row(3).rev().collect::<String>());
+    }
+
+    #[test]
+    pub fn test_dirs() {
+        for d in Dir::ALL {
+            assert_eq!(d, d.rotate_cw().rotate_ccw());
+            assert_eq!(-d, d.rotate_cw().rotate_cw());
+            assert_eq!(d, -(-d));
+        }
+        assert_eq!(None, Pos::new(0, 3).step(Dir::North));
+        assert_eq!(Some(Pos::new(1, 3)), Pos::new(0, 3).step(Dir::South));
+    }
+}
@@ -0,0 +1,177 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+/// Puzzle inputs laid out as `<root>/<year>/day<NN>.txt`, with an `answers.toml` ledger at the root.
+#[derive(Debug, Clone)]
+pub struct InputStore {
+    root: PathBuf,
+}
+
+impl InputStore {
+    pub const ENV_VAR: &'static str = "AOC_INPUTS";
+    pub const DEFAULT_ROOT: &'static str = "inputs";
+
+    pub fn new(root: impl Into<PathBuf>) -> InputStore {
+        InputStore { root: root.into() }
+    }
+
+    /// Root from `$AOC_INPUTS`, falling back to `./inputs`.
+    pub fn from_env() -> InputStore {
+        InputStore::from_env_or(Self::DEFAULT_ROOT)
+    }
+
+    /// Root from `$AOC_INPUTS`, falling back to `default`.
+    pub fn from_env_or(default: impl Into<PathBuf>) -> InputStore {
+        InputStore::new(std::env::var_os(Self::ENV_VAR).map_or_else(|| default.into(), PathBuf::from))
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    pub fn input_path(&self, year: u32, day: u32) -> PathBuf {
+        self.root.join(year.to_string()).join(format!("day{:02}.txt", day))
+    }
+
+    pub fn load_input(&self, year: u32, day: u32) -> io::Result<String> {
+        fs::read_to_string(self.input_path(year, day))
+    }
+
+    pub fn ledger_path(&self) -> PathBuf {
+        self.root.join("answers.toml")
+    }
+
+    /// The answer ledger, or an empty one if none has been written yet.
+    pub fn load_ledger(&self) -> io::Result<Ledger> {
+        match fs::read_to_string(self.ledger_path()) {
+            Ok(s) => Ledger::parse(&s),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Ledger::default()),
+            Err(e) => Err(e),
+        }
+    }
+
+    pub fn save_ledger(&self, ledger: &Ledger) -> io::Result<()> {
+        fs::create_dir_all(&self.root)?;
+        fs::write(self.ledger_path(), ledger.to_toml()?)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    Wrong,
+    Unknown,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+struct DayAnswers {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    part1: Option<toml::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    part2: Option<toml::Value>,
+}
+
+/// Accepted answers, stored as `[<year>.day<NN>]` tables with `part1`/`part2` keys.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Ledger {
+    years: BTreeMap<String, BTreeMap<String, DayAnswers>>,
+}
+
+impl Ledger {
+    pub fn parse(s: &str) -> io::Result<Ledger> {
+        toml::from_str(s).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    pub fn to_toml(&self) -> io::Result<String> {
+        toml::to_string(self).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    fn slot(answers: &mut DayAnswers, part: u8) -> &mut Option<toml::Value> {
+        match part {
+            1 => &mut answers.part1,
+            2 => &mut answers.part2,
+            _ => panic!("No such part: {}", part),
+        }
+    }
+
+    /// The accepted answer, if any. Hand-written integer answers are returned in their decimal form.
+    pub fn get(&self, year: u32, day: u32, part: u8) -> Option<String> {
+        let answers = self.years.get(&year.to_string())?.get(&format!("day{:02}", day))?;
+        let value = match part {
+            1 => answers.part1.as_ref(),
+            2 => answers.part2.as_ref(),
+            _ => None,
+        }?;
+        match value {
+            toml::Value::String(s) => Some(s.clone()),
+            other => Some(other.to_string()),
+        }
+    }
+
+    pub fn set(&mut self, year: u32, day: u32, part: u8, answer: &str) {
+        let answers = self
+            .years
+            .entry(year.to_string())
+            .or_default()
+            .entry(format!("day{:02}", day))
+            .or_default();
+        *Ledger::slot(answers, part) = Some(toml::Value::String(answer.to_string()));
+    }
+
+    pub fn check(&self, year: u32, day: u32, part: u8, answer: &str) -> Verdict {
+        match self.get(year, day, part) {
+            Some(expected) if expected == answer => Verdict::Correct,
+            Some(_) => Verdict::Wrong,
+            None => Verdict::Unknown,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    const LEDGER: &str = r#"
+[2023.day05]
+part1 = "35"
+part2 = 46
+
+[2022.day10]
+part1 = 13140
+"#;
+
+    #[test]
+    pub fn test_input_path() {
+        let store = InputStore::new("/tmp/aoc");
+        assert_eq!(Path::new("/tmp/aoc/2023/day05.txt"), store.input_path(2023, 5));
+        assert_eq!(Path::new("/tmp/aoc/2022/day17.txt"), store.input_path(2022, 17));
+    }
+
+    #[test_case(2023, 5, 1, "35", Verdict::Correct; "string answer")]
+    #[test_case(2023, 5, 2, "46", Verdict::Correct; "integer answer")]
+    #[test_case(2023, 5, 2, "47", Verdict::Wrong; "wrong")]
+    #[test_case(2022, 10, 2, "EHZFZHCZ", Verdict::Unknown; "missing part")]
+    #[test_case(2024, 1, 1, "11", Verdict::Unknown; "missing year")]
+    pub fn test_check(year: u32, day: u32, part: u8, answer: &str, expected: Verdict) {
+        let ledger = Ledger::parse(LEDGER).unwrap();
+        assert_eq!(expected, ledger.check(year, day, part, answer));
+    }
+
+    #[test]
+    pub fn test_round_trip() {
+        let mut ledger = Ledger::parse(LEDGER).unwrap();
+        ledger.set(2022, 10, 2, "EHZFZHCZ");
+        ledger.set(2024, 1, 1, "11");
+
+        let reparsed = Ledger::parse(&ledger.to_toml().unwrap()).unwrap();
+        assert_eq!(ledger, reparsed);
+        assert_eq!(Some("EHZFZHCZ".to_string()), reparsed.get(2022, 10, 2));
+        assert_eq!(Some("13140".to_string()), reparsed.get(2022, 10, 1));
+    }
+}
@@ -0,0 +1,273 @@
+use std::cmp::{max, min};
+use std::ops::{Add, RangeInclusive, Sub};
+
+use crate::ranges::{RangeBound, RangeSet};
+
+/// Bounds needed to shift values from one range onto another.
+pub trait MapBound: RangeBound + Add<Output = Self> + Sub<Output = Self> {}
+
+impl<T: RangeBound + Add<Output = T> + Sub<Output = T>> MapBound for T {}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct Segment<T> {
+    src: RangeInclusive<T>,
+    dst_start: T,
+}
+
+impl<T: MapBound> Segment<T> {
+    fn map(&self, x: T) -> T {
+        self.dst_start + (x - *self.src.start())
+    }
+
+    fn unmap(&self, y: T) -> T {
+        *self.src.start() + (y - self.dst_start)
+    }
+
+    fn dst(&self) -> RangeInclusive<T> {
+        self.dst_start..=self.map(*self.src.end())
+    }
+}
+
+/// A map over integers that shifts each of a set of disjoint source ranges onto a destination range.
+/// Values outside every source range map to themselves.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct IntervalMap<T> {
+    segments: Vec<Segment<T>>,
+}
+
+impl<T> Default for IntervalMap<T> {
+    fn default() -> Self {
+        IntervalMap { segments: Vec::new() }
+    }
+}
+
+impl<T: MapBound> IntervalMap<T> {
+    pub fn new() -> IntervalMap<T> {
+        IntervalMap::default()
+    }
+
+    /// Map `src` onto the range starting at `dst_start`. Any part of `src` that is already mapped keeps its
+    /// existing mapping.
+    pub fn insert(&mut self, src: RangeInclusive<T>, dst_start: T) {
+        if src.is_empty() {
+            return;
+        }
+        let seg = Segment { src, dst_start };
+        let mapped: RangeSet<T> = self.segments.iter().map(|s| s.src.clone()).collect();
+        let fresh = RangeSet::from(seg.src.clone()).difference(&mapped);
+
+        self.segments.extend(fresh.iter().map(|r| Segment {
+            src: r.clone(),
+            dst_start: seg.map(*r.start()),
+        }));
+        self.segments.sort_unstable_by_key(|s| *s.src.start());
+    }
+
+    /// Source and destination ranges of every explicitly-mapped segment, in source order.
+    pub fn segments(&self) -> impl Iterator<Item = (RangeInclusive<T>, RangeInclusive<T>)> + '_ {
+        self.segments.iter().map(|s| (s.src.clone(), s.dst()))
+    }
+
+    pub fn get(&self, x: T) -> T {
+        let idx = self.segments.partition_point(|s| *s.src.end() < x);
+        match self.segments.get(idx) {
+            Some(seg) if *seg.src.start() <= x => seg.map(x),
+            _ => x,
+        }
+    }
+
+    /// Image of a whole range, as (source piece, destination start) pairs covering the range in order.
+    fn pieces(&self, range: &RangeInclusive<T>) -> Vec<Segment<T>> {
+        let (mut lo, end) = (*range.start(), *range.end());
+        let mut rv = Vec::new();
+        if end < lo {
+            return rv;
+        }
+
+        let mut idx = self.segments.partition_point(|s| *s.src.end() < lo);
+        while let Some(seg) = self.segments.get(idx).filter(|s| *s.src.start() <= end) {
+            if lo < *seg.src.start() {
+                let gap_end = seg.src.start().checked_pred().unwrap();
+                rv.push(Segment { src: lo..=gap_end, dst_start: lo });
+            }
+            let a = max(lo, *seg.src.start());
+            let b = min(end, *seg.src.end());
+            rv.push(Segment { src: a..=b, dst_start: seg.map(a) });
+
+            if end <= *seg.src.end() {
+                return rv;
+            }
+            lo = seg.src.end().checked_succ().unwrap();
+            idx += 1;
+        }
+        rv.push(Segment { src: lo..=end, dst_start: lo });
+        rv
+    }
+
+    pub fn map_range(&self, range: &RangeInclusive<T>) -> RangeSet<T> {
+        self.pieces(range).iter().map(|p| p.dst()).collect()
+    }
+
+    pub fn map_set(&self, set: &RangeSet<T>) -> RangeSet<T> {
+        set.iter().flat_map(|r| self.pieces(r)).map(|p| p.dst()).collect()
+    }
+
+    /// The map equivalent to applying `self` and then `next`.
+    pub fn then(&self, next: &IntervalMap<T>) -> IntervalMap<T> {
+        let mut composed = Vec::new();
+
+        for seg in &self.segments {
+            for piece in next.pieces(&seg.dst()) {
+                let src_start = seg.unmap(*piece.src.start());
+                let src_end = seg.unmap(*piece.src.end());
+                composed.push(Segment {
+                    src: src_start..=src_end,
+                    dst_start: piece.dst_start,
+                });
+            }
+        }
+
+        // Points that `self` leaves alone go straight into `next`.
+        let mapped: RangeSet<T> = self.segments.iter().map(|s| s.src.clone()).collect();
+        for seg in &next.segments {
+            for r in RangeSet::from(seg.src.clone()).difference(&mapped).iter() {
+                composed.push(Segment {
+                    src: r.clone(),
+                    dst_start: seg.map(*r.start()),
+                });
+            }
+        }
+
+        IntervalMap::from_disjoint(composed)
+    }
+
+    /// The inverse map, or `None` if this map is not a bijection.
+    pub fn invert(&self) -> Option<IntervalMap<T>> {
+        let domain: RangeSet<T> = self.segments.iter().map(|s| s.src.clone()).collect();
+        let image: RangeSet<T> = self.segments.iter().map(|s| s.dst()).collect();
+        if domain != image {
+            return None;
+        }
+        // Overlapping destinations would merge in `image`, so compare total widths too.
+        let total: u64 = self.segments.iter().map(|s| T::span(*s.src.start(), *s.src.end())).sum();
+        if total != image.len() {
+            return None;
+        }
+
+        let inverted = self
+            .segments
+            .iter()
+            .map(|s| Segment {
+                src: s.dst(),
+                dst_start: *s.src.start(),
+            })
+            .collect();
+        Some(IntervalMap::from_disjoint(inverted))
+    }
+
+    /// Build from segments with non-overlapping sources, dropping identity pieces and merging neighbours.
+    fn from_disjoint(mut segments: Vec<Segment<T>>) -> IntervalMap<T> {
+        segments.sort_unstable_by_key(|s| *s.src.start());
+
+        let mut rv: Vec<Segment<T>> = Vec::with_capacity(segments.len());
+        for seg in segments {
+            if seg.dst_start == *seg.src.start() {
+                continue;
+            }
+            if let Some(last) = rv.last_mut() {
+                let contiguous = last.src.end().checked_succ() == Some(*seg.src.start())
+                    && last.map(*last.src.end()).checked_succ() == Some(seg.dst_start);
+                if contiguous {
+                    last.src = *last.src.start()..=*seg.src.end();
+                    continue;
+                }
+            }
+            rv.push(seg);
+        }
+        IntervalMap { segments: rv }
+    }
+}
+
+impl<T: MapBound> FromIterator<(RangeInclusive<T>, T)> for IntervalMap<T> {
+    fn from_iter<I: IntoIterator<Item = (RangeInclusive<T>, T)>>(iter: I) -> Self {
+        let mut rv = IntervalMap::new();
+        for (src, dst_start) in iter {
+            rv.insert(src, dst_start);
+        }
+        rv
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    fn seed_to_soil() -> IntervalMap<u64> {
+        [(98..=99, 50), (50..=97, 52)].into_iter().collect()
+    }
+
+    fn soil_to_fertilizer() -> IntervalMap<u64> {
+        [(15..=51, 0), (52..=53, 37), (0..=14, 39)].into_iter().collect()
+    }
+
+    #[test_case(79, 81; "shifted up")]
+    #[test_case(98, 50; "shifted down")]
+    #[test_case(13, 13; "unmapped")]
+    #[test_case(100, 100; "past the end")]
+    pub fn test_get(x: u64, exp: u64) {
+        assert_eq!(exp, seed_to_soil().get(x));
+    }
+
+    #[test]
+    pub fn test_insert_keeps_first_mapping() {
+        let mut m: IntervalMap<u64> = IntervalMap::new();
+        m.insert(10..=19, 110);
+        m.insert(5..=24, 205);
+        assert_eq!(205, m.get(5));
+        assert_eq!(110, m.get(10));
+        assert_eq!(119, m.get(19));
+        assert_eq!(220, m.get(20));
+    }
+
+    #[test]
+    pub fn test_map_range() {
+        let m = seed_to_soil();
+        assert_eq!(&[0..=49, 52..=53], m.map_range(&(0..=51)).ranges());
+        assert_eq!(&[50..=51, 100..=105], m.map_range(&(98..=105)).ranges());
+    }
+
+    #[test]
+    pub fn test_then_matches_sequential_lookup() {
+        let a = seed_to_soil();
+        let b = soil_to_fertilizer();
+        let composed = a.then(&b);
+        for x in 0..=120 {
+            assert_eq!(b.get(a.get(x)), composed.get(x), "mismatch at {}", x);
+        }
+        let seeds = RangeSet::from(0..=120);
+        assert_eq!(b.map_set(&a.map_set(&seeds)), composed.map_set(&seeds));
+    }
+
+    #[test]
+    pub fn test_then_drops_identity() {
+        let swap: IntervalMap<i64> = [(0..=9, 100), (100..=109, 0)].into_iter().collect();
+        assert_eq!(0, swap.then(&swap).segments().count());
+        assert_eq!(0, swap.then(&swap.invert().unwrap()).segments().count());
+    }
+
+    #[test]
+    pub fn test_invert() {
+        let m = seed_to_soil();
+        let inv = m.invert().unwrap();
+        for x in 0..=120 {
+            assert_eq!(x, inv.get(m.get(x)));
+        }
+    }
+
+    #[test]
+    pub fn test_invert_not_bijective() {
+        let m: IntervalMap<u64> = [(0..=4, 10)].into_iter().collect();
+        assert_eq!(None, m.invert());
+    }
+}
@@ -0,0 +1,16 @@
+pub mod codegen;
+pub mod cycle;
+pub mod differential;
+pub mod export;
+pub mod graph_export;
+pub mod grid;
+pub mod inputs;
+pub mod interval_map;
+pub mod memory;
+pub mod ocr;
+pub mod params;
+pub mod ranges;
+pub mod registry;
+pub mod report;
+pub mod series;
+pub mod visualize;
@@ -0,0 +1,114 @@
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// A global allocator over [`System`] that tracks the high-water mark of live heap bytes.
+///
+/// ```ignore
+/// #[global_allocator]
+/// static ALLOC: PeakAlloc = PeakAlloc::new();
+///
+/// let baseline = ALLOC.reset_peak();
+/// solve(input);
+/// let used = ALLOC.peak() - baseline;
+/// ```
+pub struct PeakAlloc {
+    current: AtomicUsize,
+    peak: AtomicUsize,
+}
+
+impl PeakAlloc {
+    pub const fn new() -> PeakAlloc {
+        PeakAlloc {
+            current: AtomicUsize::new(0),
+            peak: AtomicUsize::new(0),
+        }
+    }
+
+    pub fn current(&self) -> usize {
+        self.current.load(Ordering::Relaxed)
+    }
+
+    pub fn peak(&self) -> usize {
+        self.peak.load(Ordering::Relaxed)
+    }
+
+    /// Start a new measurement, returning the bytes currently live.
+    pub fn reset_peak(&self) -> usize {
+        let current = self.current();
+        self.peak.store(current, Ordering::Relaxed);
+        current
+    }
+
+    fn grow(&self, bytes: usize) {
+        let current = self.current.fetch_add(bytes, Ordering::Relaxed) + bytes;
+        self.peak.fetch_max(current, Ordering::Relaxed);
+    }
+
+    fn shrink(&self, bytes: usize) {
+        self.current.fetch_sub(bytes, Ordering::Relaxed);
+    }
+}
+
+impl Default for PeakAlloc {
+    fn default() -> Self {
+        PeakAlloc::new()
+    }
+}
+
+unsafe impl GlobalAlloc for PeakAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            self.grow(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            self.grow(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        self.shrink(layout.size());
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            if new_size > layout.size() {
+                self.grow(new_size - layout.size());
+            } else {
+                self.shrink(layout.size() - new_size);
+            }
+        }
+        new_ptr
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn test_peak() {
+        let alloc = PeakAlloc::new();
+        let small = Layout::from_size_align(16, 8).unwrap();
+        let big = Layout::from_size_align(1024, 8).unwrap();
+        unsafe {
+            let a = alloc.alloc(small);
+            assert_eq!(16, alloc.reset_peak());
+            let b = alloc.alloc_zeroed(big);
+            let b = alloc.realloc(b, big, 2048);
+            alloc.dealloc(b, Layout::from_size_align(2048, 8).unwrap());
+            assert_eq!(16, alloc.current());
+            assert_eq!(16 + 2048, alloc.peak());
+            alloc.dealloc(a, small);
+        }
+        assert_eq!(0, alloc.current());
+    }
+}
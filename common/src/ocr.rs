@@ -0,0 +1,226 @@
+//! Reading the block letters some puzzles draw instead of printing an answer.
+//!
+//! Screens are rows of `#` for lit pixels and `.` (or space) for dark ones. Six rows are read with the usual 4x6 font
+//! and ten rows with the 6x10 one. Letters are split on fully dark columns, so spacing between them doesn't matter.
+
+use std::fmt;
+
+const SMALL: &[(char, &str)] = &[
+    ('A', ".##.\n#..#\n#..#\n####\n#..#\n#..#"),
+    ('B', "###.\n#..#\n###.\n#..#\n#..#\n###."),
+    ('C', ".##.\n#..#\n#...\n#...\n#..#\n.##."),
+    ('E', "####\n#...\n###.\n#...\n#...\n####"),
+    ('F', "####\n#...\n###.\n#...\n#...\n#..."),
+    ('G', ".##.\n#..#\n#...\n#.##\n#..#\n.###"),
+    ('H', "#..#\n#..#\n####\n#..#\n#..#\n#..#"),
+    ('I', ".###\n..#.\n..#.\n..#.\n..#.\n.###"),
+    ('J', "..##\n...#\n...#\n...#\n#..#\n.##."),
+    ('K', "#..#\n#.#.\n##..\n#.#.\n#.#.\n#..#"),
+    ('L', "#...\n#...\n#...\n#...\n#...\n####"),
+    ('O', ".##.\n#..#\n#..#\n#..#\n#..#\n.##."),
+    ('P', "###.\n#..#\n#..#\n###.\n#...\n#..."),
+    ('R', "###.\n#..#\n#..#\n###.\n#.#.\n#..#"),
+    ('S', ".###\n#...\n#...\n.##.\n...#\n###."),
+    ('U', "#..#\n#..#\n#..#\n#..#\n#..#\n.##."),
+    ('Y', "#...#\n#...#\n.#.#.\n..#..\n..#..\n..#.."),
+    ('Z', "####\n...#\n..#.\n.#..\n#...\n####"),
+];
+
+const LARGE: &[(char, &str)] = &[
+    (
+        'A',
+        "..##..\n.#..#.\n#....#\n#....#\n#....#\n######\n#....#\n#....#\n#....#\n#....#",
+    ),
+    (
+        'B',
+        "#####.\n#....#\n#....#\n#....#\n#####.\n#....#\n#....#\n#....#\n#....#\n#####.",
+    ),
+    (
+        'C',
+        ".####.\n#....#\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#....#\n.####.",
+    ),
+    (
+        'E',
+        "######\n#.....\n#.....\n#.....\n#####.\n#.....\n#.....\n#.....\n#.....\n######",
+    ),
+    (
+        'F',
+        "######\n#.....\n#.....\n#.....\n#####.\n#.....\n#.....\n#.....\n#.....\n#.....",
+    ),
+    (
+        'G',
+        ".####.\n#....#\n#.....\n#.....\n#.....\n#..###\n#....#\n#....#\n#...##\n.###.#",
+    ),
+    (
+        'H',
+        "#....#\n#....#\n#....#\n#....#\n######\n#....#\n#....#\n#....#\n#....#\n#....#",
+    ),
+    (
+        'J',
+        "...###\n....#.\n....#.\n....#.\n....#.\n....#.\n....#.\n#...#.\n#...#.\n.###..",
+    ),
+    (
+        'K',
+        "#....#\n#...#.\n#..#..\n#.#...\n##....\n##....\n#.#...\n#..#..\n#...#.\n#....#",
+    ),
+    (
+        'L',
+        "#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n######",
+    ),
+    (
+        'N',
+        "#....#\n##...#\n##...#\n#.#..#\n#.#..#\n#..#.#\n#..#.#\n#...##\n#...##\n#....#",
+    ),
+    (
+        'P',
+        "#####.\n#....#\n#....#\n#....#\n#####.\n#.....\n#.....\n#.....\n#.....\n#.....",
+    ),
+    (
+        'R',
+        "#####.\n#....#\n#....#\n#....#\n#####.\n#..#..\n#...#.\n#...#.\n#....#\n#....#",
+    ),
+    (
+        'X',
+        "#....#\n#....#\n.#..#.\n.#..#.\n..##..\n..##..\n.#..#.\n.#..#.\n#....#\n#....#",
+    ),
+    (
+        'Z',
+        "######\n.....#\n.....#\n....#.\n...#..\n..#...\n.#....\n#.....\n#.....\n######",
+    ),
+];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OcrError {
+    /// Neither font is this many rows tall.
+    Height(usize),
+    /// The `index`th letter, starting at screen column `column`, isn't in the font.
+    UnknownGlyph { index: usize, column: usize, glyph: String },
+}
+
+impl fmt::Display for OcrError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OcrError::Height(rows) => write!(f, "no font is {} rows tall, expected 6 or 10", rows),
+            OcrError::UnknownGlyph { index, column, glyph } => {
+                write!(f, "unknown glyph {} at column {}:\n{}", index, column, glyph)
+            }
+        }
+    }
+}
+
+impl std::error::Error for OcrError {}
+
+/// Reads the letters on `screen`. Blank lines before and after are ignored.
+pub fn decode(screen: &str) -> Result<String, OcrError> {
+    let rows: Vec<Vec<bool>> = screen
+        .trim_matches('\n')
+        .lines()
+        .map(|line| line.chars().map(|c| c == '#').collect())
+        .collect();
+    let font = match rows.len() {
+        6 => SMALL,
+        10 => LARGE,
+        n => return Err(OcrError::Height(n)),
+    };
+
+    let width = rows.iter().map(Vec::len).max().unwrap_or(0);
+    let lit = |c: usize| rows.iter().any(|row| row.get(c).copied().unwrap_or(false));
+
+    let mut letters = String::new();
+    let mut c = 0;
+    while c < width {
+        if !lit(c) {
+            c += 1;
+            continue;
+        }
+        let start = c;
+        while c < width && lit(c) {
+            c += 1;
+        }
+        let glyph = render(&rows, start..c);
+        match font.iter().find(|(_, pattern)| trim(pattern) == glyph) {
+            Some(&(letter, _)) => letters.push(letter),
+            None => {
+                return Err(OcrError::UnknownGlyph {
+                    index: letters.len(),
+                    column: start,
+                    glyph,
+                })
+            }
+        }
+    }
+    Ok(letters)
+}
+
+fn render(rows: &[Vec<bool>], columns: std::ops::Range<usize>) -> String {
+    rows.iter()
+        .map(|row| {
+            columns
+                .clone()
+                .map(|c| if row.get(c).copied().unwrap_or(false) { '#' } else { '.' })
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// A font pattern without its dark edge columns, the way [`decode`] cuts letters out of a screen.
+fn trim(pattern: &str) -> String {
+    let rows: Vec<Vec<bool>> = pattern.lines().map(|l| l.chars().map(|c| c == '#').collect()).collect();
+    let width = rows.iter().map(Vec::len).max().unwrap_or(0);
+    let lit = |c: usize| rows.iter().any(|row| row.get(c).copied().unwrap_or(false));
+    let start = (0..width).find(|&c| lit(c)).unwrap_or(0);
+    let end = (0..width).rfind(|&c| lit(c)).map_or(start, |c| c + 1);
+    render(&rows, start..end)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Lays font letters out side by side with `gap` dark columns after each.
+    fn screen(font: &[(char, &str)], text: &str, gap: usize) -> String {
+        let height = font[0].1.lines().count();
+        let mut rows = vec![String::new(); height];
+        for ch in text.chars() {
+            let (_, pattern) = font.iter().find(|(c, _)| *c == ch).unwrap();
+            for (row, line) in rows.iter_mut().zip(pattern.lines()) {
+                row.push_str(line);
+                row.push_str(&".".repeat(gap));
+            }
+        }
+        rows.join("\n")
+    }
+
+    #[test]
+    pub fn test_small() {
+        let letters: String = SMALL.iter().map(|(c, _)| c).collect();
+        assert_eq!(Ok(letters.clone()), decode(&screen(SMALL, &letters, 1)));
+        assert_eq!(Ok("HI".to_string()), decode(&format!("\n{}\n", screen(SMALL, "HI", 3))));
+    }
+
+    #[test]
+    pub fn test_large() {
+        let letters: String = LARGE.iter().map(|(c, _)| c).collect();
+        assert_eq!(Ok(letters.clone()), decode(&screen(LARGE, &letters, 2)));
+    }
+
+    #[test]
+    pub fn test_unknown() {
+        let mut text = screen(SMALL, "AB", 1);
+        text = text
+            .lines()
+            .map(|l| format!("{}#....", l))
+            .collect::<Vec<_>>()
+            .join("\n");
+        assert_eq!(
+            Err(OcrError::UnknownGlyph {
+                index: 2,
+                column: 10,
+                glyph: "#\n#\n#\n#\n#\n#".to_string(),
+            }),
+            decode(&text)
+        );
+        assert_eq!(Err(OcrError::Height(3)), decode("#\n#\n#"));
+    }
+}
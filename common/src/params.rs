@@ -0,0 +1,153 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+thread_local! {
+    static OVERRIDES: RefCell<HashMap<String, String>> = RefCell::new(HashMap::new());
+}
+
+/// Puzzle parameters that differ between the examples and the real input, declared with [`params!`](crate::params!).
+pub trait Params: Default {
+    /// Every field name, in declaration order.
+    const KEYS: &'static [&'static str];
+
+    /// Set one field from its string form.
+    fn apply(&mut self, key: &str, value: &str) -> Result<(), String>;
+
+    /// Defaults with this thread's overrides applied. Overrides are thread-local, so read this before fanning out.
+    /// Overrides for keys this day doesn't declare are left for other days; a value that doesn't parse panics.
+    fn current() -> Self {
+        let mut params = Self::default();
+        OVERRIDES.with_borrow(|overrides| {
+            for (key, value) in overrides.iter().filter(|(key, _)| Self::KEYS.contains(&key.as_str())) {
+                if let Err(e) = params.apply(key, value) {
+                    panic!("Bad parameter {}={}: {}", key, value, e);
+                }
+            }
+        });
+        params
+    }
+}
+
+/// Replace this thread's overrides.
+pub fn set_overrides(overrides: impl IntoIterator<Item = (String, String)>) {
+    OVERRIDES.with_borrow_mut(|o| *o = overrides.into_iter().collect());
+}
+
+/// Run `f` with the given overrides, restoring the previous ones afterwards.
+pub fn with_overrides<R>(overrides: impl IntoIterator<Item = (String, String)>, f: impl FnOnce() -> R) -> R {
+    let previous = OVERRIDES.with_borrow_mut(|o| std::mem::replace(o, overrides.into_iter().collect()));
+    let rv = f();
+    OVERRIDES.with_borrow_mut(|o| *o = previous);
+    rv
+}
+
+/// Parse a `key=value` command-line override.
+pub fn parse_override(s: &str) -> Result<(String, String), String> {
+    let (key, value) = s.split_once('=').ok_or_else(|| format!("expected key=value, got {:?}", s))?;
+    Ok((key.trim().to_string(), value.trim().to_string()))
+}
+
+/// Declare a `Params` struct with typed fields and their defaults for the real input.
+///
+/// ```ignore
+/// params! {
+///     pub struct Params {
+///         target_y: i64 = 2_000_000,
+///     }
+/// }
+/// ```
+#[macro_export]
+macro_rules! params {
+    ($vis:vis struct $name:ident { $($(#[$meta:meta])* $field:ident : $ty:ty = $default:expr),* $(,)? }) => {
+        #[derive(Debug, Clone, PartialEq)]
+        $vis struct $name {
+            $($(#[$meta])* pub $field: $ty,)*
+        }
+
+        impl Default for $name {
+            fn default() -> Self {
+                $name { $($field: $default,)* }
+            }
+        }
+
+        impl $crate::params::Params for $name {
+            const KEYS: &'static [&'static str] = &[$(stringify!($field)),*];
+
+            fn apply(&mut self, key: &str, value: &str) -> Result<(), String> {
+                match key {
+                    $(stringify!($field) => {
+                        self.$field = value.parse().map_err(|e| format!("{}", e))?;
+                    })*
+                    _ => return Err(format!("unknown parameter {:?}", key)),
+                }
+                Ok(())
+            }
+        }
+    };
+}
+
+/// Like `#[aoc_case]`, but runs every registered solver with the given parameter overrides.
+///
+/// ```ignore
+/// param_case!(aoc_test_example, EXAMPLE, { target_y: 10, max_coord: 20 }, 26, 56000011);
+/// ```
+#[macro_export]
+macro_rules! param_case {
+    ($test:ident, $input:expr, { $($key:ident : $value:expr),* $(,)? }, $p1:expr $(, $p2:expr)?) => {
+        #[test]
+        fn $test() {
+            let overrides = vec![$((stringify!($key).to_string(), $value.to_string())),*];
+            $crate::params::with_overrides(overrides, || {
+                for (label, solver) in super::_gen_lists::P1_LABELS.iter().zip(super::_gen_lists::P1_SOLUTIONS) {
+                    assert_eq!($p1, solver($input), "Part 1 Test failed solution: {}", label);
+                }
+                $(
+                    for (label, solver) in super::_gen_lists::P2_LABELS.iter().zip(super::_gen_lists::P2_SOLUTIONS) {
+                        assert_eq!($p2, solver($input), "Part 2 Test failed solution: {}", label);
+                    }
+                )?
+            });
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    crate::params! {
+        struct Example {
+            target_y: i64 = 2_000_000,
+            scale: usize = 999_999,
+        }
+    }
+
+    #[test]
+    pub fn test_defaults() {
+        assert_eq!(Example { target_y: 2_000_000, scale: 999_999 }, Example::current());
+    }
+
+    #[test]
+    pub fn test_overrides() {
+        let overrides = vec![parse_override("target_y=10").unwrap()];
+        let params = with_overrides(overrides, Example::current);
+        assert_eq!(Example { target_y: 10, scale: 999_999 }, params);
+        assert_eq!(Example::default(), Example::current());
+    }
+
+    #[test]
+    pub fn test_other_days_overrides() {
+        let overrides = vec![parse_override("target_y=10").unwrap(), parse_override("unrelated=1").unwrap()];
+        let params = with_overrides(overrides, Example::current);
+        assert_eq!(Example { target_y: 10, scale: 999_999 }, params);
+        assert_eq!(&["target_y", "scale"], Example::KEYS);
+    }
+
+    #[test]
+    pub fn test_bad_override() {
+        let mut params = Example::default();
+        assert!(params.apply("target_y", "ten").is_err());
+        assert!(params.apply("target_x", "10").is_err());
+        assert!(parse_override("target_y").is_err());
+    }
+}
@@ -0,0 +1,356 @@
+use std::cmp::{max, min};
+use std::fmt::Debug;
+use std::ops::RangeInclusive;
+
+/// Integer types that can bound the ranges in a `RangeSet`.
+pub trait RangeBound: Copy + Ord + Debug {
+    fn checked_succ(self) -> Option<Self>;
+    fn checked_pred(self) -> Option<Self>;
+    /// Number of points in `lo..=hi`, saturating at `u64::MAX`.
+    fn span(lo: Self, hi: Self) -> u64;
+}
+
+macro_rules! impl_range_bound {
+    ($($t:ty),*) => {
+        $(
+            impl RangeBound for $t {
+                fn checked_succ(self) -> Option<Self> {
+                    self.checked_add(1)
+                }
+
+                fn checked_pred(self) -> Option<Self> {
+                    self.checked_sub(1)
+                }
+
+                fn span(lo: Self, hi: Self) -> u64 {
+                    if hi < lo {
+                        return 0;
+                    }
+                    let width = (hi as i128) - (lo as i128) + 1;
+                    min(width, u64::MAX as i128) as u64
+                }
+            }
+        )*
+    };
+}
+
+impl_range_bound!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+
+/// A set of integers stored as sorted, disjoint, non-adjacent inclusive ranges.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RangeSet<T> {
+    ranges: Vec<RangeInclusive<T>>,
+}
+
+impl<T> Default for RangeSet<T> {
+    fn default() -> Self {
+        RangeSet { ranges: Vec::new() }
+    }
+}
+
+impl<T: RangeBound> RangeSet<T> {
+    pub fn new() -> RangeSet<T> {
+        RangeSet::default()
+    }
+
+    pub fn ranges(&self) -> &[RangeInclusive<T>] {
+        &self.ranges
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, RangeInclusive<T>> {
+        self.ranges.iter()
+    }
+
+    pub fn ranges_count(&self) -> usize {
+        self.ranges.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// Number of points covered by the set.
+    pub fn len(&self) -> u64 {
+        self.ranges
+            .iter()
+            .map(|r| T::span(*r.start(), *r.end()))
+            .fold(0u64, |a, b| a.saturating_add(b))
+    }
+
+    pub fn min(&self) -> Option<T> {
+        self.ranges.first().map(|r| *r.start())
+    }
+
+    pub fn max(&self) -> Option<T> {
+        self.ranges.last().map(|r| *r.end())
+    }
+
+    pub fn contains(&self, point: T) -> bool {
+        let idx = self.ranges.partition_point(|r| *r.end() < point);
+        idx < self.ranges.len() && *self.ranges[idx].start() <= point
+    }
+
+    pub fn insert(&mut self, range: &RangeInclusive<T>) {
+        let (start, end) = (*range.start(), *range.end());
+        if end < start {
+            return;
+        }
+
+        // Everything in lo..hi either overlaps or touches the new range.
+        let lo = self
+            .ranges
+            .partition_point(|r| r.end().checked_succ().is_some_and(|n| n < start));
+        let hi = self
+            .ranges
+            .partition_point(|r| end.checked_succ().is_none_or(|n| *r.start() <= n));
+
+        let mut merged = start..=end;
+        if lo < hi {
+            merged = min(start, *self.ranges[lo].start())..=max(end, *self.ranges[hi - 1].end());
+        }
+        self.ranges.splice(lo..hi, [merged]);
+    }
+
+    pub fn insert_point(&mut self, point: T) {
+        self.insert(&(point..=point));
+    }
+
+    pub fn remove(&mut self, range: &RangeInclusive<T>) {
+        let (start, end) = (*range.start(), *range.end());
+        if end < start {
+            return;
+        }
+
+        let lo = self.ranges.partition_point(|r| *r.end() < start);
+        let hi = self.ranges.partition_point(|r| *r.start() <= end);
+        if lo >= hi {
+            return;
+        }
+
+        let mut leftovers = Vec::with_capacity(2);
+        let first_start = *self.ranges[lo].start();
+        let last_end = *self.ranges[hi - 1].end();
+        if first_start < start {
+            leftovers.push(first_start..=start.checked_pred().unwrap());
+        }
+        if end < last_end {
+            leftovers.push(end.checked_succ().unwrap()..=last_end);
+        }
+        self.ranges.splice(lo..hi, leftovers);
+    }
+
+    pub fn remove_point(&mut self, point: T) {
+        self.remove(&(point..=point));
+    }
+
+    pub fn union(&self, other: &RangeSet<T>) -> RangeSet<T> {
+        let (a, b) = (&self.ranges, &other.ranges);
+        let mut rv = RangeSet::new();
+        let (mut i, mut j) = (0, 0);
+        while i < a.len() || j < b.len() {
+            let next = if j >= b.len() || (i < a.len() && a[i].start() <= b[j].start()) {
+                i += 1;
+                &a[i - 1]
+            } else {
+                j += 1;
+                &b[j - 1]
+            };
+            rv.push_sorted(next.clone());
+        }
+        rv
+    }
+
+    pub fn difference(&self, other: &RangeSet<T>) -> RangeSet<T> {
+        let sub = &other.ranges;
+        let mut rv = Vec::with_capacity(self.ranges.len());
+        let mut j = 0;
+
+        for r in &self.ranges {
+            let (mut lo, hi) = (*r.start(), *r.end());
+            while j < sub.len() && *sub[j].end() < lo {
+                j += 1;
+            }
+
+            let mut remaining = true;
+            let mut k = j;
+            while remaining && k < sub.len() && *sub[k].start() <= hi {
+                if lo < *sub[k].start() {
+                    rv.push(lo..=sub[k].start().checked_pred().unwrap());
+                }
+                match sub[k].end().checked_succ() {
+                    Some(next) if *sub[k].end() < hi => lo = next,
+                    _ => remaining = false,
+                }
+                k += 1;
+            }
+            if remaining {
+                rv.push(lo..=hi);
+            }
+        }
+
+        RangeSet { ranges: rv }
+    }
+
+    pub fn intersection(&self, other: &RangeSet<T>) -> RangeSet<T> {
+        let (a, b) = (&self.ranges, &other.ranges);
+        let mut rv = Vec::new();
+        let (mut i, mut j) = (0, 0);
+        while i < a.len() && j < b.len() {
+            let lo = max(*a[i].start(), *b[j].start());
+            let hi = min(*a[i].end(), *b[j].end());
+            if lo <= hi {
+                rv.push(lo..=hi);
+            }
+            if a[i].end() < b[j].end() {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+        RangeSet { ranges: rv }
+    }
+
+    pub fn intersect_range(&self, range: &RangeInclusive<T>) -> RangeSet<T> {
+        self.intersection(&RangeSet::from(range.clone()))
+    }
+
+    /// Every point within `bounds` that is not in this set.
+    pub fn complement(&self, bounds: RangeInclusive<T>) -> RangeSet<T> {
+        RangeSet::from(bounds).difference(self)
+    }
+
+    /// Append a range whose start is no lower than any range already in the set.
+    fn push_sorted(&mut self, range: RangeInclusive<T>) {
+        if range.is_empty() {
+            return;
+        }
+        match self.ranges.last_mut() {
+            Some(last) if last.end().checked_succ().is_none_or(|n| *range.start() <= n) => {
+                if last.end() < range.end() {
+                    *last = *last.start()..=*range.end();
+                }
+            }
+            _ => self.ranges.push(range),
+        }
+    }
+}
+
+impl<T: RangeBound> From<RangeInclusive<T>> for RangeSet<T> {
+    fn from(range: RangeInclusive<T>) -> Self {
+        let mut rv = RangeSet::new();
+        rv.insert(&range);
+        rv
+    }
+}
+
+impl<T: RangeBound> FromIterator<RangeInclusive<T>> for RangeSet<T> {
+    fn from_iter<I: IntoIterator<Item = RangeInclusive<T>>>(iter: I) -> Self {
+        let mut items: Vec<RangeInclusive<T>> = iter.into_iter().collect();
+        items.sort_unstable_by_key(|r| *r.start());
+
+        let mut rv = RangeSet::new();
+        for r in items {
+            rv.push_sorted(r);
+        }
+        rv
+    }
+}
+
+impl<T: RangeBound> Extend<RangeInclusive<T>> for RangeSet<T> {
+    fn extend<I: IntoIterator<Item = RangeInclusive<T>>>(&mut self, iter: I) {
+        for r in iter {
+            self.insert(&r);
+        }
+    }
+}
+
+impl<'a, T: RangeBound> IntoIterator for &'a RangeSet<T> {
+    type Item = &'a RangeInclusive<T>;
+    type IntoIter = std::slice::Iter<'a, RangeInclusive<T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.ranges.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    fn set(ranges: &[RangeInclusive<i64>]) -> RangeSet<i64> {
+        ranges.iter().cloned().collect()
+    }
+
+    #[test_case(&[1..=3, 5..=7], &[1..=3, 5..=7]; "disjoint")]
+    #[test_case(&[1..=3, 4..=7], &[1..=7]; "adjacent")]
+    #[test_case(&[5..=7, 1..=3, 2..=6], &[1..=7]; "bridging")]
+    #[test_case(&[10..=12, 1..=2, 5..=5], &[1..=2, 5..=5, 10..=12]; "unsorted")]
+    #[test_case(&[1..=10, 3..=4], &[1..=10]; "contained")]
+    pub fn test_insert(input: &[RangeInclusive<i64>], exp: &[RangeInclusive<i64>]) {
+        assert_eq!(exp, set(input).ranges());
+    }
+
+    #[test_case(&[1..=10], 4..=6, &[1..=3, 7..=10]; "split in the middle")]
+    #[test_case(&[5..=10], 7..=12, &[5..=6]; "overlap second-larger")]
+    #[test_case(&[3..=7], 5..=10, &[3..=4]; "overlap first-larger")]
+    #[test_case(&[7..=14], 3..=122, &[]; "overlap second-encompassing")]
+    #[test_case(&[7..=14], 22..=122, &[7..=14]; "no intersection")]
+    #[test_case(&[1..=3, 5..=7, 9..=11], 2..=10, &[1..=1, 11..=11]; "spanning several")]
+    pub fn test_remove(input: &[RangeInclusive<i64>], sub: RangeInclusive<i64>, exp: &[RangeInclusive<i64>]) {
+        let mut s = set(input);
+        s.remove(&sub);
+        assert_eq!(exp, s.ranges());
+    }
+
+    #[test_case(&[1..=10, 20..=30], &[3..=4, 8..=22, 29..=40], &[1..=2, 5..=7, 23..=28]; "several splits")]
+    #[test_case(&[1..=10], &[], &[1..=10]; "nothing removed")]
+    #[test_case(&[1..=10], &[0..=11], &[]; "everything removed")]
+    pub fn test_difference(a: &[RangeInclusive<i64>], b: &[RangeInclusive<i64>], exp: &[RangeInclusive<i64>]) {
+        assert_eq!(exp, set(a).difference(&set(b)).ranges());
+    }
+
+    #[test_case(&[5..=10], &[7..=12], &[7..=10]; "overlap second-larger")]
+    #[test_case(&[3..=7], &[5..=10], &[5..=7]; "overlap first-larger")]
+    #[test_case(&[3..=12], &[5..=10], &[5..=10]; "overlap first-encompassing")]
+    #[test_case(&[7..=14], &[3..=122], &[7..=14]; "overlap second-encompassing")]
+    #[test_case(&[7..=14], &[22..=122], &[]; "no intersection")]
+    #[test_case(&[0..=5, 8..=9], &[1..=2, 4..=8], &[1..=2, 4..=5, 8..=8]; "interleaved")]
+    pub fn test_intersection(a: &[RangeInclusive<i64>], b: &[RangeInclusive<i64>], exp: &[RangeInclusive<i64>]) {
+        assert_eq!(exp, set(a).intersection(&set(b)).ranges());
+    }
+
+    #[test_case(&[1..=3], &[4..=6, 10..=11], &[1..=6, 10..=11]; "adjacent")]
+    #[test_case(&[1..=5, 20..=25], &[3..=21], &[1..=25]; "bridging")]
+    pub fn test_union(a: &[RangeInclusive<i64>], b: &[RangeInclusive<i64>], exp: &[RangeInclusive<i64>]) {
+        assert_eq!(exp, set(a).union(&set(b)).ranges());
+    }
+
+    #[test]
+    pub fn test_complement() {
+        let s = set(&[-5..=-2, 3..=4, 8..=30]);
+        assert_eq!(&[0..=2, 5..=7], s.complement(0..=20).ranges());
+        assert_eq!(&[-10..=-6, -1..=2, 5..=7], s.complement(-10..=10).ranges());
+    }
+
+    #[test]
+    pub fn test_contains_and_len() {
+        let s = set(&[-5..=-2, 3..=4, 8..=30]);
+        assert!(s.contains(-5));
+        assert!(s.contains(4));
+        assert!(!s.contains(5));
+        assert!(!s.contains(31));
+        assert_eq!(4 + 2 + 23, s.len());
+    }
+
+    #[test]
+    pub fn test_extremes() {
+        let mut s: RangeSet<u8> = RangeSet::from(250..=255);
+        s.insert(&(0..=249));
+        assert_eq!(&[0..=255], s.ranges());
+        assert_eq!(256, s.len());
+        s.remove_point(255);
+        s.remove_point(0);
+        assert_eq!(&[1..=254], s.ranges());
+    }
+}
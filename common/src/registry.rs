@@ -0,0 +1,159 @@
+use std::fmt::Display;
+use std::time::{Duration, Instant};
+
+type TimedFn = Box<dyn Fn(&str) -> Timing + Send + Sync>;
+
+/// One solver's answer, and how long it took.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Timing {
+    pub answer: String,
+    /// Time spent in the generator, if the solver has a separate one. Otherwise it is included in `solver`.
+    pub generator: Option<Duration>,
+    pub solver: Duration,
+}
+
+impl Timing {
+    /// Time a generator and solver separately.
+    pub fn split<'a, G, T: Display>(input: &'a str, gen: impl FnOnce(&'a str) -> G, solve: impl FnOnce(G) -> T) -> Timing {
+        let start = Instant::now();
+        let data = gen(input);
+        let generator = start.elapsed();
+        let start = Instant::now();
+        let answer = solve(data);
+        let solver = start.elapsed();
+        Timing {
+            answer: answer.to_string(),
+            generator: Some(generator),
+            solver,
+        }
+    }
+
+    pub fn total(&self) -> Duration {
+        self.generator.unwrap_or_default() + self.solver
+    }
+}
+
+/// One registered implementation of a part, with its answer rendered as a string.
+pub struct Solver {
+    pub label: &'static str,
+    func: TimedFn,
+}
+
+impl Solver {
+    pub fn new<T: Display + 'static>(label: &'static str, func: fn(&str) -> T) -> Solver {
+        Solver {
+            label,
+            func: Box::new(move |input| {
+                let start = Instant::now();
+                let answer = func(input);
+                let solver = start.elapsed();
+                Timing {
+                    answer: answer.to_string(),
+                    generator: None,
+                    solver,
+                }
+            }),
+        }
+    }
+
+    pub fn run(&self, input: &str) -> String {
+        self.time(input).answer
+    }
+
+    pub fn time(&self, input: &str) -> Timing {
+        (self.func)(input)
+    }
+
+    /// True if `slug` names this solver, either as the whole label or as one half of a `gen / solver` pair.
+    pub fn matches(&self, slug: &str) -> bool {
+        self.label == slug || self.label.split(" / ").any(|part| part == slug)
+    }
+}
+
+/// Everything registered for one `#[aoc(year, dayN)]` module.
+pub struct Day {
+    pub year: u32,
+    pub day: u32,
+    pub input: &'static str,
+    /// Keys of the day's `params!` struct, if it has one.
+    pub params: &'static [&'static str],
+    pub part1: Vec<Solver>,
+    pub part2: Vec<Solver>,
+}
+
+impl Day {
+    pub fn new(year: u32, day: u32, input: &'static str) -> Day {
+        Day {
+            year,
+            day,
+            input,
+            params: &[],
+            part1: Vec::new(),
+            part2: Vec::new(),
+        }
+    }
+
+    pub fn with_params(mut self, keys: &'static [&'static str]) -> Day {
+        self.params = keys;
+        self
+    }
+
+    pub fn with_part1<T: Display + 'static>(mut self, labels: &[&'static str], funcs: &[fn(&str) -> T]) -> Day {
+        self.part1.extend(labels.iter().zip(funcs).map(|(l, f)| Solver::new(l, *f)));
+        self
+    }
+
+    pub fn with_part2<T: Display + 'static>(mut self, labels: &[&'static str], funcs: &[fn(&str) -> T]) -> Day {
+        self.part2.extend(labels.iter().zip(funcs).map(|(l, f)| Solver::new(l, *f)));
+        self
+    }
+
+    /// Replace the composed `gen / solver` function for `label` with one that times the generator separately.
+    /// Labels the macro did not register are ignored.
+    pub fn with_split(mut self, part: u8, label: &str, func: impl Fn(&str) -> Timing + Send + Sync + 'static) -> Day {
+        let solvers = match part {
+            1 => &mut self.part1,
+            _ => &mut self.part2,
+        };
+        if let Some(s) = solvers.iter_mut().find(|s| s.label == label) {
+            s.func = Box::new(func);
+        }
+        self
+    }
+
+    pub fn part(&self, part: u8) -> &[Solver] {
+        match part {
+            1 => &self.part1,
+            2 => &self.part2,
+            _ => &[],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn test_solver_matches() {
+        let s = Solver::new("gen / draft_solvr", |input: &str| input.len());
+        assert!(s.matches("draft_solvr"));
+        assert!(s.matches("gen"));
+        assert!(s.matches("gen / draft_solvr"));
+        assert!(!s.matches("draft"));
+        assert_eq!("3", s.run("abc"));
+    }
+
+    #[test]
+    pub fn test_with_split() {
+        let day = Day::new(2022, 1, "")
+            .with_part1(&["gen / len", "chars"], &[|i: &str| i.len(), |i: &str| i.chars().count()])
+            .with_split(1, "gen / len", |input| Timing::split(input, |i| i.split(','), |parts| parts.count()))
+            .with_split(1, "missing", |input| Timing::split(input, |i| i, |i| i.len()));
+        let timings: Vec<Timing> = day.part(1).iter().map(|s| s.time("a,b")).collect();
+        assert_eq!("2", timings[0].answer);
+        assert!(timings[0].generator.is_some());
+        assert_eq!("3", timings[1].answer);
+        assert!(timings[1].generator.is_none());
+    }
+}
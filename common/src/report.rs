@@ -0,0 +1,155 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+/// Everything one `aoc run` measured, for comparing against later runs.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Report {
+    /// `git rev-parse HEAD` of the tree that was run, suffixed with `-dirty` if it had uncommitted changes.
+    pub commit: Option<String>,
+    /// Seconds since the Unix epoch.
+    pub timestamp: u64,
+    pub results: Vec<RunResult>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RunResult {
+    pub year: u32,
+    pub day: u32,
+    pub part: u8,
+    pub solver: String,
+    pub answer: String,
+    /// `None` for `#[solution]`s, whose parsing is included in `solver_ns`.
+    pub generator_ns: Option<u64>,
+    pub solver_ns: u64,
+    /// High-water mark of heap bytes allocated while solving, above what was live beforehand.
+    pub peak_memory_bytes: u64,
+}
+
+impl RunResult {
+    pub fn total_ns(&self) -> u64 {
+        self.generator_ns.unwrap_or(0) + self.solver_ns
+    }
+
+    fn key(&self) -> (u32, u32, u8, &str) {
+        (self.year, self.day, self.part, &self.solver)
+    }
+}
+
+impl Report {
+    pub fn load(path: &Path) -> io::Result<Report> {
+        let s = fs::read_to_string(path)?;
+        serde_json::from_str(&s).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        if let Some(dir) = path.parent().filter(|d| !d.as_os_str().is_empty()) {
+            fs::create_dir_all(dir)?;
+        }
+        let s = serde_json::to_string_pretty(self).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        fs::write(path, s + "\n")
+    }
+}
+
+/// One solver that appears in both reports.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Comparison<'a> {
+    pub before: &'a RunResult,
+    pub after: &'a RunResult,
+    pub slower: bool,
+    pub bigger: bool,
+    pub answer_changed: bool,
+}
+
+impl Comparison<'_> {
+    pub fn is_regression(&self) -> bool {
+        self.slower || self.bigger || self.answer_changed
+    }
+
+    /// Change in total time, as a fraction of the old time.
+    pub fn time_change(&self) -> f64 {
+        change(self.before.total_ns(), self.after.total_ns())
+    }
+}
+
+fn change(before: u64, after: u64) -> f64 {
+    (after as f64 - before as f64) / (before.max(1) as f64)
+}
+
+/// Pair up the solvers run in both reports. A solver regressed if its total time or peak memory grew by more
+/// than `threshold` (as a fraction, so `0.1` is 10%), or if its answer changed.
+pub fn compare<'a>(before: &'a Report, after: &'a Report, threshold: f64) -> Vec<Comparison<'a>> {
+    after
+        .results
+        .iter()
+        .filter_map(|a| {
+            let b = before.results.iter().find(|b| b.key() == a.key())?;
+            Some(Comparison {
+                before: b,
+                after: a,
+                slower: change(b.total_ns(), a.total_ns()) > threshold,
+                bigger: change(b.peak_memory_bytes, a.peak_memory_bytes) > threshold,
+                answer_changed: a.answer != b.answer,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(solver: &str, answer: &str, ns: u64, bytes: u64) -> RunResult {
+        RunResult {
+            year: 2023,
+            day: 4,
+            part: 1,
+            solver: solver.to_string(),
+            answer: answer.to_string(),
+            generator_ns: Some(ns / 2),
+            solver_ns: ns / 2,
+            peak_memory_bytes: bytes,
+        }
+    }
+
+    fn report(results: Vec<RunResult>) -> Report {
+        Report {
+            commit: Some("abc123".to_string()),
+            timestamp: 0,
+            results,
+        }
+    }
+
+    #[test]
+    pub fn test_compare() {
+        let before = report(vec![
+            result("peek_bits", "13", 1000, 100),
+            result("draft / draft", "13", 5000, 1000),
+            result("removed", "13", 10, 0),
+        ]);
+        let after = report(vec![
+            result("peek_bits", "13", 1200, 100),
+            result("draft / draft", "12", 5200, 2000),
+            result("added", "13", 10, 0),
+        ]);
+
+        let changes = compare(&before, &after, 0.1);
+        assert_eq!(2, changes.len());
+        let flags = |c: &Comparison| (c.after.solver.clone(), c.slower, c.bigger, c.answer_changed);
+        assert_eq!(("peek_bits".to_string(), true, false, false), flags(&changes[0]));
+        assert_eq!(("draft / draft".to_string(), false, true, true), flags(&changes[1]));
+        assert!((changes[0].time_change() - 0.2).abs() < 1e-9);
+        assert!(compare(&before, &before, 0.1).iter().all(|c| !c.is_regression()));
+    }
+
+    #[test]
+    pub fn test_round_trip() {
+        let r = report(vec![result("peek_bits", "13", 1000, 100)]);
+        let path = std::env::temp_dir().join(format!("aoc-report-{}.json", std::process::id()));
+        r.save(&path).unwrap();
+        assert_eq!(r, Report::load(&path).unwrap());
+        fs::remove_file(path).unwrap();
+    }
+}
@@ -0,0 +1,57 @@
+/// Leading forward differences of `samples`: `rv[j]` is the `j`th difference at the first sample. Stops early
+/// once a row of differences is all zero.
+fn forward_differences(samples: &[i64]) -> Vec<i64> {
+    let mut row = samples.to_vec();
+    let mut rv = Vec::new();
+    while !row.is_empty() && row.iter().any(|&x| x != 0) {
+        rv.push(row[0]);
+        row = row.windows(2).map(|w| w[1] - w[0]).collect();
+    }
+    rv
+}
+
+/// The value at index `n` of the lowest-degree polynomial through `samples`, taken as the values at
+/// `0, 1, 2, ...`. `n` may be negative or far beyond the samples.
+pub fn extrapolate(samples: &[i64], n: i64) -> i64 {
+    // Newton's forward formula: sum of C(n, j) times the jth difference.
+    let mut binomial: i128 = 1;
+    let mut rv: i128 = 0;
+    for (j, diff) in forward_differences(samples).into_iter().enumerate() {
+        rv += binomial * diff as i128;
+        binomial = binomial * (n as i128 - j as i128) / (j as i128 + 1);
+    }
+    rv as i64
+}
+
+/// The value following `samples`.
+pub fn next_term(samples: &[i64]) -> i64 {
+    extrapolate(samples, samples.len() as i64)
+}
+
+/// The value preceding `samples`.
+pub fn previous_term(samples: &[i64]) -> i64 {
+    extrapolate(samples, -1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case(&[0, 3, 6, 9, 12, 15], 18, -3; "linear")]
+    #[test_case(&[1, 3, 6, 10, 15, 21], 28, 0; "quadratic")]
+    #[test_case(&[10, 13, 16, 21, 30, 45], 68, 5; "cubic")]
+    #[test_case(&[0, 0, 0], 0, 0; "zeros")]
+    #[test_case(&[7], 7, 7; "single")]
+    pub fn test_next_and_previous(samples: &[i64], next: i64, previous: i64) {
+        assert_eq!(next, next_term(samples));
+        assert_eq!(previous, previous_term(samples));
+    }
+
+    #[test]
+    pub fn test_far_extrapolation() {
+        let f = |x: i64| 3 * x * x - 7 * x + 11;
+        let samples: Vec<i64> = (0..3).map(f).collect();
+        assert_eq!(f(202_300), extrapolate(&samples, 202_300));
+    }
+}
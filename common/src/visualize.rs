@@ -0,0 +1,436 @@
+//! Frame-by-frame terminal animation for simulations, switched on by `aoc run --visualize`, and recording of the
+//! same frames for `aoc run --export`.
+//!
+//! A simulation implements [`Visualize`] and calls [`show`] whenever it has something worth drawing. While
+//! visualization is off, `show` does nothing, so solvers can call it freely.
+//!
+//! Keys while playing: space pauses and resumes, `n` or `.` steps one frame while paused, `+` and `-` double and
+//! halve the frame rate, and `q` stops drawing and lets the solver finish.
+
+use std::fmt::Write as _;
+use std::io::{self, Read, Stdout, Write};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use termion::raw::{IntoRawMode, RawTerminal};
+use termion::{clear, color, cursor, style, AsyncReader};
+
+use crate::export::{self, Palette, Recorder};
+use crate::grid::{Grid, Pos};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Color {
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+    White,
+    Gray,
+}
+
+impl Color {
+    fn escape(self) -> String {
+        match self {
+            Color::Red => color::Fg(color::Red).to_string(),
+            Color::Green => color::Fg(color::Green).to_string(),
+            Color::Yellow => color::Fg(color::Yellow).to_string(),
+            Color::Blue => color::Fg(color::Blue).to_string(),
+            Color::Magenta => color::Fg(color::Magenta).to_string(),
+            Color::Cyan => color::Fg(color::Cyan).to_string(),
+            Color::White => color::Fg(color::White).to_string(),
+            Color::Gray => color::Fg(color::LightBlack).to_string(),
+        }
+    }
+}
+
+/// One character of a frame, with its styling.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Cell {
+    pub ch: char,
+    pub color: Option<Color>,
+    pub bold: bool,
+}
+
+impl Cell {
+    pub const fn new(ch: char) -> Cell {
+        Cell {
+            ch,
+            color: None,
+            bold: false,
+        }
+    }
+
+    pub const fn colored(ch: char, color: Color) -> Cell {
+        Cell {
+            ch,
+            color: Some(color),
+            bold: false,
+        }
+    }
+
+    pub const fn bold(self) -> Cell {
+        Cell { bold: true, ..self }
+    }
+}
+
+impl From<char> for Cell {
+    fn from(ch: char) -> Self {
+        Cell::new(ch)
+    }
+}
+
+/// A picture of a simulation at one moment: an optional title line above rows of cells.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Frame {
+    pub title: Option<String>,
+    pub rows: Vec<Vec<Cell>>,
+}
+
+impl Frame {
+    pub fn new() -> Frame {
+        Frame::default()
+    }
+
+    /// One row per grid row, with `f` picking the cell for each position.
+    pub fn from_grid<T>(grid: &Grid<T>, mut f: impl FnMut(Pos, &T) -> Cell) -> Frame {
+        Frame {
+            title: None,
+            rows: grid
+                .iter_rows()
+                .enumerate()
+                .map(|(r, row)| row.iter().enumerate().map(|(c, t)| f(Pos::new(r, c), t)).collect())
+                .collect(),
+        }
+    }
+
+    pub fn titled(self, title: impl Into<String>) -> Frame {
+        Frame {
+            title: Some(title.into()),
+            ..self
+        }
+    }
+
+    pub fn push_row(&mut self, cells: impl IntoIterator<Item = impl Into<Cell>>) {
+        self.rows.push(cells.into_iter().map(Into::into).collect());
+    }
+
+    /// Add a row of unstyled text.
+    pub fn push_text(&mut self, text: &str) {
+        self.push_row(text.chars());
+    }
+
+    /// The frame as plain text, one line per row, without any styling.
+    pub fn text(&self) -> String {
+        let mut rv = String::new();
+        for line in self
+            .title
+            .iter()
+            .cloned()
+            .chain(self.rows.iter().map(|row| row.iter().map(|c| c.ch).collect()))
+        {
+            rv.push_str(&line);
+            rv.push('\n');
+        }
+        rv
+    }
+
+    /// The frame with terminal escapes for its styling if `color` is set. Lines end in `\r\n`, since the terminal
+    /// is in raw mode while playing.
+    fn render(&self, color: bool) -> String {
+        let mut rv = String::new();
+        if let Some(title) = &self.title {
+            let _ = write!(
+                rv,
+                "{}{}{}{}\r\n",
+                style::Bold,
+                title,
+                style::Reset,
+                clear::UntilNewline
+            );
+        }
+        for row in &self.rows {
+            let mut styled = false;
+            for cell in row {
+                let wants_style = color && (cell.color.is_some() || cell.bold);
+                if styled {
+                    rv.push_str(style::Reset.as_ref());
+                }
+                if wants_style {
+                    if cell.bold {
+                        rv.push_str(style::Bold.as_ref());
+                    }
+                    if let Some(c) = cell.color {
+                        rv.push_str(&c.escape());
+                    }
+                }
+                styled = wants_style;
+                rv.push(cell.ch);
+            }
+            if styled {
+                rv.push_str(style::Reset.as_ref());
+            }
+            let _ = write!(rv, "{}\r\n", clear::UntilNewline);
+        }
+        rv
+    }
+}
+
+/// A simulation that can be drawn.
+pub trait Visualize {
+    fn frame(&self) -> Frame;
+
+    /// Colours for each kind of cell when the frames are exported as images.
+    fn palette(&self) -> Palette {
+        Palette::default()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Settings {
+    /// Frames drawn per second while playing.
+    pub fps: f64,
+    /// Whether to use the frames' colours and bold text.
+    pub color: bool,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Settings { fps: 20.0, color: true }
+    }
+}
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static PLAYER: Mutex<Option<Player>> = Mutex::new(None);
+static RECORDER: Mutex<Option<Recorder>> = Mutex::new(None);
+
+fn update_enabled(player: &Option<Player>, recorder: &Option<Recorder>) {
+    ENABLED.store(player.is_some() || recorder.is_some(), Ordering::Relaxed);
+}
+
+/// Play every later call to [`show`] in the terminal.
+pub fn enable(settings: Settings) {
+    let mut player = PLAYER.lock().unwrap();
+    *player = Some(Player::new(settings));
+    update_enabled(&player, &RECORDER.lock().unwrap());
+}
+
+/// Write every later frame to `path` until [`save_recording`] or [`finish`]. Fails if the path isn't a kind of
+/// image we can write.
+pub fn record(path: PathBuf, settings: export::Settings) -> io::Result<()> {
+    let mut recorder = RECORDER.lock().unwrap();
+    *recorder = Some(Recorder::new(path, settings)?);
+    update_enabled(&PLAYER.lock().unwrap(), &recorder);
+    Ok(())
+}
+
+/// Whether frames are being drawn. Worth checking before building an expensive frame or title.
+pub fn enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// Draw `sim`'s current frame, if visualization is on.
+pub fn show(sim: &impl Visualize) {
+    if enabled() {
+        draw(sim.frame(), &sim.palette());
+    }
+}
+
+/// Draw `sim`'s current frame under a title, if visualization is on. The title is only built when it's needed.
+pub fn show_titled(sim: &impl Visualize, title: impl FnOnce() -> String) {
+    if enabled() {
+        draw(sim.frame().titled(title()), &sim.palette());
+    }
+}
+
+/// Draw `frame` with the default palette, if visualization is on.
+pub fn show_frame(frame: Frame) {
+    if enabled() {
+        draw(frame, &Palette::default());
+    }
+}
+
+/// Record `frame`, then play it and wait out the rest of the frame time or until the user steps.
+fn draw(frame: Frame, palette: &Palette) {
+    let mut recorder = RECORDER.lock().unwrap();
+    if let Some(r) = recorder.as_mut() {
+        r.record(&frame, palette);
+    }
+    let mut player = PLAYER.lock().unwrap();
+    if let Some(p) = player.as_mut() {
+        if !p.play(&frame) {
+            *player = None;
+        }
+    }
+    update_enabled(&player, &recorder);
+}
+
+/// Stop recording and finish writing the file, leaving the terminal player as it is. Returns false if nothing was
+/// drawn, in which case no file is written.
+pub fn save_recording() -> io::Result<bool> {
+    let mut recorder = RECORDER.lock().unwrap();
+    let taken = recorder.take();
+    update_enabled(&PLAYER.lock().unwrap(), &recorder);
+    match taken {
+        Some(recorder) => recorder.save(),
+        None => Ok(false),
+    }
+}
+
+/// Stop visualizing, give the terminal back, and write out any recording.
+pub fn finish() -> io::Result<bool> {
+    ENABLED.store(false, Ordering::Relaxed);
+    PLAYER.lock().unwrap().take();
+    save_recording()
+}
+
+/// The terminal while frames are playing. Without a terminal on stdin, frames still play, but the keys do nothing.
+struct Player {
+    settings: Settings,
+    paused: bool,
+    keys: Option<(RawTerminal<Stdout>, AsyncReader)>,
+    frames: u64,
+}
+
+impl Player {
+    fn new(settings: Settings) -> Player {
+        let keys = io::stdout()
+            .into_raw_mode()
+            .ok()
+            .map(|raw| (raw, termion::async_stdin()));
+        print!("{}{}", clear::All, cursor::Hide);
+        Player {
+            settings,
+            paused: false,
+            keys,
+            frames: 0,
+        }
+    }
+
+    /// Draw one frame and wait. Returns false once the user has asked to stop.
+    fn play(&mut self, frame: &Frame) -> bool {
+        self.frames += 1;
+        let status = format!(
+            "frame {}  {:.0} fps  {}",
+            self.frames,
+            self.settings.fps,
+            if self.paused {
+                "[paused: space resumes, n steps]"
+            } else {
+                "[space pauses, +/- speed, q stops]"
+            }
+        );
+        let mut out = io::stdout().lock();
+        let _ = write!(
+            out,
+            "{}{}{}\r\n{}",
+            cursor::Goto(1, 1),
+            status,
+            clear::UntilNewline,
+            frame.render(self.settings.color)
+        );
+        let _ = write!(out, "{}", clear::AfterCursor);
+        let _ = out.flush();
+
+        let deadline = Instant::now() + Duration::from_secs_f64(1.0 / self.settings.fps.max(0.1));
+        loop {
+            for key in self.read_keys() {
+                match key {
+                    b' ' => self.paused = !self.paused,
+                    b'n' | b'.' if self.paused => return true,
+                    b'+' | b'=' => self.settings.fps = (self.settings.fps * 2.0).min(10_000.0),
+                    b'-' => self.settings.fps = (self.settings.fps / 2.0).max(0.25),
+                    b'q' => return false,
+                    // Ctrl-C doesn't raise a signal in raw mode.
+                    3 => {
+                        drop(self.keys.take());
+                        print!("{}", cursor::Show);
+                        std::process::exit(130);
+                    }
+                    _ => {}
+                }
+            }
+            if !self.paused && Instant::now() >= deadline {
+                return true;
+            }
+            thread::sleep(
+                Duration::from_millis(5)
+                    .min(deadline.saturating_duration_since(Instant::now()))
+                    .max(Duration::from_millis(1)),
+            );
+        }
+    }
+
+    fn read_keys(&mut self) -> Vec<u8> {
+        let mut buf = Vec::new();
+        if let Some((_, reader)) = self.keys.as_mut() {
+            let _ = reader.read_to_end(&mut buf);
+        }
+        buf
+    }
+}
+
+impl Drop for Player {
+    fn drop(&mut self) {
+        // Leave the last frame on screen, with the cursor below it.
+        let _ = write!(io::stdout(), "{}\r\n", cursor::Show);
+        let _ = io::stdout().flush();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Counter(u32);
+
+    impl Visualize for Counter {
+        fn frame(&self) -> Frame {
+            let mut frame = Frame::new().titled(format!("count {}", self.0));
+            frame.push_row((0..self.0).map(|_| Cell::colored('#', Color::Green)));
+            frame
+        }
+    }
+
+    #[test]
+    pub fn test_frame_text() {
+        let grid = Grid::parse_bytes("ab\ncd\n");
+        let frame = Frame::from_grid(&grid, |p, &b| {
+            if p == Pos::new(1, 0) {
+                Cell::new('*').bold()
+            } else {
+                Cell::new(b as char)
+            }
+        });
+        assert_eq!("ab\n*d\n", frame.text());
+        assert_eq!("count 3\n###\n", Counter(3).frame().text());
+    }
+
+    #[test]
+    pub fn test_render_color() {
+        let mut frame = Frame::new();
+        frame.push_row([Cell::new('a'), Cell::colored('b', Color::Red), Cell::new('c')]);
+        let plain = frame.render(false);
+        assert_eq!(format!("abc{}\r\n", clear::UntilNewline), plain);
+        let colored = frame.render(true);
+        assert_eq!(
+            format!(
+                "a{}b{}c{}\r\n",
+                color::Fg(color::Red),
+                style::Reset,
+                clear::UntilNewline
+            ),
+            colored
+        );
+    }
+
+    #[test]
+    pub fn test_show_is_noop_when_disabled() {
+        assert!(!enabled());
+        show(&Counter(1));
+    }
+}
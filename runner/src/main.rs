@@ -0,0 +1,643 @@
+use std::path::{Path, PathBuf};
+use std::process::{self, ExitCode};
+use std::time::{Duration, SystemTime};
+
+use aoc_common::inputs::{InputStore, Ledger, Verdict};
+use aoc_common::memory::PeakAlloc;
+use aoc_common::params;
+use aoc_common::registry::{Day, Solver, Timing};
+use aoc_common::report::{self, Report, RunResult};
+use aoc_common::{export, graph_export, visualize};
+use clap::{Parser, Subcommand};
+
+#[global_allocator]
+static ALLOC: PeakAlloc = PeakAlloc::new();
+
+#[derive(Parser)]
+#[command(name = "aoc", about = "Run Advent of Code solutions from every year")]
+struct Cli {
+    /// Input store root, holding `<year>/day<NN>.txt` and `answers.toml`. Defaults to `$AOC_INPUTS` or `./inputs`.
+    #[arg(long, global = true)]
+    inputs: Option<PathBuf>,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Run the solvers for a year, a single day, or everything.
+    Run {
+        year: Option<u32>,
+        day: Option<u32>,
+        #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: Option<u8>,
+        /// Only run solvers whose label, generator or solver slug matches.
+        #[arg(long)]
+        solver: Option<String>,
+        #[arg(long, conflicts_with_all = ["year", "day"])]
+        all: bool,
+        /// Override a puzzle parameter, e.g. `--param target_y=10`. May be repeated.
+        #[arg(long = "param", value_name = "KEY=VALUE", value_parser = params::parse_override)]
+        params: Vec<(String, String)>,
+        /// Also write the results, timings and peak memory as a JSON report.
+        #[arg(long, value_name = "PATH")]
+        report: Option<PathBuf>,
+        /// Animate simulations that support it in the terminal while they run.
+        #[arg(long)]
+        visualize: bool,
+        /// Frames per second for `--visualize` and `--export` animations.
+        #[arg(long, default_value_t = 20.0)]
+        fps: f64,
+        /// Draw `--visualize` frames without colour.
+        #[arg(long, requires = "visualize")]
+        no_color: bool,
+        /// Write the frames of simulations that support it to an image: `.gif` or `.apng` for an animation, or
+        /// `.png` for the last frame.
+        #[arg(long, value_name = "PATH")]
+        export: Option<PathBuf>,
+        /// Keep only one frame in this many in `--export` animations.
+        #[arg(long, default_value_t = 1, value_parser = clap::value_parser!(u32).range(1..), requires = "export")]
+        export_every: u32,
+        /// Pixels per cell in `--export` images.
+        #[arg(long, default_value_t = 4, value_parser = clap::value_parser!(u32).range(1..=64), requires = "export")]
+        scale: u32,
+        /// Write the graph of days built around one, with the solution highlighted: `.dot` or `.gv` for Graphviz DOT,
+        /// or `.graphml` for GraphML.
+        #[arg(long, value_name = "PATH")]
+        graph: Option<PathBuf>,
+    },
+    /// Compare two JSON reports, failing if any solver got slower, used more memory, or changed its answer.
+    Compare {
+        before: PathBuf,
+        after: PathBuf,
+        /// Percentage a solver's time or memory may grow by before it counts as a regression.
+        #[arg(long, default_value_t = 10.0)]
+        threshold: f64,
+    },
+    /// Record an accepted answer in the ledger.
+    Accept {
+        year: u32,
+        day: u32,
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: u8,
+        answer: String,
+    },
+    /// List every registered day and its solvers.
+    List,
+}
+
+struct Row {
+    year: u32,
+    day: u32,
+    part: u8,
+    label: &'static str,
+    timing: Timing,
+    peak_memory: usize,
+    verdict: Verdict,
+    /// The `--export` file this solver drew into, if it drew anything.
+    exported: Option<PathBuf>,
+    export_error: Option<String>,
+    /// The `--graph` file this solver wrote, if it wrote one.
+    graphed: Option<PathBuf>,
+    graph_error: Option<String>,
+}
+
+/// Where `--export` and `--graph` write: the given path when a single solver is selected, otherwise one file per
+/// solver with the day, part and solver added to the name.
+struct Output {
+    path: PathBuf,
+    per_solver: bool,
+}
+
+impl Output {
+    fn path_for(&self, day: &Day, part: u8, label: &str) -> PathBuf {
+        if !self.per_solver {
+            return self.path.clone();
+        }
+        let stem = self.path.file_stem().and_then(|s| s.to_str()).unwrap_or("frames");
+        let ext = self.path.extension().and_then(|e| e.to_str()).unwrap_or_default();
+        let label: String = label
+            .replace(" / ", "-")
+            .chars()
+            .map(|c| if c.is_alphanumeric() || c == '_' || c == '-' { c } else { '-' })
+            .collect();
+        self.path
+            .with_file_name(format!("{}-{}-day{:02}-part{}-{}.{}", stem, day.year, day.day, part, label, ext))
+    }
+}
+
+struct Export {
+    output: Output,
+    settings: export::Settings,
+}
+
+fn all_days() -> Vec<Day> {
+    let mut days = advent_of_code_2022::days();
+    days.extend(advent_of_code_2023::days());
+    days.extend(advent_of_code_2024::days());
+    days
+}
+
+/// Input from the store, falling back to whatever the day compiled in.
+fn load_input(store: &InputStore, day: &Day) -> Option<String> {
+    match store.load_input(day.year, day.day) {
+        Ok(input) => Some(input),
+        Err(_) if !day.input.is_empty() => Some(day.input.to_string()),
+        Err(_) => None,
+    }
+}
+
+/// Overridden keys that none of `days` declares, so they would be silently ignored.
+fn unknown_params<'a>(days: &[Day], params: &'a [(String, String)]) -> Vec<&'a str> {
+    params
+        .iter()
+        .map(|(key, _)| key.as_str())
+        .filter(|key| !days.iter().any(|d| d.params.contains(key)))
+        .collect()
+}
+
+/// The solvers of `day` picked by `--part` and `--solver`.
+fn selected<'a>(day: &'a Day, part: Option<u8>, solver: Option<&'a str>) -> impl Iterator<Item = (u8, &'a Solver)> {
+    [1, 2]
+        .into_iter()
+        .filter(move |p| part.is_none_or(|want| want == *p))
+        .flat_map(move |p| day.part(p).iter().map(move |s| (p, s)))
+        .filter(move |(_, s)| solver.is_none_or(|slug| s.matches(slug)))
+}
+
+fn run(
+    days: &[Day],
+    store: &InputStore,
+    ledger: &Ledger,
+    part: Option<u8>,
+    solver: Option<&str>,
+    export: Option<&Export>,
+    graph: Option<&Output>,
+) -> Vec<Row> {
+    let mut rows = Vec::new();
+    for day in days {
+        let Some(input) = load_input(store, day) else {
+            println!(
+                "## AOC {}, Day {}: No input found at {}.",
+                day.year,
+                day.day,
+                store.input_path(day.year, day.day).display()
+            );
+            continue;
+        };
+        for (p, s) in selected(day, part, solver) {
+            let path = export.map(|e| e.output.path_for(day, p, s.label));
+            let mut export_error = None;
+            if let (Some(e), Some(path)) = (export, &path) {
+                if let Err(err) = visualize::record(path.clone(), e.settings) {
+                    export_error = Some(format!("Could not export to {}: {}", path.display(), err));
+                }
+            }
+            let graph_path = graph.map(|g| g.path_for(day, p, s.label));
+            let mut graph_error = None;
+            if let Some(path) = &graph_path {
+                if let Err(err) = graph_export::record(path.clone()) {
+                    graph_error = Some(format!("Could not write the graph to {}: {}", path.display(), err));
+                }
+            }
+            let baseline = ALLOC.reset_peak();
+            let timing = s.time(&input);
+            let peak_memory = ALLOC.peak() - baseline;
+            let mut exported = None;
+            if export_error.is_none() {
+                match visualize::save_recording() {
+                    Ok(true) => exported = path,
+                    Ok(false) => {}
+                    Err(err) => {
+                        let path = path.unwrap_or_default();
+                        export_error = Some(format!("Could not export to {}: {}", path.display(), err));
+                    }
+                }
+            }
+            let mut graphed = None;
+            if graph_error.is_none() {
+                match graph_export::finish() {
+                    Ok(true) => graphed = graph_path,
+                    Ok(false) => {}
+                    Err(err) => {
+                        let path = graph_path.unwrap_or_default();
+                        graph_error = Some(format!("Could not write the graph to {}: {}", path.display(), err));
+                    }
+                }
+            }
+            rows.push(Row {
+                year: day.year,
+                day: day.day,
+                part: p,
+                label: s.label,
+                verdict: ledger.check(day.year, day.day, p, &timing.answer),
+                timing,
+                peak_memory,
+                exported,
+                export_error,
+                graphed,
+                graph_error,
+            });
+        }
+    }
+    rows
+}
+
+fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["B", "KiB", "MiB", "GiB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} B", bytes)
+    } else {
+        format!("{:.1} {}", value, UNITS[unit])
+    }
+}
+
+fn print_table(rows: &[Row]) {
+    if rows.is_empty() {
+        return;
+    }
+    let answer_cell = |r: &Row| {
+        if r.timing.answer.contains('\n') {
+            "(see below)".to_string()
+        } else {
+            r.timing.answer.clone()
+        }
+    };
+    let label_w = rows.iter().map(|r| r.label.len()).chain(["Solver".len()]).max().unwrap();
+    let answer_w = rows.iter().map(|r| answer_cell(r).len()).chain(["Answer".len()]).max().unwrap();
+
+    println!(
+        "Year  Day  Part  {:label_w$}  {:answer_w$}  Status   {:>10}  {:>10}  {:>10}",
+        "Solver", "Answer", "Generate", "Solve", "Memory"
+    );
+    for r in rows {
+        let status = match r.verdict {
+            Verdict::Correct => "correct",
+            Verdict::Wrong => "WRONG",
+            Verdict::Unknown => "unknown",
+        };
+        let generate = r.timing.generator.map_or("-".to_string(), |d| format!("{:.2?}", d));
+        println!(
+            "{:<4}  {:>3}  {:>4}  {:label_w$}  {:answer_w$}  {:7}  {:>10}  {:>10}  {:>10}",
+            r.year,
+            r.day,
+            r.part,
+            r.label,
+            answer_cell(r),
+            status,
+            generate,
+            format!("{:.2?}", r.timing.solver),
+            format_bytes(r.peak_memory as u64)
+        );
+    }
+
+    for r in rows.iter().filter(|r| r.timing.answer.contains('\n')) {
+        println!("\n{} day {} part {} ({}):\n{}", r.year, r.day, r.part, r.label, r.timing.answer);
+    }
+}
+
+/// The commit being run, marked `-dirty` if tracked files have changed. `None` outside a git checkout.
+fn git_commit() -> Option<String> {
+    let git = |args: &[&str]| {
+        let out = process::Command::new("git").args(args).output().ok()?;
+        out.status.success().then(|| String::from_utf8_lossy(&out.stdout).trim().to_string())
+    };
+    let commit = git(&["rev-parse", "HEAD"])?;
+    let dirty = git(&["status", "--porcelain", "--untracked-files=no"]).is_some_and(|s| !s.is_empty());
+    Some(if dirty { format!("{}-dirty", commit) } else { commit })
+}
+
+fn build_report(rows: &[Row]) -> Report {
+    let nanos = |d: Duration| d.as_nanos() as u64;
+    Report {
+        commit: git_commit(),
+        timestamp: SystemTime::now().duration_since(SystemTime::UNIX_EPOCH).map_or(0, |d| d.as_secs()),
+        results: rows
+            .iter()
+            .map(|r| RunResult {
+                year: r.year,
+                day: r.day,
+                part: r.part,
+                solver: r.label.to_string(),
+                answer: r.timing.answer.clone(),
+                generator_ns: r.timing.generator.map(nanos),
+                solver_ns: nanos(r.timing.solver),
+                peak_memory_bytes: r.peak_memory as u64,
+            })
+            .collect(),
+    }
+}
+
+/// Print how every solver common to both reports changed. Returns false if any regressed.
+fn print_comparison(before: &Report, after: &Report, threshold: f64) -> bool {
+    let changes = report::compare(before, after, threshold / 100.0);
+    let commit = |r: &Report| r.commit.clone().unwrap_or_else(|| "unknown commit".to_string());
+    println!("Comparing {} against {}", commit(after), commit(before));
+    if changes.is_empty() {
+        println!("No solvers in common.");
+        return true;
+    }
+
+    let label_w = changes.iter().map(|c| c.after.solver.len()).chain(["Solver".len()]).max().unwrap();
+    println!(
+        "Year  Day  Part  {:label_w$}  {:>10}  {:>10}  {:>8}  {:>10}  {:>10}",
+        "Solver", "Before", "After", "Change", "Mem before", "Mem after"
+    );
+    for c in &changes {
+        let mut flags = Vec::new();
+        if c.slower {
+            flags.push("SLOWER");
+        }
+        if c.bigger {
+            flags.push("MORE MEMORY");
+        }
+        if c.answer_changed {
+            flags.push("ANSWER CHANGED");
+        }
+        let line = format!(
+            "{:<4}  {:>3}  {:>4}  {:label_w$}  {:>10}  {:>10}  {:>+7.1}%  {:>10}  {:>10}  {}",
+            c.after.year,
+            c.after.day,
+            c.after.part,
+            c.after.solver,
+            format!("{:.2?}", Duration::from_nanos(c.before.total_ns())),
+            format!("{:.2?}", Duration::from_nanos(c.after.total_ns())),
+            c.time_change() * 100.0,
+            format_bytes(c.before.peak_memory_bytes),
+            format_bytes(c.after.peak_memory_bytes),
+            flags.join(", ")
+        );
+        println!("{}", line.trim_end());
+    }
+
+    let regressions = changes.iter().filter(|c| c.is_regression()).count();
+    if regressions > 0 {
+        println!("\n{} of {} solvers regressed by more than {}%.", regressions, changes.len(), threshold);
+    }
+    regressions == 0
+}
+
+fn load_report(path: &Path) -> Option<Report> {
+    Report::load(path).map_err(|e| eprintln!("Could not read {}: {}", path.display(), e)).ok()
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    let mut days = all_days();
+    let store = cli.inputs.map(InputStore::new).unwrap_or_else(InputStore::from_env);
+    let mut ledger = match store.load_ledger() {
+        Ok(ledger) => ledger,
+        Err(e) => {
+            eprintln!("Could not read {}: {}", store.ledger_path().display(), e);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    match cli.command {
+        Command::Compare {
+            before,
+            after,
+            threshold,
+        } => {
+            let (Some(before), Some(after)) = (load_report(&before), load_report(&after)) else {
+                return ExitCode::FAILURE;
+            };
+            if !print_comparison(&before, &after, threshold) {
+                return ExitCode::FAILURE;
+            }
+        }
+        Command::List => {
+            for day in &days {
+                println!("{} day {}", day.year, day.day);
+                for p in [1, 2] {
+                    for s in day.part(p) {
+                        println!("    part {}: {}", p, s.label);
+                    }
+                }
+            }
+        }
+        Command::Accept {
+            year,
+            day,
+            part,
+            answer,
+        } => {
+            ledger.set(year, day, part, &answer);
+            if let Err(e) = store.save_ledger(&ledger) {
+                eprintln!("Could not write {}: {}", store.ledger_path().display(), e);
+                return ExitCode::FAILURE;
+            }
+        }
+        Command::Run {
+            year,
+            day,
+            part,
+            solver,
+            all,
+            params,
+            report,
+            visualize,
+            fps,
+            no_color,
+            export,
+            export_every,
+            scale,
+            graph,
+        } => {
+            if !all && year.is_none() {
+                eprintln!("Pass a year (and optionally a day), or --all.");
+                return ExitCode::FAILURE;
+            }
+            days.retain(|d| year.is_none_or(|y| y == d.year) && day.is_none_or(|n| n == d.day));
+            if days.is_empty() {
+                eprintln!("No solutions registered for that selection.");
+                return ExitCode::FAILURE;
+            }
+            let unknown = unknown_params(&days, &params);
+            if !unknown.is_empty() {
+                eprintln!("No selected day takes the parameter(s): {}", unknown.join(", "));
+                return ExitCode::FAILURE;
+            }
+            params::set_overrides(params);
+            if visualize {
+                visualize::enable(visualize::Settings { fps, color: !no_color });
+            }
+            let count: usize = days.iter().map(|d| selected(d, part, solver.as_deref()).count()).sum();
+            let per_solver = count > 1;
+            let export = match export {
+                Some(path) => {
+                    if let Err(e) = export::Format::from_path(&path) {
+                        eprintln!("Could not export to {}: {}", path.display(), e);
+                        return ExitCode::FAILURE;
+                    }
+                    Some(Export {
+                        output: Output { path, per_solver },
+                        settings: export::Settings {
+                            scale: scale as usize,
+                            every: export_every as usize,
+                            fps,
+                        },
+                    })
+                }
+                None => None,
+            };
+            let graph = match graph {
+                Some(path) => {
+                    if let Err(e) = graph_export::Format::from_path(&path) {
+                        eprintln!("Could not write the graph to {}: {}", path.display(), e);
+                        return ExitCode::FAILURE;
+                    }
+                    Some(Output { path, per_solver })
+                }
+                None => None,
+            };
+            let rows = run(&days, &store, &ledger, part, solver.as_deref(), export.as_ref(), graph.as_ref());
+            // Recordings are already saved per solver, so this only gives the terminal back.
+            let _ = visualize::finish();
+            if rows.is_empty() && days.iter().any(|d| load_input(&store, d).is_some()) {
+                eprintln!("No solvers matched.");
+                return ExitCode::FAILURE;
+            }
+            print_table(&rows);
+            if let Some(path) = report {
+                if let Err(e) = build_report(&rows).save(&path) {
+                    eprintln!("Could not write {}: {}", path.display(), e);
+                    return ExitCode::FAILURE;
+                }
+            }
+            if let Some(export) = &export {
+                for path in rows.iter().filter_map(|r| r.exported.as_ref()) {
+                    println!("Exported {}", path.display());
+                }
+                let errors: Vec<&String> = rows.iter().filter_map(|r| r.export_error.as_ref()).collect();
+                for e in &errors {
+                    eprintln!("{}", e);
+                }
+                if !errors.is_empty() {
+                    return ExitCode::FAILURE;
+                }
+                if rows.iter().all(|r| r.exported.is_none()) {
+                    eprintln!("Could not export to {}: nothing was drawn", export.output.path.display());
+                    return ExitCode::FAILURE;
+                }
+            }
+            if let Some(graph) = &graph {
+                for path in rows.iter().filter_map(|r| r.graphed.as_ref()) {
+                    println!("Exported {}", path.display());
+                }
+                let errors: Vec<&String> = rows.iter().filter_map(|r| r.graph_error.as_ref()).collect();
+                for e in &errors {
+                    eprintln!("{}", e);
+                }
+                if !errors.is_empty() {
+                    return ExitCode::FAILURE;
+                }
+                if rows.iter().all(|r| r.graphed.is_none()) {
+                    eprintln!("Could not write the graph to {}: no selected solver has one", graph.path.display());
+                    return ExitCode::FAILURE;
+                }
+            }
+            if rows.iter().any(|r| r.verdict == Verdict::Wrong) {
+                return ExitCode::FAILURE;
+            }
+        }
+    }
+
+    ExitCode::SUCCESS
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn select(wanted: &[(u32, u32)]) -> Vec<Day> {
+        let mut days = all_days();
+        days.retain(|d| wanted.contains(&(d.year, d.day)));
+        days
+    }
+
+    #[test]
+    pub fn test_unknown_params() {
+        let days = select(&[(2022, 15), (2023, 11)]);
+        let params = ["target_y=10", "scale_factor=99", "target_z=1"].map(|p| params::parse_override(p).unwrap());
+        assert_eq!(vec!["target_z"], unknown_params(&days, &params));
+        assert_eq!(vec!["target_y"], unknown_params(&days[1..], &params[..1]));
+    }
+
+    #[test]
+    pub fn test_export_paths() {
+        let days = select(&[(2022, 14)]);
+        let mut output = Output {
+            path: PathBuf::from("out/sand.gif"),
+            per_solver: false,
+        };
+        assert_eq!(PathBuf::from("out/sand.gif"), output.path_for(&days[0], 2, "part1 / noabyss"));
+        output.per_solver = true;
+        assert_eq!(
+            PathBuf::from("out/sand-2022-day14-part2-part1-noabyss.gif"),
+            output.path_for(&days[0], 2, "part1 / noabyss")
+        );
+        assert_eq!(2, selected(&days[0], Some(2), None).count());
+        assert_eq!(1, selected(&days[0], Some(2), Some("part2")).count());
+    }
+
+    /// Every selected day reads its own keys out of the shared overrides.
+    #[test]
+    pub fn test_params_across_days() {
+        let root = std::env::temp_dir().join(format!("aoc-runner-params-{}", process::id()));
+        std::fs::create_dir_all(root.join("2022")).unwrap();
+        std::fs::create_dir_all(root.join("2023")).unwrap();
+        let sensors = [
+            "Sensor at x=2, y=18: closest beacon is at x=-2, y=15",
+            "Sensor at x=9, y=16: closest beacon is at x=10, y=16",
+            "Sensor at x=13, y=2: closest beacon is at x=15, y=3",
+            "Sensor at x=12, y=14: closest beacon is at x=10, y=16",
+            "Sensor at x=10, y=20: closest beacon is at x=10, y=16",
+            "Sensor at x=14, y=17: closest beacon is at x=10, y=16",
+            "Sensor at x=8, y=7: closest beacon is at x=2, y=10",
+            "Sensor at x=2, y=0: closest beacon is at x=2, y=10",
+            "Sensor at x=0, y=11: closest beacon is at x=2, y=10",
+            "Sensor at x=20, y=14: closest beacon is at x=25, y=17",
+            "Sensor at x=17, y=20: closest beacon is at x=21, y=22",
+            "Sensor at x=16, y=7: closest beacon is at x=15, y=3",
+            "Sensor at x=14, y=3: closest beacon is at x=15, y=3",
+            "Sensor at x=20, y=1: closest beacon is at x=15, y=3",
+        ];
+        std::fs::write(root.join("2022/day15.txt"), sensors.join("\n")).unwrap();
+        let galaxies = [
+            "...#......",
+            ".......#..",
+            "#.........",
+            "..........",
+            "......#...",
+            ".#........",
+            ".........#",
+            "..........",
+            ".......#..",
+            "#...#.....",
+        ];
+        std::fs::write(root.join("2023/day11.txt"), galaxies.join("\n")).unwrap();
+
+        let days = select(&[(2022, 15), (2023, 11)]);
+        let store = InputStore::new(&root);
+        let overrides = ["target_y=10", "max_coord=20", "scale_factor=99"].map(|p| params::parse_override(p).unwrap());
+        let rows = params::with_overrides(overrides, || run(&days, &store, &Ledger::default(), None, None, None, None));
+        std::fs::remove_dir_all(&root).unwrap();
+
+        let answers = |year| {
+            rows.iter()
+                .filter(|r| r.year == year)
+                .map(|r| (r.part, r.timing.answer.clone()))
+                .collect::<Vec<_>>()
+        };
+        assert!(answers(2022).contains(&(1, "26".to_string())));
+        assert!(answers(2022).contains(&(2, "56000011".to_string())));
+        assert!(answers(2023).contains(&(2, "8410".to_string())));
+    }
+}